@@ -0,0 +1,119 @@
+//! Benchmarks measuring the throughput of [`TaskPool`] and [`KeyedLimiter`] under various
+//! concurrency settings, to help choose sensible defaults for `--max-downloads` and `--max-per-track`.
+//!
+//! Run with `cargo bench --features bench`.
+
+use std::future::Future;
+use std::time::Duration;
+
+use auxiliaire::limiter::KeyedLimiter;
+use auxiliaire::task_pool::TaskPool;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mini_exercism::http;
+use tokio::runtime::Runtime;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Number of tasks spawned in each benchmark iteration.
+const NUM_TASKS: usize = 200;
+
+/// Tracks used as limiter keys; tasks are spread evenly between them.
+const TRACKS: [&str; 4] = ["rust", "python", "go", "elixir"];
+
+/// Spawns [`NUM_TASKS`] tasks in a [`TaskPool`], each of which needs a permit from
+/// a [`KeyedLimiter`] configured with the given limits before running, and waits for them.
+async fn run_tasks<F, Fut>(max_downloads: usize, max_per_track: Option<usize>, task: F)
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = auxiliaire::Result<()>> + Send + 'static,
+{
+    let limiter = KeyedLimiter::new(max_downloads, max_per_track);
+    let mut task_pool = TaskPool::new();
+
+    for i in 0..NUM_TASKS {
+        let limiter = limiter.clone();
+        let task = task(i);
+        task_pool.spawn(format!("task {i}"), async move {
+            let _permit = limiter.get_permit_for(TRACKS[i % TRACKS.len()]).await;
+            task.await
+        });
+    }
+
+    task_pool.join(|| "benchmark task failed").await.unwrap();
+}
+
+/// Measures the overhead of the task pool and limiter themselves, using tasks that do no work.
+fn bench_overhead(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("overhead");
+    group.throughput(Throughput::Elements(NUM_TASKS as u64));
+    for max_downloads in [1, 4, 16, 64] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(max_downloads),
+            &max_downloads,
+            |b, &max_downloads| {
+                b.to_async(&runtime).iter(|| {
+                    run_tasks(max_downloads, None, |_| async {
+                        tokio::task::yield_now().await;
+                        Ok(())
+                    })
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Measures download throughput against a synthetic local server that simulates
+/// Exercism's latency, for various combinations of global and per-track limits.
+fn bench_downloads(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mock_server = runtime.block_on(async {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("x".repeat(4096))
+                    .set_delay(Duration::from_millis(20)),
+            )
+            .mount(&mock_server)
+            .await;
+        mock_server
+    });
+    let http_client = http::Client::new();
+
+    let mut group = c.benchmark_group("downloads");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(NUM_TASKS as u64));
+    for (max_downloads, max_per_track) in
+        [(1, None), (4, None), (16, None), (64, None), (16, Some(2)), (64, Some(8))]
+    {
+        let id = match max_per_track {
+            Some(max_per_track) => format!("{max_downloads}/{max_per_track}-per-track"),
+            None => max_downloads.to_string(),
+        };
+        group.bench_function(BenchmarkId::from_parameter(id), |b| {
+            b.to_async(&runtime).iter(|| {
+                run_tasks(max_downloads, max_per_track, |i| {
+                    let http_client = http_client.clone();
+                    let url = format!("{}/files/{i}", mock_server.uri());
+                    async move {
+                        http_client
+                            .get(url)
+                            .send()
+                            .await?
+                            .error_for_status()?
+                            .bytes()
+                            .await?;
+                        Ok(())
+                    }
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_overhead, bench_downloads);
+criterion_main!(benches);
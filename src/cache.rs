@@ -0,0 +1,259 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use ring::digest::{digest, SHA256};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::{debug, instrument, trace};
+
+use crate::command::args::ResponseCacheArgs;
+use crate::command::backup::manifest::hex_digest;
+use crate::Result;
+
+pub const RESPONSES_DIR_NAME: &str = "responses";
+
+/// Number of hexadecimal characters of the hash used as [cache scope](cache_scope).
+const SCOPE_LEN: usize = 16;
+
+/// On-disk cache of Exercism API responses, shared between commands.
+///
+/// Responses are stored in a subdirectory of the cache directory named after the cache's
+/// [scope](cache_scope), so that responses fetched with different API tokens or from different
+/// API endpoints are never mixed up.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: Option<PathBuf>,
+    ttl: Duration,
+}
+
+/// How long a cached response can be used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Expiry {
+    /// Response expires after the cache's TTL.
+    Ttl,
+
+    /// Response never expires, because its key changes when its content does.
+    Never,
+}
+
+impl ResponseCache {
+    /// Creates a cache storing responses in the given scope (see [`cache_scope`]).
+    pub fn new(args: &ResponseCacheArgs, scope: &str) -> Self {
+        Self {
+            dir: args
+                .cache_dir
+                .as_ref()
+                .map(|dir| dir.join(RESPONSES_DIR_NAME).join(scope)),
+            ttl: Duration::from_secs(args.cache_ttl),
+        }
+    }
+
+    /// Returns the cached value for `key` if there is one, otherwise calls `fetch` and caches its result.
+    ///
+    /// Failing to read from or write to the cache is never an error; the value is simply fetched again.
+    #[instrument(level = "trace", skip(self, fetch))]
+    pub async fn get_or_fetch<T, F, Fut>(&self, key: &str, expiry: Expiry, fetch: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let Some(entry_path) = self.entry_path(key) else {
+            return fetch().await;
+        };
+
+        if let Some(value) = self.load(&entry_path, expiry).await {
+            trace!("Cache hit");
+            return Ok(value);
+        }
+
+        let value = fetch().await?;
+        if let Err(err) = self.save(&entry_path, &value).await {
+            debug!("Failed to cache response for {key}: {err:#}");
+        }
+        Ok(value)
+    }
+
+    /// Removes all cached responses in the given cache directory.
+    pub async fn clear(cache_dir: &Path) -> Result<()> {
+        let responses_dir = cache_dir.join(RESPONSES_DIR_NAME);
+        match fs::remove_dir_all(&responses_dir).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err).with_context(|| {
+                format!("failed to remove cached responses in {}", responses_dir.display())
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| {
+            let file_name = key
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+                .collect::<String>();
+            dir.join(format!("{file_name}.json"))
+        })
+    }
+
+    async fn load<T>(&self, entry_path: &Path, expiry: Expiry) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let content = fs::read_to_string(entry_path).await.ok()?;
+        let entry = serde_json::from_str::<CacheEntry<T>>(&content).ok()?;
+
+        let age = unix_time(SystemTime::now()).saturating_sub(entry.cached_at);
+        (expiry == Expiry::Never || age < self.ttl.as_secs()).then_some(entry.value)
+    }
+
+    async fn save<T>(&self, entry_path: &Path, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let entry = CacheEntry { cached_at: unix_time(SystemTime::now()), value };
+        let content = serde_json::to_string(&entry)?;
+
+        if let Some(parent) = entry_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        // Multiple tasks (or processes) could be caching the same response,
+        // so write to a temporary file first to avoid partial reads.
+        let mut temp_path = entry_path.as_os_str().to_owned();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        fs::write(&temp_path, content).await?;
+        fs::rename(&temp_path, entry_path).await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the scope of cached responses fetched using the given API token from the given
+/// Exercism API base URLs (see [`ResponseCache::new`]).
+///
+/// The scope is a hash, so that the token cannot be recovered from the cache directory.
+pub fn cache_scope(api_token: &str, api_base_urls: &[&str]) -> String {
+    let scope = api_base_urls
+        .iter()
+        .fold(api_token.to_string(), |scope, url| format!("{scope}\n{url}"));
+
+    hex_digest(&digest(&SHA256, scope.as_bytes()))[..SCOPE_LEN].to_string()
+}
+
+/// Returns the cache key for a page of solutions fetched with the given filters.
+pub fn solutions_page_key(
+    track: Option<&str>,
+    exercise: Option<&str>,
+    published: bool,
+    page: i64,
+) -> String {
+    format!(
+        "v2/solutions/track-{}/exercise-{}/published-{published}/page-{page}",
+        track.unwrap_or_default(),
+        exercise.unwrap_or_default()
+    )
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    cached_at: u64,
+    value: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use test_log::test;
+
+    use super::*;
+
+    fn get_cache(dir: &Path, ttl: u64) -> ResponseCache {
+        ResponseCache::new(
+            &ResponseCacheArgs { cache_dir: Some(dir.into()), cache_ttl: ttl },
+            &cache_scope("some_token", &["https://exercism.org/api/v2"]),
+        )
+    }
+
+    async fn fetch_counted(cache: &ResponseCache, expiry: Expiry, count: &AtomicUsize) -> String {
+        cache
+            .get_or_fetch("v2/solutions?page=1", expiry, || async {
+                count.fetch_add(1, Ordering::Relaxed);
+                Ok("value".to_string())
+            })
+            .await
+            .unwrap()
+    }
+
+    #[test(tokio::test)]
+    async fn test_caching() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = get_cache(dir.path(), 3600);
+        let count = AtomicUsize::new(0);
+
+        assert_eq!("value", fetch_counted(&cache, Expiry::Ttl, &count).await);
+        assert_eq!("value", fetch_counted(&cache, Expiry::Ttl, &count).await);
+        assert_eq!(1, count.load(Ordering::Relaxed));
+
+        ResponseCache::clear(dir.path()).await.unwrap();
+        assert_eq!("value", fetch_counted(&cache, Expiry::Ttl, &count).await);
+        assert_eq!(2, count.load(Ordering::Relaxed));
+    }
+
+    #[test(tokio::test)]
+    async fn test_scopes() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = ResponseCacheArgs { cache_dir: Some(dir.path().into()), cache_ttl: 3600 };
+        let api_base_urls = ["https://exercism.org/api/v2"];
+        let cache = ResponseCache::new(&args, &cache_scope("some_token", &api_base_urls));
+        let other_token_cache =
+            ResponseCache::new(&args, &cache_scope("other_token", &api_base_urls));
+        let other_url_cache = ResponseCache::new(
+            &args,
+            &cache_scope("some_token", &["https://exercism.example.com/api/v2"]),
+        );
+        let count = AtomicUsize::new(0);
+
+        fetch_counted(&cache, Expiry::Ttl, &count).await;
+        fetch_counted(&other_token_cache, Expiry::Ttl, &count).await;
+        fetch_counted(&other_url_cache, Expiry::Ttl, &count).await;
+        assert_eq!(3, count.load(Ordering::Relaxed));
+
+        fetch_counted(&other_token_cache, Expiry::Ttl, &count).await;
+        assert_eq!(3, count.load(Ordering::Relaxed));
+        assert!(!cache_scope("some_token", &api_base_urls).contains("some_token"));
+    }
+
+    #[test(tokio::test)]
+    async fn test_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = get_cache(dir.path(), 0);
+        let count = AtomicUsize::new(0);
+
+        fetch_counted(&cache, Expiry::Ttl, &count).await;
+        fetch_counted(&cache, Expiry::Ttl, &count).await;
+        assert_eq!(2, count.load(Ordering::Relaxed));
+
+        fetch_counted(&cache, Expiry::Never, &count).await;
+        assert_eq!(2, count.load(Ordering::Relaxed));
+    }
+
+    #[test(tokio::test)]
+    async fn test_disabled() {
+        let cache = ResponseCache::new(&ResponseCacheArgs::default(), "");
+        let count = AtomicUsize::new(0);
+
+        fetch_counted(&cache, Expiry::Ttl, &count).await;
+        fetch_counted(&cache, Expiry::Ttl, &count).await;
+        assert_eq!(2, count.load(Ordering::Relaxed));
+    }
+}
@@ -1,11 +1,59 @@
 //! Definition of supported CLI commands.
 
+pub mod args;
 pub mod backup;
+pub mod cache;
+pub mod changes;
+pub mod community;
+pub mod exercises;
+pub mod iterations;
+pub mod list;
+pub mod man;
+pub mod open;
+pub mod search;
+pub mod serve;
+pub mod size;
+pub mod solution;
+pub mod status;
+pub mod tracks;
+pub mod verify;
 
 use clap::Subcommand;
+use mini_exercism::core::Credentials;
 
 use crate::command::backup::args::BackupArgs;
 use crate::command::backup::BackupCommand;
+use crate::command::cache::args::CacheArgs;
+use crate::command::cache::CacheCommand;
+use crate::command::changes::args::ChangesArgs;
+use crate::command::changes::ChangesCommand;
+use crate::command::community::args::CommunityArgs;
+use crate::command::community::CommunityCommand;
+use crate::command::exercises::args::ExercisesArgs;
+use crate::command::exercises::ExercisesCommand;
+use crate::command::iterations::args::IterationsArgs;
+use crate::command::iterations::IterationsCommand;
+use crate::command::list::args::ListArgs;
+use crate::command::list::ListCommand;
+use crate::command::man::args::ManArgs;
+use crate::command::man::ManCommand;
+use crate::command::open::args::OpenArgs;
+use crate::command::open::OpenCommand;
+use crate::command::search::args::SearchArgs;
+use crate::command::search::SearchCommand;
+use crate::command::serve::args::ServeArgs;
+use crate::command::serve::ServeCommand;
+use crate::command::size::args::SizeArgs;
+use crate::command::size::SizeCommand;
+use crate::command::solution::args::SolutionArgs;
+use crate::command::solution::SolutionCommand;
+use crate::command::status::args::StatusArgs;
+use crate::command::status::StatusCommand;
+use crate::command::tracks::args::TracksArgs;
+use crate::command::tracks::TracksCommand;
+use crate::command::verify::args::VerifyArgs;
+use crate::command::verify::VerifyCommand;
+use crate::credentials::credentials_provider;
 use crate::Result;
 
 /// Possible commands supported by our CLI application.
@@ -23,7 +71,128 @@ pub enum Command {
     /// by default, the API token configured for the local installation of the Exercism CLI application
     /// will be used. The command does not require the Exercism CLI to work, but if it's not installed,
     /// then the API token will have to be specified (see --token).
-    Backup(BackupArgs),
+    ///
+    /// To back up solutions of multiple accounts at once, use --account once per account.
+    Backup(Box<BackupArgs>),
+
+    /// List Exercism.org solutions
+    ///
+    /// Fetches the list of solutions submitted to the Exercism.org website and prints them, without
+    /// downloading any file. Solutions are printed as they are fetched; use --format jsonl to output
+    /// one JSON object per solution so that other tools can process the list while it's being fetched.
+    ///
+    /// Like the backup command, this command needs an Exercism API token (see --token).
+    List(ListArgs),
+
+    /// List joined Exercism.org tracks
+    ///
+    /// Fetches the list of tracks joined on the Exercism.org website and prints them, along with
+    /// the number of exercises completed and published in each track and the date of the last
+    /// activity in the track. This can be used to get a quick overview before choosing which
+    /// solutions to back up.
+    ///
+    /// Like the backup command, this command needs an Exercism API token (see --token).
+    Tracks(TracksArgs),
+
+    /// List the exercises of an Exercism.org track and their backup status
+    ///
+    /// Fetches the list of exercises in a track from the Exercism.org website and compares it with
+    /// solutions stored in a directory where solutions have been backed up (see the backup
+    /// command). Each exercise is listed with the status of its solution, if any, and whether
+    /// that solution is backed up, stale (changed since the last backup) or missing locally.
+    ///
+    /// Like the backup command, this command needs an Exercism API token (see --token).
+    Exercises(ExercisesArgs),
+
+    /// Show information about a single Exercism.org solution
+    ///
+    /// Fetches the metadata of a solution from the Exercism.org website (UUID, status, mentoring
+    /// status, URLs, iterations, etc.) and prints it along with the state of its local backup,
+    /// if it has been backed up (see the backup command).
+    Solution(SolutionArgs),
+
+    /// List the iterations of an Exercism.org solution
+    ///
+    /// Fetches the iteration history of a solution from the Exercism.org website and prints it,
+    /// indicating for each iteration whether it has been backed up locally (see the backup
+    /// command's --iterations option).
+    #[command(alias = "iteration")]
+    Iterations(IterationsArgs),
+
+    /// Download someone's published Exercism.org solution
+    ///
+    /// Fetches the files of a solution published on the Exercism.org website (identified by its
+    /// public URL) and stores them, along with the solution's metadata, in the _community directory
+    /// of a directory where solutions have been backed up. This makes it possible to keep solutions
+    /// shared in forums around for study.
+    Community(CommunityArgs),
+
+    /// Report disk usage of a backup directory
+    ///
+    /// Scans a directory where solutions have been backed up (see the backup command) and reports
+    /// how much disk space is used per track, along with the biggest solutions. For each, the space
+    /// used by the solution's current files is reported separately from the space used by
+    /// its backed up iterations.
+    Size(SizeArgs),
+
+    /// Report the status of backed up solutions
+    ///
+    /// Checks solutions stored in a directory where solutions have been backed up (see the backup
+    /// command) against their backup state and reports any inconsistency. Solutions are also compared
+    /// with those on the Exercism.org website to find solutions that are outdated or that have not been
+    /// backed up yet, unless --offline is used.
+    Status(StatusArgs),
+
+    /// Report what changed on Exercism.org since the last backup
+    ///
+    /// Compares solutions on the Exercism.org website with the backup state stored in a directory
+    /// where solutions have been backed up (see the backup command) and prints a change log: new
+    /// solutions, new iterations, solutions that were completed or published and changes in
+    /// mentoring status. Nothing is downloaded.
+    ///
+    /// Like the backup command, this command needs an Exercism API token (see --token).
+    Changes(ChangesArgs),
+
+    /// Verify the integrity of backed up files
+    ///
+    /// Hashes every file stored in a directory where solutions have been backed up (see the backup
+    /// command) and compares it with the manifest written at the end of the last backup. Files that
+    /// were corrupted, modified, removed or added since are reported. This check is performed
+    /// entirely offline; to compare backed up solutions with Exercism.org, see the status command.
+    Verify(VerifyArgs),
+
+    /// Open a backed up solution in a browser or editor
+    ///
+    /// Locates a solution in a directory where solutions have been backed up (see the backup
+    /// command) and opens its page on the Exercism.org website. It's also possible to open
+    /// the solution's public page instead, or to open its directory in an editor.
+    Open(OpenArgs),
+
+    /// Search the content of backed up solutions
+    ///
+    /// Searches the files of solutions stored in a directory where solutions have been backed up
+    /// (see the backup command), including backed up iterations. Matching lines are printed along
+    /// with the path of their file and their line number. See options for ways to filter
+    /// the solutions and files to search.
+    Search(SearchArgs),
+
+    /// Browse backed up solutions in a web browser
+    ///
+    /// Starts a small local HTTP server that renders a directory where solutions have been backed
+    /// up (see the backup command), so that tracks, exercises, solution files and backed up
+    /// iterations can be browsed in a web browser. Nothing is written to the backup directory.
+    Serve(ServeArgs),
+
+    /// Manage the cache of Exercism API responses
+    ///
+    /// When the --cache-dir option is passed to the backup or list commands, responses from the
+    /// Exercism API are cached in that directory and reused across runs. This command can be used
+    /// to clear that cache.
+    Cache(CacheArgs),
+
+    /// Generate man pages for auxiliaire and its commands
+    #[command(hide = true)]
+    Man(ManArgs),
 }
 
 impl Command {
@@ -32,10 +201,38 @@ impl Command {
     /// This method is provided explicitly in order to make it `async`.
     pub async fn execute(self) -> Result<()> {
         match self {
+            Command::Backup(args) if !args.account.is_empty() => {
+                BackupCommand::execute_accounts(*args, None).await
+            },
             Command::Backup(args) => {
-                let backup_command = BackupCommand::new(args, None)?;
+                let backup_command = BackupCommand::new(*args, None)?;
                 BackupCommand::execute(backup_command).await
             },
+            Command::List(args) => ListCommand::new(args, None)?.execute().await,
+            Command::Tracks(args) => TracksCommand::new(args, None)?.execute().await,
+            Command::Exercises(args) => ExercisesCommand::new(args, None)?.execute().await,
+            Command::Solution(args) => SolutionCommand::new(args, None)?.execute().await,
+            Command::Iterations(args) => IterationsCommand::new(args, None)?.execute().await,
+            Command::Community(args) => CommunityCommand::new(args, None)?.execute().await,
+            Command::Size(args) => SizeCommand::new(args).execute().await,
+            Command::Status(args) => StatusCommand::new(args, None)?.execute().await,
+            Command::Changes(args) => ChangesCommand::new(args, None)?.execute().await,
+            Command::Verify(args) => VerifyCommand::new(args).execute().await,
+            Command::Open(args) => OpenCommand::new(args).execute().await,
+            Command::Search(args) => SearchCommand::new(args)?.execute().await,
+            Command::Serve(args) => ServeCommand::new(args).execute().await,
+            Command::Cache(args) => CacheCommand::new(args).execute().await,
+            Command::Man(args) => ManCommand::new(args).execute(),
         }
     }
 }
+
+/// Returns the [`Credentials`] to use to access the Exercism API.
+///
+/// See [`credentials_provider`] for details.
+pub(crate) fn get_credentials(
+    token: Option<&str>,
+    token_command: Option<&str>,
+) -> Result<Credentials> {
+    credentials_provider(token, token_command).credentials()
+}
@@ -0,0 +1,298 @@
+//! Arguments shared by multiple commands.
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use clap::Args;
+use regex::Regex;
+
+use crate::Error;
+
+/// Reference to a solution, in the form `<track>/<exercise>` (e.g. `rust/poker`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SolutionRef {
+    /// Name of the solution's track.
+    pub track: String,
+
+    /// Name of the solution's exercise.
+    pub exercise: String,
+}
+
+impl SolutionRef {
+    /// Returns the path where this solution is stored in the given backup directory.
+    pub fn path_in(&self, backup_path: &Path) -> PathBuf {
+        [backup_path, Path::new(&self.track), Path::new(&self.exercise)]
+            .iter()
+            .collect()
+    }
+}
+
+impl FromStr for SolutionRef {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((track, exercise))
+                if !track.is_empty() && !exercise.is_empty() && !exercise.contains('/') =>
+            {
+                Ok(Self { track: track.into(), exercise: exercise.into() })
+            },
+            _ => Err(anyhow!("invalid solution '{s}': expected <track>/<exercise>")),
+        }
+    }
+}
+
+impl Display for SolutionRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.track, self.exercise)
+    }
+}
+
+/// Filter selecting solutions by track and exercise, usually loaded from a filter file
+/// (see [`load`](Self::load)).
+///
+/// Each line of a filter file contains a pattern in the form `<track>/<exercise>` (or `<track>`
+/// to match all exercises of a track), in which `*` matches any number of characters and `?`
+/// matches a single character. Lines starting with `!` exclude matching solutions instead.
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// A solution matches the filter if it matches at least one include pattern (or if there are
+/// none) and does not match any exclude pattern.
+#[derive(Debug, Clone, Default)]
+pub struct SolutionFilter {
+    includes: Vec<Regex>,
+    excludes: Vec<Regex>,
+}
+
+impl SolutionFilter {
+    /// Loads a filter from the file at the given path.
+    pub fn load(path: &str) -> crate::Result<Self> {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read filter file {path}"))?
+            .parse()
+            .with_context(|| format!("invalid filter file {path}"))
+    }
+
+    /// Determines if the solution to the given exercise matches this filter.
+    pub fn matches(&self, track_name: &str, exercise_name: &str) -> bool {
+        let solution = format!("{track_name}/{exercise_name}");
+
+        (self.includes.is_empty() || self.includes.iter().any(|re| re.is_match(&solution)))
+            && !self.excludes.iter().any(|re| re.is_match(&solution))
+    }
+}
+
+impl FromStr for SolutionFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+
+        for (line_number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (patterns, pattern) = match line.strip_prefix('!') {
+                Some(pattern) => (&mut filter.excludes, pattern.trim()),
+                None => (&mut filter.includes, line),
+            };
+            let pattern = match pattern.split('/').count() {
+                1 => format!("{pattern}/*"),
+                2 => pattern.to_string(),
+                _ => {
+                    return Err(anyhow!(
+                        "invalid pattern '{pattern}' on line {}: expected <track>/<exercise>",
+                        line_number + 1
+                    ))
+                },
+            };
+            patterns.push(glob_regex(&pattern));
+        }
+
+        Ok(filter)
+    }
+}
+
+/// Glob pattern matching the files of a solution (e.g. `*.png` or `src/*.rs`).
+///
+/// A pattern containing a `/` is matched against the file's path in the solution;
+/// otherwise, it is matched against the file's name only.
+#[derive(Debug, Clone)]
+pub struct FilePattern {
+    regex: Regex,
+    match_path: bool,
+}
+
+impl FilePattern {
+    /// Determines if the given solution file (a path using `/` as separator) matches this pattern.
+    pub fn matches(&self, file: &str) -> bool {
+        let file = if self.match_path { file } else { file.rsplit('/').next().unwrap_or(file) };
+
+        self.regex.is_match(file)
+    }
+}
+
+impl FromStr for FilePattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = s.trim().trim_start_matches('/');
+        if pattern.is_empty() {
+            return Err(anyhow!("invalid file pattern '{s}': pattern cannot be empty"));
+        }
+
+        Ok(Self { regex: glob_regex(pattern), match_path: pattern.contains('/') })
+    }
+}
+
+/// Converts a glob pattern in which `*` and `?` never match `/` to an anchored regex.
+fn glob_regex(pattern: &str) -> Regex {
+    let regex = pattern
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join("[^/]")
+        })
+        .collect::<Vec<_>>()
+        .join("[^/]*");
+
+    Regex::new(&format!("^{regex}$")).expect("escaped glob pattern should be a valid regex")
+}
+
+/// Arguments controlling the on-disk cache of Exercism API responses.
+#[derive(Debug, Clone, Default, Args)]
+pub struct ResponseCacheArgs {
+    /// Directory where to cache Exercism API responses; if unspecified, responses are not cached
+    #[arg(long, env = "AUXILIAIRE_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Number of seconds after which cached lists of solutions and iterations expire
+    #[arg(long, value_name = "SECONDS", default_value_t = 3600, env = "AUXILIAIRE_CACHE_TTL")]
+    pub cache_ttl: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod solution_ref {
+        use assert_matches::assert_matches;
+
+        use super::*;
+
+        #[test]
+        fn test_from_str() {
+            assert_matches!("rust/poker".parse::<SolutionRef>(), Ok(solution_ref) => {
+                assert_eq!("rust", solution_ref.track);
+                assert_eq!("poker", solution_ref.exercise);
+                assert_eq!("rust/poker", solution_ref.to_string());
+            });
+
+            assert!("rust".parse::<SolutionRef>().is_err());
+            assert!("rust/".parse::<SolutionRef>().is_err());
+            assert!("/poker".parse::<SolutionRef>().is_err());
+            assert!("rust/poker/extra".parse::<SolutionRef>().is_err());
+        }
+
+        #[test]
+        fn test_path_in() {
+            let solution_ref: SolutionRef = "rust/poker".parse().unwrap();
+
+            assert_eq!(
+                [Path::new("backup"), Path::new("rust"), Path::new("poker")]
+                    .iter()
+                    .collect::<PathBuf>(),
+                solution_ref.path_in(Path::new("backup"))
+            );
+        }
+    }
+
+    mod solution_filter {
+        use super::*;
+
+        #[test]
+        fn test_matches() {
+            let filter: SolutionFilter = "
+                # Rust and Clojure solutions, except hello-world
+                rust
+                clojure/*
+                !*/hello-world
+
+                python/poker
+                go/?lock
+            "
+            .parse()
+            .unwrap();
+
+            assert!(filter.matches("rust", "poker"));
+            assert!(filter.matches("clojure", "two-fer"));
+            assert!(!filter.matches("rust", "hello-world"));
+            assert!(!filter.matches("clojure", "hello-world"));
+            assert!(filter.matches("python", "poker"));
+            assert!(!filter.matches("python", "clock"));
+            assert!(filter.matches("go", "clock"));
+            assert!(!filter.matches("go", "clocks"));
+            assert!(!filter.matches("elixir", "poker"));
+        }
+
+        #[test]
+        fn test_excludes_only() {
+            let filter: SolutionFilter = "!rust/*\n!python/poker".parse().unwrap();
+
+            assert!(!filter.matches("rust", "poker"));
+            assert!(!filter.matches("python", "poker"));
+            assert!(filter.matches("python", "clock"));
+            assert!(SolutionFilter::default().matches("rust", "poker"));
+        }
+
+        #[test]
+        fn test_invalid() {
+            let err = "rust/poker/extra".parse::<SolutionFilter>().unwrap_err();
+            assert!(err.to_string().contains("line 1"), "{err}");
+        }
+
+        #[test]
+        fn test_load() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let filter_file_path = temp_dir.path().join("filters.txt");
+            std::fs::write(&filter_file_path, "rust/poker\n").unwrap();
+
+            let filter = SolutionFilter::load(filter_file_path.to_str().unwrap()).unwrap();
+            assert!(filter.matches("rust", "poker"));
+            assert!(!filter.matches("rust", "clock"));
+
+            assert!(SolutionFilter::load("does/not/exist.txt").is_err());
+        }
+    }
+
+    mod file_pattern {
+        use super::*;
+
+        #[test]
+        fn test_matches() {
+            let pattern: FilePattern = "*.png".parse().unwrap();
+            assert!(pattern.matches("logo.png"));
+            assert!(pattern.matches("assets/logo.png"));
+            assert!(!pattern.matches("logo.png.rs"));
+            assert!(!pattern.matches("png/lib.rs"));
+
+            let pattern: FilePattern = "src/?.rs".parse().unwrap();
+            assert!(pattern.matches("src/a.rs"));
+            assert!(!pattern.matches("src/ab.rs"));
+            assert!(!pattern.matches("lib/src/a.rs"));
+            assert!(!pattern.matches("a.rs"));
+        }
+
+        #[test]
+        fn test_invalid() {
+            assert!("".parse::<FilePattern>().is_err());
+            assert!("/".parse::<FilePattern>().is_err());
+        }
+    }
+}
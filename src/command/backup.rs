@@ -3,38 +3,109 @@
 pub mod args;
 #[macro_use]
 mod detail;
-mod iterations;
-mod state;
-
-use std::collections::HashSet;
+pub mod builder;
+mod debug_http;
+pub(crate) mod difficulty;
+pub(crate) mod history;
+mod icons;
+mod immutable;
+mod interrupt;
+pub(crate) mod iterations;
+mod lock;
+pub(crate) mod manifest;
+mod mentoring;
+mod metrics;
+mod mirror;
+pub(crate) mod paths;
+mod permissions;
+mod plan;
+mod progress;
+mod prompt;
+mod report;
+pub(crate) mod snapshot;
+pub(crate) mod state;
+mod workspace;
+
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::io::IsTerminal;
+use std::iter;
+use std::num::NonZeroUsize;
 use std::panic::resume_unwind;
 use std::path::{Path, PathBuf};
+use std::slice;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use itertools::Itertools;
 use mini_exercism::api::v2::iteration::Iteration;
-use mini_exercism::api::v2::solution::Solution;
-use mini_exercism::api::v2::{solution, solutions};
-use mini_exercism::cli::get_cli_credentials;
-use mini_exercism::core::Credentials;
+use mini_exercism::api::v2::solution::{MentoringStatus, Solution};
+use mini_exercism::api::v2::{solution, solutions, submission};
 use mini_exercism::stream::StreamExt;
 use mini_exercism::{api, http};
+use similar::TextDiff;
 use tokio::io::{AsyncWriteExt, BufWriter};
-use tokio::{fs, spawn};
+use tokio::sync::Mutex;
+use tokio::{fs, spawn, time};
 use tracing::{debug, enabled, error, info, instrument, trace, warn, Level};
 
-use crate::command::backup::args::{BackupArgs, OverwritePolicy, SolutionStatus};
+use crate::cache::{solutions_page_key, Expiry, ResponseCache};
+use crate::command::args::SolutionRef;
+use crate::command::backup::args::{
+    AccountToken, BackupArgs, DebugHttp, LongPathsPolicy, OutputLayout, OverwritePolicy,
+    RemotelyDeletedAction, SolutionId, SolutionStatus, TestsStatus,
+};
+use crate::command::backup::builder::{build_clients, BackupCommandBuilder};
+use crate::command::backup::debug_http::{error_outcome, Redactor};
+use crate::command::backup::detail::solution_desc;
+use crate::command::backup::difficulty::ExerciseDifficulties;
+use crate::command::backup::history::BackupHistory;
+use crate::command::backup::icons::Icon;
+use crate::command::backup::immutable::{is_immutable, set_immutable};
+use crate::command::backup::interrupt::{interrupted, AbortOnDrop, PendingPaths, GRACE_PERIOD};
 use crate::command::backup::iterations::{
-    get_iterations_dir_name, SyncOps, ITERATIONS_DIR_ENV_VAR_NAME,
+    get_iterations_dir_name, list_iterations_in, plan_iteration_sync, IterationMetadata, SyncOps,
+    ITERATIONS_DIR_ENV_VAR_NAME, ITERATION_METADATA_FILE_NAME,
+};
+use crate::command::backup::lock::SolutionLock;
+use crate::command::backup::manifest::{
+    backup_files, hash_file, sign_manifest, FileHasher, Manifest,
+};
+use crate::command::backup::mentoring::{save_mentoring_requests, MentoringRequest};
+use crate::command::backup::metrics::Metrics;
+use crate::command::backup::mirror::mirror_solution;
+use crate::command::backup::paths::{check_path_length, shorten_file_path, ShortenedPaths};
+use crate::command::backup::permissions::Modes;
+use crate::command::backup::plan::{Plan, PlannedSolution};
+use crate::command::backup::progress::{ProgressEvent, ProgressReporter};
+use crate::command::backup::prompt::{
+    ask_overwrite, can_prompt, file_changes_summary, FileChange, OverwriteAnswer,
+};
+use crate::command::backup::report::{ErrorReport, FailedSolution};
+use crate::command::backup::snapshot::{
+    create_snapshot, prune_snapshots, snapshot_output_path, solution_snapshots_path,
 };
 use crate::command::backup::state::{
-    BackupState, AUXILIAIRE_STATE_DIR_NAME, BACKUP_STATE_FILE_NAME, BACKUP_STATE_TEMP_FILE_NAME,
+    BackupState, LastIterationMarker, AUXILIAIRE_STATE_DIR_NAME, BACKUP_STATE_FILE_NAME,
+    BACKUP_STATE_TEMP_FILE_NAME,
 };
-use crate::limiter::Limiter;
+use crate::command::backup::workspace::{ExercismMetadata, EXERCISM_METADATA_FILE_NAME};
+use crate::command::size::human_size;
+use crate::command::solution::solution_uuid;
+use crate::credentials::{credentials_provider, CredentialsProvider};
+use crate::error::Interrupted;
+use crate::limiter::{KeyedLimiter, Limiter, RateLimiter};
 use crate::task_pool::TaskPool;
-use crate::Result;
+use crate::walk::subdirectories;
+use crate::{Result, USER_AGENT};
+
+/// Name of the directory, in a backup directory, where solutions that no longer exist
+/// on Exercism.org are archived (see [`BackupArgs::remotely_deleted`]).
+pub const ARCHIVE_DIR_NAME: &str = "_archive";
 
 /// Command wrapper used for the [`Backup`](crate::command::Command::Backup) command.
 ///
@@ -55,43 +126,178 @@ use crate::Result;
 #[derive(Debug)]
 pub struct BackupCommand {
     args: BackupArgs,
+    output_path: PathBuf,
     v1_client: api::v1::Client,
     v2_client: api::v2::Client,
-    limiter: Limiter,
+    limiter: KeyedLimiter,
+    metadata_limiter: KeyedLimiter,
+    download_limiter: KeyedLimiter,
+    iteration_files_limiter: Limiter,
+    modes: Modes,
+    rate_limiter: RateLimiter,
     iterations_dir_name: String,
     iterations_dir_filter: String,
+    metrics: Metrics,
+    max_total_size_reached: AtomicBool,
+    response_cache: ResponseCache,
+    exercise_difficulties: ExerciseDifficulties,
+    overwrite_all: Mutex<bool>,
+    plan: Option<Plan>,
+    solutions_to_retry: Option<HashSet<String>>,
+    failed_solutions: std::sync::Mutex<Vec<FailedSolution>>,
+    mentoring_requests: std::sync::Mutex<Vec<MentoringRequest>>,
+    http_client: http::Client,
+    redactor: Redactor,
+    icons: std::sync::Mutex<HashSet<PathBuf>>,
+    remote_solutions: std::sync::Mutex<HashSet<(String, String)>>,
+    backed_up_solutions: std::sync::Mutex<Vec<(String, String)>>,
+    downloaded_solutions: std::sync::Mutex<Vec<(Solution, PathBuf)>>,
+    /// Size and hash of each downloaded file, only recorded with `--verify-after`.
+    downloaded_files: std::sync::Mutex<HashMap<PathBuf, (u64, String)>>,
+    /// Files that were not backed up because of `--max-file-size`.
+    skipped_files: std::sync::Mutex<Vec<(Solution, String)>>,
+    interrupted: AtomicBool,
+    pending_paths: PendingPaths,
+    progress: ProgressReporter,
 }
 
 impl BackupCommand {
     /// Creates a new [`BackupCommand`] using the provided [`args`](BackupArgs).
     ///
+    /// Credentials are resolved from the [`token`](BackupArgs::token) and
+    /// [`token_command`](BackupArgs::token_command) arguments (see [`credentials_provider`]).
+    ///
     /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
     pub fn new(args: BackupArgs, api_base_url: Option<&str>) -> Result<Arc<Self>> {
+        let credentials_provider =
+            credentials_provider(args.token.as_deref(), args.token_command.as_deref());
+
+        Self::with_credentials_provider(args, credentials_provider.as_ref(), api_base_url)
+    }
+
+    /// Creates a new [`BackupCommand`] using the provided [`args`](BackupArgs), fetching
+    /// credentials from the given [`CredentialsProvider`].
+    ///
+    /// The [`token`](BackupArgs::token) and [`token_command`](BackupArgs::token_command)
+    /// arguments are ignored.
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn with_credentials_provider(
+        args: BackupArgs,
+        credentials_provider: &dyn CredentialsProvider,
+        api_base_url: Option<&str>,
+    ) -> Result<Arc<Self>> {
+        let (v1_client, v2_client, cache_scope, api_token) =
+            build_clients(credentials_provider, &args, api_base_url)?;
+
+        Self::with_clients(args, v1_client, v2_client, &cache_scope, Some(&api_token), None, None)
+    }
+
+    /// Returns a [builder](BackupCommandBuilder) that can be used to create a [`BackupCommand`]
+    /// with custom credentials or API clients.
+    pub fn builder(args: BackupArgs) -> BackupCommandBuilder {
+        BackupCommandBuilder::new(args)
+    }
+
+    fn with_clients(
+        mut args: BackupArgs,
+        v1_client: api::v1::Client,
+        v2_client: api::v2::Client,
+        cache_scope: &str,
+        api_token: Option<&str>,
+        limiters: Option<(KeyedLimiter, RateLimiter)>,
+        progress: Option<ProgressReporter>,
+    ) -> Result<Arc<Self>> {
+        if args.overwrite == OverwritePolicy::Ask && !can_prompt() {
+            info!("Input is not interactive; existing solutions will not be overwritten");
+            args.overwrite = OverwritePolicy::Never;
+        }
+        if args.no_delete && args.remotely_deleted != RemotelyDeletedAction::Report {
+            bail!("--no-delete can only be used with --remotely-deleted report");
+        }
+        let modes = Modes { file: args.file_mode, dir: args.dir_mode };
+        if cfg!(not(unix)) && modes.is_set() {
+            bail!("--file-mode and --dir-mode are only supported on Unix");
+        }
+
+        let (limiter, rate_limiter) = limiters.unwrap_or_else(|| {
+            (
+                KeyedLimiter::new(args.max_downloads, args.max_per_track.map(NonZeroUsize::get)),
+                RateLimiter::new(args.max_rpm),
+            )
+        });
+        let metadata_limiter = limiter.child(args.max_metadata_requests.map(NonZeroUsize::get));
+        let download_limiter = limiter.child(args.max_file_downloads.map(NonZeroUsize::get));
+        let iteration_files_limiter = Limiter::new(
+            args.max_file_downloads
+                .map_or(args.max_downloads, NonZeroUsize::get),
+        );
+        let response_cache = ResponseCache::new(&args.cache, cache_scope);
+        let plan = (args.dry_run && io::stdout().is_terminal()).then(Plan::default);
+        let solutions_to_retry = match (&args.error_report, args.retry_failed) {
+            (Some(error_report), true) => Some(
+                ErrorReport::load(error_report)?
+                    .map(|report| {
+                        report
+                            .failed_solutions
+                            .into_iter()
+                            .map(|solution| solution.uuid)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        };
+        let progress = match progress {
+            Some(progress) => progress,
+            None => ProgressReporter::new(args.progress, args.progress_fd)?,
+        };
         let http_client = http::Client::builder()
-            .cookie_store(true)
+            .user_agent(USER_AGENT)
             .build()
             .with_context(|| "failed to create HTTP client")?;
-        let credentials = args
-            .token
-            .as_ref()
-            .map(|token| Ok(Credentials::from_api_token(token)))
-            .unwrap_or_else(|| {
-                get_cli_credentials().with_context(|| "failed to get Exercism CLI credentials")
-            })?;
-
-        let v1_client = build_client!(api::v1::Client, http_client, credentials, api_base_url);
-        let v2_client = build_client!(api::v2::Client, http_client, credentials, api_base_url);
-        let limiter = Limiter::new(args.max_downloads);
+        let redactor = Redactor::new(&args, api_token);
         let iterations_dir_name = get_iterations_dir_name();
         let iterations_dir_filter = format!("{iterations_dir_name}/");
+        let output_path = if args.snapshot {
+            snapshot_output_path(&args.path, args.dry_run)?
+        } else {
+            args.path.clone()
+        };
 
         Ok(Arc::new(Self {
             args,
+            output_path,
             v1_client,
             v2_client,
             limiter,
+            metadata_limiter,
+            download_limiter,
+            iteration_files_limiter,
+            modes,
+            rate_limiter,
             iterations_dir_name,
             iterations_dir_filter,
+            metrics: Metrics::default(),
+            max_total_size_reached: AtomicBool::new(false),
+            response_cache,
+            exercise_difficulties: ExerciseDifficulties::default(),
+            overwrite_all: Mutex::new(false),
+            plan,
+            solutions_to_retry,
+            failed_solutions: std::sync::Mutex::default(),
+            mentoring_requests: std::sync::Mutex::default(),
+            http_client,
+            redactor,
+            icons: std::sync::Mutex::default(),
+            remote_solutions: std::sync::Mutex::default(),
+            backed_up_solutions: std::sync::Mutex::default(),
+            downloaded_solutions: std::sync::Mutex::default(),
+            downloaded_files: std::sync::Mutex::default(),
+            skipped_files: std::sync::Mutex::default(),
+            interrupted: AtomicBool::new(false),
+            pending_paths: PendingPaths::default(),
+            progress,
         }))
     }
 
@@ -100,18 +306,277 @@ impl BackupCommand {
     /// See [struct description](Self) for details on how to call this method.
     #[instrument(skip_all)]
     pub async fn execute(this: Arc<Self>) -> Result<()> {
+        let start = Instant::now();
+        let mut result = Self::backup_until_interrupted(Arc::clone(&this)).await;
+        if result.is_ok() && this.args.verify_after {
+            result = this.verify_backup().await;
+        }
+
+        let slowest_solutions = this.metrics.slowest_solutions(this.args.slowest_solutions);
+        if !slowest_solutions.is_empty() {
+            info!(
+                "Slowest solution(s):\n{}",
+                slowest_solutions
+                    .iter()
+                    .map(|timing| format!("  {timing}"))
+                    .join("\n")
+            );
+        }
+        let skipped_files = this.skipped_files.lock().unwrap().clone();
+        if let Some(max_file_size) = this
+            .args
+            .max_file_size
+            .filter(|_| !skipped_files.is_empty())
+        {
+            warn!(
+                "File(s) skipped because they are larger than {}:\n{}",
+                human_size(max_file_size),
+                skipped_files
+                    .iter()
+                    .map(|(solution, file)| format!("  {}: {file}", solution_desc(solution)))
+                    .join("\n")
+            );
+        }
+        this.progress.report(ProgressEvent::Finished {
+            success: result.is_ok(),
+            files_downloaded: this.metrics.total_files_downloaded(),
+            bytes_downloaded: this.metrics.total_bytes_downloaded(),
+            slowest_solutions: &slowest_solutions,
+        });
+
+        // Saving the outputs of the backup can fail, but this must not hide the backup's own error,
+        // so each output is saved even if a previous one could not be.
+        let mut output_errors = Vec::new();
+        if let Some(metrics_file) = &this.args.metrics_file {
+            output_errors.extend(
+                this.metrics
+                    .save(metrics_file, start.elapsed(), result.is_ok())
+                    .await
+                    .err(),
+            );
+        }
+        if let Some(mentoring_requests) = &this.args.mentoring_requests {
+            let requests = this.mentoring_requests.lock().unwrap().clone();
+            output_errors.extend(
+                save_mentoring_requests(requests, mentoring_requests)
+                    .await
+                    .err(),
+            );
+        }
+        if let Some(error_report) = &this.args.error_report {
+            let report_result = match &result {
+                Ok(()) => ErrorReport::remove(error_report).await,
+                Err(err) => {
+                    let failed_solutions = this.failed_solutions.lock().unwrap().clone();
+                    ErrorReport::new(err, failed_solutions)
+                        .save(error_report)
+                        .await
+                },
+            };
+            output_errors.extend(report_result.err());
+        }
+        if !this.args.dry_run && !this.is_interrupted() {
+            output_errors.extend(this.update_backup_metadata().await.err());
+        }
+        if let Some(keep_snapshots) = this
+            .args
+            .keep_snapshots
+            .filter(|_| result.is_ok() && !this.args.dry_run)
+        {
+            output_errors.extend(
+                prune_snapshots(&this.args.path, keep_snapshots as usize)
+                    .await
+                    .err(),
+            );
+        }
+
+        // Only one error can be returned; if the backup itself failed, its error takes precedence.
+        let mut output_errors = output_errors.into_iter();
+        let result = result.and_then(|()| output_errors.next().map_or(Ok(()), Err));
+        for output_error in output_errors {
+            error!("{output_error:#}");
+        }
+        result
+    }
+
+    /// Updates the backup history and manifest of the backup directory and of its
+    /// [mirrors](BackupArgs::mirror) once all solutions have been backed up.
+    #[instrument(level = "debug", skip_all)]
+    async fn update_backup_metadata(&self) -> Result<()> {
+        let backed_up_solutions = self.backed_up_solutions.lock().unwrap().clone();
+        BackupHistory::update(
+            &self.output_path,
+            backed_up_solutions
+                .iter()
+                .map(|(track, exercise)| (track.as_str(), exercise.as_str())),
+        )
+        .await?;
+        Manifest::update(&self.output_path, self.args.checksum).await?;
+        if let Some(sign_key) = &self.args.sign_key {
+            sign_manifest(&self.output_path, sign_key).await?;
+        }
+        self.apply_modes_outside_solutions().await?;
+
+        for mirror in &self.args.mirror {
+            BackupHistory::update(
+                mirror,
+                backed_up_solutions
+                    .iter()
+                    .map(|(track, exercise)| (track.as_str(), exercise.as_str())),
+            )
+            .await?;
+            Manifest::update(mirror, self.args.checksum).await?;
+            if let Some(sign_key) = &self.args.sign_key {
+                sign_manifest(mirror, sign_key).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Backs up the solutions of all [accounts](BackupArgs::account), each in its own
+    /// subdirectory of the backup directory.
+    ///
+    /// Accounts are backed up concurrently, but share the same limits (see
+    /// [`max_downloads`](BackupArgs::max_downloads) and [`max_rpm`](BackupArgs::max_rpm)).
+    /// A summary of each account's backup is logged at the end.
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    #[instrument(skip_all)]
+    pub async fn execute_accounts(args: BackupArgs, api_base_url: Option<&str>) -> Result<()> {
+        let limiter =
+            KeyedLimiter::new(args.max_downloads, args.max_per_track.map(NonZeroUsize::get));
+        let rate_limiter = RateLimiter::new(args.max_rpm);
+        // Progress events of all accounts must be written to the same stream without
+        // overwriting each other, so the stream is only opened once.
+        let progress = ProgressReporter::new(args.progress, args.progress_fd)?;
+
+        let mut commands = Vec::with_capacity(args.account.len());
+        for account in &args.account {
+            let mut account_args = args.clone();
+            account_args.path = args.path.join(&account.name);
+            match &account.token {
+                AccountToken::Token(token) => account_args.token = Some(token.clone()),
+                AccountToken::Command(token_command) => {
+                    account_args.token_command = Some(token_command.clone())
+                },
+            }
+            account_args.account = vec![];
+            account_args.mirror = args
+                .mirror
+                .iter()
+                .map(|mirror| mirror.join(&account.name))
+                .collect();
+
+            let mut builder = Self::builder(account_args)
+                .limiters(limiter.clone(), rate_limiter.clone())
+                .progress(progress.for_account(&account.name));
+            if let Some(api_base_url) = api_base_url {
+                builder = builder.api_base_url(api_base_url);
+            }
+            commands.push((account.name.as_str(), builder.build()?));
+        }
+
+        let mut task_pool = TaskPool::new();
+        for (name, command) in &commands {
+            task_pool.spawn(format!("account {name}"), Self::execute(Arc::clone(command)));
+        }
+        let result = task_pool
+            .join(|| "errors detected while backing up accounts")
+            .await;
+
+        info!(
+            "Accounts backed up:\n{}",
+            commands
+                .iter()
+                .map(|(name, command)| format!(
+                    "  {name}: {} file(s) downloaded ({})",
+                    command.metrics.total_files_downloaded(),
+                    human_size(command.metrics.total_bytes_downloaded()),
+                ))
+                .join("\n")
+        );
+
+        // Make sure the program exits with the proper code if we were interrupted.
+        result.map_err(|err| {
+            if commands.iter().any(|(_, command)| command.is_interrupted()) {
+                err.context(Interrupted)
+            } else {
+                err
+            }
+        })
+    }
+
+    async fn backup_until_interrupted(this: Arc<Self>) -> Result<()> {
+        let mut backup = Box::pin(Self::backup(Arc::clone(&this)));
+
+        tokio::select! {
+            result = &mut backup => return result,
+            _ = interrupted() => (),
+        }
+
+        // Stop backing up new solutions, but give those in progress a chance to complete
+        // so that their backup state is saved.
+        this.interrupted.store(true, Ordering::Relaxed);
+        warn!(
+            "Backup interrupted; waiting up to {} seconds for solutions being backed up to complete",
+            GRACE_PERIOD.as_secs()
+        );
+
+        let result = tokio::select! {
+            result = time::timeout(GRACE_PERIOD, &mut backup) => result.ok(),
+            _ = interrupted() => None,
+        };
+        match result {
+            Some(Ok(())) => Err(Interrupted.into()),
+            Some(Err(err)) => Err(err.context(Interrupted)),
+            None => {
+                warn!("Aborting backup; incomplete files will be removed");
+
+                // Dropping the backup future aborts all remaining tasks.
+                drop(backup);
+                this.pending_paths.clean_up().await;
+                Err(Interrupted.into())
+            },
+        }
+    }
+
+    async fn backup(this: Arc<Self>) -> Result<()> {
         info!("Starting Exercism solutions backup to {}", this.args.path.display());
         trace!(?this.args);
 
+        this.check_exercism_availability().await?;
         this.create_output_directory(&this.args.path).await?;
+        for mirror in &this.args.mirror {
+            this.create_output_directory(mirror).await?;
+        }
+        if this.args.snapshot && !this.args.dry_run {
+            create_snapshot(&this.args.path, &this.output_path).await?;
+            info!("Backing up to snapshot {}", this.output_path.display());
+        }
 
-        let output_path = this.args.path.canonicalize().with_context(|| {
-            format!("failed to get absolute path for output directory {}", this.args.path.display())
+        let output_path = this.output_path.canonicalize().with_context(|| {
+            format!(
+                "failed to get absolute path for output directory {}",
+                this.output_path.display()
+            )
         })?;
         trace!(output_path = %output_path.display());
 
-        match spawn(Self::backup_solutions(Arc::clone(&this), output_path)).await {
+        let backup_task = spawn(Self::backup_solutions(Arc::clone(&this), output_path));
+        let _abort_on_drop = AbortOnDrop(backup_task.abort_handle());
+        match backup_task.await {
             Ok(Ok(())) => {
+                if let Some(plan) = &this.plan {
+                    anstream::print!("{}", plan.render(&this.iterations_dir_name));
+                }
+                if this.args.estimate_size {
+                    info!(
+                        "Estimated download size: {} in {} file(s)",
+                        human_size(this.metrics.total_bytes_downloaded()),
+                        this.metrics.total_files_downloaded(),
+                    );
+                }
                 info!("Exercism solutions backup complete");
                 Ok(())
             },
@@ -122,16 +587,59 @@ impl BackupCommand {
 
     #[instrument(skip_all)]
     async fn backup_solutions(this: Arc<Self>, output_path: PathBuf) -> Result<()> {
-        let mut task_pool = TaskPool::new();
+        // Solutions are fetched one page at a time, but we don't want to create tasks (and clone
+        // solutions) for thousands of solutions at once, so we wait for solutions to be backed up
+        // before queuing more.
+        let mut task_pool = TaskPool::with_max_tasks(this.args.max_queued_solutions);
+
+        if let Some(solution_id) = &this.args.solution_uuid {
+            let solution = this.get_single_solution(solution_id).await?;
+
+            this.create_track_directories(&output_path, slice::from_ref(&solution))
+                .await?;
+            if this.should_process_solutions() {
+                Self::spawn_icon_downloads(
+                    &this,
+                    &mut task_pool,
+                    &output_path,
+                    slice::from_ref(&solution),
+                );
+                Self::spawn_solution_backup(&this, &mut task_pool, &output_path, solution).await;
+            }
+        } else {
+            Self::backup_all_solutions(&this, &mut task_pool, &output_path).await?;
+        }
+
+        task_pool
+            .join_with_progress(
+                || "errors detected while backing up solutions",
+                |progress| {
+                    debug!(
+                        "{} solution(s) processed, {} remaining",
+                        progress.completed, progress.remaining
+                    )
+                },
+            )
+            .await
+    }
 
+    async fn backup_all_solutions(
+        this: &Arc<Self>,
+        task_pool: &mut TaskPool,
+        output_path: &Path,
+    ) -> Result<()> {
         let mut page = 1;
         loop {
+            if this.is_interrupted() {
+                break;
+            }
+
             let (solutions, meta) = this.get_solutions_for_page(page).await?;
 
             if solutions.is_empty() {
                 info!("No solutions to backup in page {page}");
             } else {
-                if this.args.dry_run && enabled!(Level::INFO) {
+                if this.args.dry_run && this.plan.is_none() && enabled!(Level::INFO) {
                     let solutions_list = solutions
                         .iter()
                         .map(|solution| {
@@ -146,16 +654,13 @@ impl BackupCommand {
 
                 // Create track directories right away so that concurrent tasks don't end up trying
                 // to create a directory multiple times.
-                this.create_track_directories(&output_path, &solutions)
+                this.create_track_directories(output_path, &solutions)
                     .await?;
 
-                if !this.args.dry_run || enabled!(Level::DEBUG) {
+                if this.should_process_solutions() {
+                    Self::spawn_icon_downloads(this, task_pool, output_path, &solutions);
                     for solution in solutions {
-                        task_pool.spawn(Self::backup_solution(
-                            Arc::clone(&this),
-                            output_path.clone(),
-                            solution,
-                        ));
+                        Self::spawn_solution_backup(this, task_pool, output_path, solution).await;
                     }
                 }
             }
@@ -166,41 +671,296 @@ impl BackupCommand {
             page += 1;
         }
 
+        if !this.is_interrupted() && this.args.can_detect_remotely_deleted() {
+            this.handle_remotely_deleted_solutions(output_path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Looks for backed up solutions that were not returned by Exercism.org (for example because
+    /// the exercise was removed or the account was reset) and handles them according to
+    /// [`BackupArgs::remotely_deleted`].
+    #[instrument(level = "debug", skip_all)]
+    async fn handle_remotely_deleted_solutions(&self, output_path: &Path) -> Result<()> {
+        let mut deleted = Vec::new();
+        for (track, track_path) in subdirectories(output_path).await? {
+            if self.args.rename_file.is_none() && !self.args.track_matches(&track) {
+                continue;
+            }
+
+            for (exercise, solution_path) in subdirectories(&track_path).await? {
+                // With a mapping file, this directory could store the solution to any of multiple
+                // exercises; we can only tell it was deleted if we looked for all of them.
+                let remote_names = self.args.remote_names(&track, &exercise);
+                let matches = !remote_names.is_empty()
+                    && remote_names.iter().all(|solution| {
+                        self.args.track_matches(&solution.track)
+                            && self.args.exercise_matches(&solution.exercise)
+                            && self
+                                .args
+                                .filter_file_matches(&solution.track, &solution.exercise)
+                    });
+                let exists_remotely = {
+                    let remote_solutions = self.remote_solutions.lock().unwrap();
+                    remote_names.into_iter().any(|solution| {
+                        remote_solutions.contains(&(solution.track, solution.exercise))
+                    })
+                };
+                if matches && !exists_remotely && BackupState::load(&solution_path).await.is_some()
+                {
+                    deleted.push((track.clone(), exercise.clone()));
+                }
+            }
+        }
+
+        for (track, exercise) in &deleted {
+            warn!(
+                "Solution to {track}/{exercise} is backed up but no longer exists on Exercism.org"
+            );
+            self.progress
+                .report(ProgressEvent::RemotelyDeleted { track, exercise });
+
+            if self.args.dry_run || self.args.remotely_deleted == RemotelyDeletedAction::Report {
+                continue;
+            }
+
+            let mirrors = self.args.mirror.iter().map(PathBuf::as_path);
+            for root_path in iter::once(output_path).chain(mirrors) {
+                let track_path = root_path.join(track);
+                let solution_path = track_path.join(exercise);
+                if root_path != output_path && !self.directory_exists(&solution_path).await {
+                    continue;
+                }
+
+                let _lock = SolutionLock::acquire(&track_path, exercise).await?;
+                if is_immutable(&solution_path).await {
+                    if !self.args.allow_immutable_update {
+                        warn!(
+                            "Solution to {track}/{exercise} is immutable and was kept in {} (see --allow-immutable-update)",
+                            root_path.display(),
+                        );
+                        continue;
+                    }
+                    set_immutable(&solution_path, false)
+                        .await
+                        .with_context(|| {
+                            format!("failed to make solution to {track}/{exercise} writable")
+                        })?;
+                }
+                if self.args.remotely_deleted == RemotelyDeletedAction::Archive {
+                    let archive_path = self
+                        .archive_directory(root_path, track, exercise)
+                        .await
+                        .with_context(|| {
+                            format!("failed to archive solution to {track}/{exercise}")
+                        })?;
+                    info!("Solution to {track}/{exercise} archived to {}", archive_path.display());
+                } else {
+                    fs::remove_dir_all(&solution_path).await.with_context(|| {
+                        format!("failed to remove solution to {track}/{exercise}")
+                    })?;
+                    info!("Solution to {track}/{exercise} removed from {}", root_path.display());
+                }
+            }
+        }
+
+        if !deleted.is_empty() {
+            warn!(
+                "{} backed up solution(s) no longer exist on Exercism.org (see --remotely-deleted)",
+                deleted.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn spawn_solution_backup(
+        this: &Arc<Self>,
+        task_pool: &mut TaskPool,
+        output_path: &Path,
+        solution: Solution,
+    ) {
+        let this = Arc::clone(this);
+        let output_path = output_path.to_path_buf();
+        let label = solution_desc(&solution).to_string();
+        let failed_solution = FailedSolution::from(&solution);
+        let local_names = this
+            .args
+            .local_names(&solution.track.name, &solution.exercise.name);
+        if let Some(request) = MentoringRequest::for_solution(&solution) {
+            this.mentoring_requests.lock().unwrap().push(request);
+        }
         task_pool
-            .join(|| "errors detected while backing up solutions")
-            .await
+            .spawn_bounded(label, async move {
+                this.progress.report(ProgressEvent::SolutionStarted {
+                    track: &failed_solution.track,
+                    exercise: &failed_solution.exercise,
+                });
+                let start = Instant::now();
+                let result = Self::backup_solution(Arc::clone(&this), output_path, solution).await;
+                let timing = this.metrics.solution_finished(
+                    &failed_solution.track,
+                    &failed_solution.exercise,
+                    &failed_solution.uuid,
+                    start.elapsed(),
+                );
+                this.progress.report(ProgressEvent::SolutionFinished {
+                    track: &failed_solution.track,
+                    exercise: &failed_solution.exercise,
+                    success: result.is_ok(),
+                    elapsed_ms: timing.elapsed_ms,
+                    bytes: timing.bytes,
+                });
+                match &result {
+                    Ok(true) => this
+                        .backed_up_solutions
+                        .lock()
+                        .unwrap()
+                        .push((local_names.track, local_names.exercise)),
+                    Ok(false) => (),
+                    Err(_) => {
+                        this.metrics.error();
+                        this.failed_solutions.lock().unwrap().push(failed_solution);
+                    },
+                }
+                result.map(|_| ())
+            })
+            .await;
+    }
+
+    fn spawn_icon_downloads(
+        this: &Arc<Self>,
+        task_pool: &mut TaskPool,
+        output_path: &Path,
+        solutions: &[Solution],
+    ) {
+        if !this.args.with_icons || this.args.dry_run {
+            return;
+        }
+
+        for icon in solutions.iter().flat_map(Icon::for_solution) {
+            // Icons are shared by many solutions, so we only download them once. Icons that were
+            // downloaded by a previous backup are kept as-is since they rarely change.
+            if !this.icons.lock().unwrap().insert(icon.path.clone())
+                || output_path.join(&icon.path).is_file()
+            {
+                continue;
+            }
+
+            let this = Arc::clone(this);
+            let output_path = output_path.to_path_buf();
+            task_pool.spawn(format!("icon {}", icon.url), async move {
+                let _permit = this.download_limiter.get_permit_for(&icon.track).await;
+                match icon.download(&this.http_client, &output_path).await {
+                    Ok(size) => this.metrics.bytes_downloaded(size),
+                    // Icons are only used for offline browsing, so they should not fail the backup.
+                    Err(err) => warn!("{err:#}"),
+                }
+                Ok(())
+            });
+        }
     }
 
+    /// Backs up the given solution if needed.
+    ///
+    /// Returns `false` if the solution was skipped without being checked (because the backup was
+    /// interrupted or the maximum total download size was reached).
     #[instrument(level = "debug", skip_all, fields(solution.track.name, solution.exercise.name))]
     async fn backup_solution(
         this: Arc<Self>,
         mut output_path: PathBuf,
         solution: Solution,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         trace!(?solution);
 
-        output_path.push(&solution.track.name);
-        output_path.push(&solution.exercise.name);
+        let local_names = this
+            .args
+            .local_names(&solution.track.name, &solution.exercise.name);
+        output_path.push(&local_names.track);
+
+        // Prevent other auxiliaire processes from modifying this solution's backup while we work on it.
+        let _lock = if this.args.dry_run {
+            None
+        } else {
+            Some(
+                SolutionLock::acquire(&output_path, &local_names.exercise)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to lock backup of solution to {}",
+                            solution_desc(&solution),
+                        )
+                    })?,
+            )
+        };
+
+        if this.is_interrupted() {
+            debug!(
+                "Skipping solution to {}/{}: backup interrupted",
+                solution.track.name, solution.exercise.name
+            );
+            return Ok(false);
+        }
+
+        output_path.push(&local_names.exercise);
         trace!(output_path = %output_path.display());
 
-        let files = this.get_solution_files(&solution).await.with_context(|| {
-            format!(
-                "failed to get list of files for solution to {}/{}",
-                solution.track.name, solution.exercise.name,
-            )
-        })?;
+        let state = this.load_backup_state(&solution, &output_path).await;
+        let (needs_backup, solution_exists) = this
+            .solution_needs_backup(&solution, &output_path, &state)
+            .await?;
+        let up_to_date = !state.needs_update(&solution)?;
+
+        if let Some(last_status) = state.status_regression(&solution) {
+            warn!(
+                "solution to {}/{} was {:?} when last backed up but is now {:?}: are you using the right API token and output directory?",
+                solution.track.name, solution.exercise.name, last_status, solution.status,
+            );
+        }
+
+        // If the solution is up-to-date, we can reuse the list of files from the last backup
+        // instead of fetching it again, since it's one of the costliest API calls we perform.
+        let (remote_solution, files) = match (needs_backup, up_to_date, &state.files) {
+            (false, true, Some(files)) => {
+                trace!("Using cached list of files");
+                (None, files.clone())
+            },
+            _ => {
+                let remote_solution =
+                    this.get_remote_solution(&solution).await.with_context(|| {
+                        format!(
+                            "failed to get list of files for solution to {}",
+                            solution_desc(&solution),
+                        )
+                    })?;
+                let files = remote_solution.files.clone();
+                (Some(remote_solution), files)
+            },
+        };
+
+        // Hidden and excluded files are filtered out before anything is downloaded, so that
+        // they are not recorded in the backup state either.
+        let (files, excluded_files): (Vec<_>, Vec<_>) = files
+            .into_iter()
+            .partition(|file| this.args.file_matches(file));
+        if !excluded_files.is_empty() {
+            debug!(
+                "Excluding {} file(s) of solution to {}: {}",
+                excluded_files.len(),
+                solution_desc(&solution),
+                excluded_files.join(", "),
+            );
+        }
 
-        let (needs_backup, solution_exists) =
-            this.solution_needs_backup(&solution, &output_path).await?;
         if this.args.dry_run && needs_backup {
             debug!("Files to back up: {}", files.join(", "));
         }
 
         if this.args.iterations_sync_policy.sync() && this.has_iterations_dir_collision(&files) {
             let warning = format!(
-                "solution to {}/{} contains a file whose name collides with the iterations backup directory name ({}); consider setting the {} environment variable to change the directory name",
-                solution.track.name,
-                solution.exercise.name,
+                "solution to {} contains a file whose name collides with the iterations backup directory name ({}); consider setting the {} environment variable to change the directory name",
+                solution_desc(&solution),
                 this.iterations_dir_name,
                 ITERATIONS_DIR_ENV_VAR_NAME,
             );
@@ -211,15 +971,45 @@ impl BackupCommand {
             }
         }
 
-        let matching_iterations = this.get_matching_solution_iterations(&solution).await?;
         let existing_iterations = this
             .get_existing_iterations(&solution, &output_path)
             .await?;
-        let iteration_ops = this.get_iteration_sync_ops(matching_iterations, existing_iterations);
+        let (iteration_ops, matching_iteration_indexes) = match &state.iterations {
+            Some(cached_iterations)
+                if up_to_date
+                    && this.args.iterations_cacheable()
+                    && this.cached_iterations_in_sync(cached_iterations, &existing_iterations) =>
+            {
+                trace!("Using cached list of iterations");
+                (SyncOps::default(), Some(cached_iterations.clone()))
+            },
+            _ => {
+                let matching_iterations = this.get_matching_solution_iterations(&solution).await?;
+                let matching_iteration_indexes = this
+                    .args
+                    .iterations_sync_policy
+                    .backup_new()
+                    .then(|| matching_iterations.iter().map(|iter| iter.index).collect());
+                let iteration_ops = plan_iteration_sync(
+                    matching_iterations,
+                    existing_iterations,
+                    this.args.iterations_sync_policy.clean_up_old(),
+                );
+                trace!(?iteration_ops);
+                (iteration_ops, matching_iteration_indexes)
+            },
+        };
 
         if this.args.iterations_sync_policy.clean_up_old()
             && !iteration_ops.existing_iterations_to_clean_up.is_empty()
         {
+            if this.args.no_delete {
+                bail!(
+                    "solution to {} has {} existing iteration(s) to clean up, but they cannot be removed with --no-delete",
+                    solution_desc(&solution),
+                    iteration_ops.existing_iterations_to_clean_up.len(),
+                );
+            }
             debug!(
                 "Existing iterations to clean up: {}",
                 iteration_ops.existing_iterations_to_clean_up.len()
@@ -231,10 +1021,85 @@ impl BackupCommand {
             debug!("Iterations to back up: {}", iteration_ops.iterations_to_backup.len());
         }
 
+        if let Some(plan) = &this.plan {
+            if needs_backup || !iteration_ops.is_empty() {
+                let planned = PlannedSolution {
+                    exists: solution_exists,
+                    files: if needs_backup { files.clone() } else { vec![] },
+                    iterations_to_backup: if this.args.iterations_sync_policy.backup_new() {
+                        iteration_ops
+                            .iterations_to_backup
+                            .iter()
+                            .map(|iteration| iteration.index)
+                            .collect()
+                    } else {
+                        vec![]
+                    },
+                    iterations_to_clean_up: iteration_ops.existing_iterations_to_clean_up.clone(),
+                };
+                plan.add(&solution.track.name, &solution.exercise.name, planned);
+            }
+        }
+
         if !needs_backup && iteration_ops.is_empty() {
+            // If the state was saved before we started caching the solution's files or iterations,
+            // or if the solution's status or mentoring status changed, update it now so that
+            // it's accurate next time.
+            let immutable = is_immutable(&output_path).await;
+            if !this.args.dry_run
+                && up_to_date
+                && !immutable
+                && (state.files.is_none()
+                    || (solution.status != solution::Status::Unknown
+                        && state.status != Some(solution.status))
+                    || (solution.mentoring_status != MentoringStatus::Unknown
+                        && state.mentoring_status.as_ref() != Some(&solution.mentoring_status))
+                    || (matching_iteration_indexes.is_some()
+                        && matching_iteration_indexes != state.iterations))
+            {
+                let _permit = this.limiter.get_permit_for(&solution.track.name).await;
+                this.save_backup_state(&solution, files, matching_iteration_indexes, &output_path)
+                    .await?;
+            }
+            if !this.args.dry_run && this.args.immutable && !immutable && solution_exists {
+                this.make_immutable(&solution, &output_path).await?;
+            }
+            this.sync_mirrors(&solution, &local_names, &output_path)
+                .await?;
+
             // No need to log something here, user has already been notified that we're
             // skipping this solution in `solution_needs_backup`.
-            return Ok(());
+            return Ok(true);
+        }
+
+        if !this.args.dry_run && this.max_total_size_exceeded() {
+            debug!(
+                "Skipping solution to {}/{}: maximum total download size reached",
+                solution.track.name, solution.exercise.name
+            );
+            return Ok(false);
+        }
+
+        // Validate the paths of all files before touching the solution's directory, so that
+        // we don't fail midway through the backup because of a path that is too long.
+        let shortened_paths = if needs_backup {
+            this.shorten_file_paths(&solution, &files, &output_path)?
+        } else {
+            ShortenedPaths::default()
+        };
+
+        if is_immutable(&output_path).await {
+            if !this.args.allow_immutable_update {
+                bail!(
+                    "solution to {} is immutable and cannot be updated (see --allow-immutable-update)",
+                    solution_desc(&solution),
+                );
+            }
+            if !this.args.dry_run {
+                set_immutable(&output_path, false).await.with_context(|| {
+                    format!("failed to make solution to {} writable", solution_desc(&solution))
+                })?;
+            }
         }
 
         if !this.args.dry_run {
@@ -247,17 +1112,22 @@ impl BackupCommand {
             .await?;
         }
 
-        if !this.args.dry_run || enabled!(Level::DEBUG) {
+        if this.should_process_solutions() {
             let mut task_pool = TaskPool::new();
 
             if needs_backup {
-                for file in files {
-                    task_pool.spawn(Self::backup_one_file(
-                        Arc::clone(&this),
-                        solution.clone(),
-                        file,
-                        output_path.clone(),
-                    ));
+                for file in &files {
+                    task_pool.spawn(
+                        file.as_str(),
+                        Self::backup_one_file(
+                            Arc::clone(&this),
+                            solution.clone(),
+                            file.clone(),
+                            shortened_paths.local_path(file).to_owned(),
+                            output_path.clone(),
+                            this.args.show_diff && solution_exists,
+                        ),
+                    );
                 }
             }
 
@@ -266,73 +1136,224 @@ impl BackupCommand {
                 iterations_output_path.push(&this.iterations_dir_name);
 
                 for existing_iteration in iteration_ops.existing_iterations_to_clean_up {
-                    task_pool.spawn(Self::remove_one_existing_iteration(
-                        Arc::clone(&this),
-                        solution.clone(),
-                        existing_iteration,
-                        iterations_output_path.clone(),
-                    ));
+                    task_pool.spawn(
+                        format!("iteration {existing_iteration}"),
+                        Self::remove_one_existing_iteration(
+                            Arc::clone(&this),
+                            solution.clone(),
+                            existing_iteration,
+                            iterations_output_path.clone(),
+                        ),
+                    );
                 }
                 for new_iteration in iteration_ops.iterations_to_backup {
-                    task_pool.spawn(Self::backup_one_iteration(
-                        Arc::clone(&this),
-                        solution.clone(),
-                        new_iteration,
-                        iterations_output_path.clone(),
-                    ));
+                    task_pool.spawn(
+                        format!("iteration {}", new_iteration.index),
+                        Self::backup_one_iteration(
+                            Arc::clone(&this),
+                            solution.clone(),
+                            new_iteration,
+                            iterations_output_path.clone(),
+                        ),
+                    );
                 }
             }
 
             task_pool
                 .join(|| {
                     format!(
-                        "errors detected while backing up solution for {}/{}",
-                        solution.track.name, solution.exercise.name
+                        "errors detected while backing up solution for {}",
+                        solution_desc(&solution)
                     )
                 })
                 .await?;
         }
 
-        if !this.args.dry_run {
-            let _permit = this.limiter.get_permit().await;
-            this.save_backup_state(&solution, &output_path).await?;
+        if let Some(remote_solution) = remote_solution.filter(|_| {
+            !this.args.dry_run
+                && needs_backup
+                && this.args.layout == OutputLayout::ExercismWorkspace
+        }) {
+            this.save_exercism_metadata(&solution, remote_solution, &output_path)
+                .await?;
         }
 
-        info!("Solution to {}/{} downloaded", solution.track.name, solution.exercise.name);
-
-        Ok(())
-    }
-
-    #[instrument(level = "trace", skip_all, fields(solution.track.name, solution.exercise.name, file))]
-    async fn backup_one_file(
-        this: Arc<Self>,
+        if !this.args.dry_run {
+            let _permit = this.limiter.get_permit_for(&solution.track.name).await;
+            if needs_backup {
+                shortened_paths.save(&output_path).await.with_context(|| {
+                    format!(
+                        "failed to save shortened paths for solution to {}",
+                        solution_desc(&solution),
+                    )
+                })?;
+            }
+            // Skipped files are not recorded in the state, so that they are not reported as missing.
+            let files = this.without_skipped_files(&solution, files);
+            this.save_backup_state(&solution, files, matching_iteration_indexes, &output_path)
+                .await?;
+            this.metrics.solution_backed_up();
+            this.modes
+                .apply(&output_path, true)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to change permissions of solution to {}",
+                        solution_desc(&solution)
+                    )
+                })?;
+            if this.args.immutable {
+                this.make_immutable(&solution, &output_path).await?;
+            }
+            this.sync_mirrors(&solution, &local_names, &output_path)
+                .await?;
+            if needs_backup {
+                this.downloaded_solutions
+                    .lock()
+                    .unwrap()
+                    .push((solution.clone(), output_path.clone()));
+            }
+        }
+
+        info!("Solution to {}/{} downloaded", solution.track.name, solution.exercise.name);
+
+        Ok(true)
+    }
+
+    #[instrument(level = "trace", skip_all, fields(solution.track.name, solution.exercise.name, file))]
+    async fn backup_one_file(
+        this: Arc<Self>,
         solution: Solution,
         file: String,
+        local_file: String,
         mut destination_path: PathBuf,
+        show_diff: bool,
     ) -> Result<()> {
-        destination_path.extend(file.split('/'));
+        destination_path.extend(local_file.split('/'));
         trace!(destination_path = %destination_path.display());
 
-        let _permit = this.limiter.get_permit().await;
+        let _permit = this
+            .download_limiter
+            .get_permit_for(&solution.track.name)
+            .await;
+        this.rate_limiter.acquire().await;
+        let start = Instant::now();
         let mut file_stream = this.v1_client.get_file(&solution.uuid, &file).await;
 
+        let mut file_size = 0;
         if !this.args.dry_run {
             this.create_file_parent_directory(&destination_path).await?;
 
+            this.pending_paths.add(&destination_path);
             let destination_file = fs::File::create(&destination_path).await?;
             let mut destination_file = BufWriter::new(destination_file);
+            let mut file_hasher = this
+                .args
+                .verify_after
+                .then(|| FileHasher::new(this.args.checksum));
 
             while let Some(bytes) = file_stream.next().await {
-                let bytes = bytes.with_context(|| {
-                    format!(
-                        "failed to download file {file} in solution to exercise {}/{}",
-                        solution.track.name, solution.exercise.name,
-                    )
-                })?;
+                let bytes = bytes
+                    .map_err(|err| {
+                        this.log_http_request(
+                            &file_endpoint(&solution, &file),
+                            &error_outcome(&err),
+                            start.elapsed(),
+                        );
+                        err
+                    })
+                    .with_context(|| {
+                        format!(
+                            "failed to download file {file} in solution to {}",
+                            solution_desc(&solution),
+                        )
+                    })?;
+                this.metrics.bytes_downloaded(bytes.len());
+                file_size += bytes.len();
+                if this.file_too_large(file_size) {
+                    break;
+                }
+                if let Some(file_hasher) = &mut file_hasher {
+                    file_hasher.update(&bytes);
+                }
                 destination_file.write_all(&bytes).await?;
             }
 
+            this.warn_if_slow(&file_endpoint(&solution, &file), start.elapsed());
+            this.log_http_request(
+                &file_endpoint(&solution, &file),
+                &format!("OK ({file_size} bytes)"),
+                start.elapsed(),
+            );
+
+            if this.file_too_large(file_size) {
+                drop(destination_file);
+                fs::remove_file(&destination_path).await.with_context(|| {
+                    format!("failed to remove partial file {}", destination_path.display())
+                })?;
+                this.pending_paths.complete(&destination_path);
+                this.skip_file(&solution, &file);
+                return Ok(());
+            }
+
             destination_file.flush().await?;
+            this.pending_paths.complete(&destination_path);
+            if let Some(file_hasher) = file_hasher {
+                this.downloaded_files
+                    .lock()
+                    .unwrap()
+                    .insert(destination_path.clone(), (file_size as u64, file_hasher.finish()));
+            }
+            this.metrics.file_downloaded();
+            this.report_file_downloaded(&solution, None, &file, file_size);
+        } else if this.args.estimate_size || show_diff {
+            // Exercism's API does not report file sizes, so we need to fetch the file's content
+            // to know its size or to compare it. At least we don't need to save it.
+            let mut content = Vec::new();
+            while let Some(bytes) = file_stream.next().await {
+                let bytes = bytes
+                    .map_err(|err| {
+                        this.log_http_request(
+                            &file_endpoint(&solution, &file),
+                            &error_outcome(&err),
+                            start.elapsed(),
+                        );
+                        err
+                    })
+                    .with_context(|| {
+                        format!(
+                            "failed to fetch file {file} in solution to {}",
+                            solution_desc(&solution),
+                        )
+                    })?;
+                this.metrics.bytes_downloaded(bytes.len());
+                file_size += bytes.len();
+                if this.file_too_large(file_size) {
+                    break;
+                }
+                if show_diff {
+                    content.extend_from_slice(&bytes);
+                }
+            }
+            this.warn_if_slow(&file_endpoint(&solution, &file), start.elapsed());
+            this.log_http_request(
+                &file_endpoint(&solution, &file),
+                &format!("OK ({file_size} bytes)"),
+                start.elapsed(),
+            );
+
+            if this.file_too_large(file_size) {
+                this.skip_file(&solution, &file);
+                return Ok(());
+            }
+            this.metrics.file_downloaded();
+            this.report_file_downloaded(&solution, None, &file, file_size);
+
+            if show_diff {
+                let local_content = fs::read(&destination_path).await.unwrap_or_default();
+                let path = format!("{}/{}/{file}", solution.track.name, solution.exercise.name);
+                anstream::print!("{}", file_diff(&path, &local_content, &content));
+            }
         }
 
         Ok(())
@@ -349,13 +1370,14 @@ impl BackupCommand {
         trace!(destination_path = %destination_path.display());
 
         if !this.args.dry_run {
-            let _permit = this.limiter.get_permit().await;
+            let _permit = this.limiter.get_permit_for(&solution.track.name).await;
             this.remove_directory(&destination_path)
                 .await
                 .with_context(|| {
                     format!(
-                        "failed to remove existing iteration {} of solution to {}/{}",
-                        iteration, solution.track.name, solution.exercise.name,
+                        "failed to remove existing iteration {} of solution to {}",
+                        iteration,
+                        solution_desc(&solution),
                     )
                 })?;
         }
@@ -378,37 +1400,72 @@ impl BackupCommand {
         destination_path.push(iteration.index.to_string());
         trace!(destination_path = %destination_path.display());
 
-        match iteration.submission_uuid {
+        match &iteration.submission_uuid {
             Some(submission_uuid) => {
-                let _permit = this.limiter.get_permit().await;
-                let files = this
-                    .v2_client
-                    .get_submission_files(&solution.uuid, &submission_uuid)
+                // The API returns the content of all of the iteration's files in a single response,
+                // so we keep a permit until they have all been saved to bound the number of responses
+                // held in memory. This is only a partial mitigation, since each response is still
+                // fully buffered: the files cannot be streamed to disk like solution files can.
+                // This limiter is separate from the others because saving files requires permits
+                // from them.
+                let _files_permit = this.iteration_files_limiter.get_permit().await;
+                let files = {
+                    let _permit = this
+                        .download_limiter
+                        .get_permit_for(&solution.track.name)
+                        .await;
+                    this.rate_limiter.acquire().await;
+                    this.timed_request(
+                        &format!(
+                            "v2/solutions/{}/submissions/{submission_uuid}/files",
+                            solution.uuid
+                        ),
+                        this.v2_client
+                            .get_submission_files(&solution.uuid, submission_uuid),
+                    )
                     .await
                     .with_context(|| {
                         format!(
-                            "failed to fetch files for iteration {} of solution to {}/{}",
-                            iteration.index, solution.track.name, solution.exercise.name,
+                            "failed to fetch files for iteration {} of solution to {}",
+                            iteration.index,
+                            solution_desc(&solution),
                         )
                     })?
-                    .files;
+                    .files
+                };
 
+                if !this.args.dry_run {
+                    this.pending_paths.add(&destination_path);
+                }
+
+                let mut task_pool =
+                    TaskPool::with_limiter(this.limiter.for_key(&solution.track.name));
                 for file in files {
-                    let mut file_path = destination_path.clone();
-                    file_path.push(&file.filename);
-
-                    this.create_file_parent_directory(&file_path).await?;
-                    if !this.args.dry_run {
-                        fs::write(&file_path, file.content).await.with_context(|| {
-                            format!(
-                                "failed to save file {} of iteration {} of solution to {}/{}",
-                                file.filename,
-                                iteration.index,
-                                solution.track.name,
-                                solution.exercise.name,
-                            )
-                        })?;
-                    }
+                    task_pool.spawn_limited(
+                        file.filename.clone(),
+                        Self::save_one_iteration_file(
+                            Arc::clone(&this),
+                            solution.clone(),
+                            iteration.index,
+                            file,
+                            destination_path.clone(),
+                        ),
+                    );
+                }
+                task_pool
+                    .join(|| {
+                        format!(
+                            "errors detected while saving files of iteration {} of solution to {}",
+                            iteration.index,
+                            solution_desc(&solution),
+                        )
+                    })
+                    .await?;
+
+                if !this.args.dry_run {
+                    this.save_iteration_metadata(&solution, &iteration, &destination_path)
+                        .await?;
+                    this.pending_paths.complete(&destination_path);
                 }
 
                 debug!(
@@ -418,10 +1475,9 @@ impl BackupCommand {
             },
             None => {
                 let error = format!(
-                    "Iteration {} of solution to {}/{} is not marked as deleted but does not have a submission UUID",
+                    "Iteration {} of solution to {} is not marked as deleted but does not have a submission UUID",
                     iteration.index,
-                    solution.track.name,
-                    solution.exercise.name,
+                    solution_desc(&solution),
                 );
 
                 error!("{}", error);
@@ -434,17 +1490,205 @@ impl BackupCommand {
         Ok(())
     }
 
+    #[instrument(level = "trace", skip_all, fields(solution.track.name, solution.exercise.name, iteration.index = iteration_index, file.filename))]
+    async fn save_one_iteration_file(
+        this: Arc<Self>,
+        solution: Solution,
+        iteration_index: i32,
+        file: submission::files::File,
+        mut destination_path: PathBuf,
+    ) -> Result<()> {
+        destination_path.push(&file.filename);
+        trace!(destination_path = %destination_path.display());
+
+        check_path_length(&destination_path).with_context(|| {
+            format!(
+                "failed to save file {} of iteration {} of solution to {}",
+                file.filename,
+                iteration_index,
+                solution_desc(&solution),
+            )
+        })?;
+
+        if this.file_too_large(file.content.len()) {
+            // Iteration files are skipped like solution files, but they are identified by their
+            // path in the solution's backup so that they can be told apart in the summary.
+            this.skip_file(
+                &solution,
+                &format!("{}/{iteration_index}/{}", this.iterations_dir_name, file.filename),
+            );
+            return Ok(());
+        }
+
+        if !this.args.dry_run || this.args.estimate_size {
+            this.metrics.bytes_downloaded(file.content.len());
+            this.metrics.file_downloaded();
+            this.report_file_downloaded(
+                &solution,
+                Some(iteration_index),
+                &file.filename,
+                file.content.len(),
+            );
+        }
+
+        if !this.args.dry_run {
+            this.create_file_parent_directory(&destination_path).await?;
+            fs::write(&destination_path, file.content)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to save file {} of iteration {} of solution to {}",
+                        file.filename,
+                        iteration_index,
+                        solution_desc(&solution),
+                    )
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Awaits the given Exercism API `request`, warning if it takes longer than
+    /// the slow request threshold (see [`warn_if_slow`](Self::warn_if_slow)).
+    ///
+    /// With `--debug-http`, the request (and possibly its response) is also logged.
+    async fn timed_request<T, E, F>(&self, endpoint: &str, request: F) -> F::Output
+    where
+        F: Future<Output = std::result::Result<T, E>>,
+        T: Debug,
+        E: std::error::Error + 'static,
+    {
+        let start = Instant::now();
+        let output = request.await;
+        let elapsed = start.elapsed();
+        self.warn_if_slow(endpoint, elapsed);
+
+        match &output {
+            Ok(response) => {
+                self.log_http_request(endpoint, "OK", elapsed);
+                if self.args.debug_http == Some(DebugHttp::Bodies) {
+                    info!(
+                        "HTTP response from {}:\n{}",
+                        self.api_url(endpoint),
+                        self.redactor.redact(&format!("{response:#?}")),
+                    );
+                }
+            },
+            Err(err) => self.log_http_request(endpoint, &error_outcome(err), elapsed),
+        }
+        output
+    }
+
+    /// Logs a request to the given Exercism API endpoint if `--debug-http` is used.
+    fn log_http_request(&self, endpoint: &str, outcome: &str, elapsed: Duration) {
+        if self.args.debug_http.is_some() {
+            info!(
+                "HTTP GET {} -> {} in {}ms",
+                self.api_url(endpoint),
+                self.redactor.redact(outcome),
+                elapsed.as_millis(),
+            );
+        }
+    }
+
+    /// Returns the URL of the given Exercism API endpoint (like `v2/solutions`), for HTTP debug logs.
+    fn api_url(&self, endpoint: &str) -> String {
+        let (api_base_url, path) = match endpoint.split_once('/') {
+            Some(("v1", path)) => (
+                self.args
+                    .v1_api_base_url
+                    .as_deref()
+                    .unwrap_or(api::v1::DEFAULT_V1_API_BASE_URL),
+                path,
+            ),
+            Some(("v2", path)) => (
+                self.args
+                    .v2_api_base_url
+                    .as_deref()
+                    .unwrap_or(api::v2::DEFAULT_V2_API_BASE_URL),
+                path,
+            ),
+            _ => return endpoint.into(),
+        };
+
+        format!("{}/{path}", api_base_url.trim_end_matches('/'))
+    }
+
+    /// Warns if a request to the given endpoint took longer than the slow request threshold
+    /// (see `--slow-request-threshold`), so that slow network paths are visible
+    /// even at the default log level.
+    fn warn_if_slow(&self, endpoint: &str, elapsed: Duration) {
+        if self
+            .args
+            .slow_request_threshold()
+            .is_some_and(|threshold| elapsed > threshold)
+        {
+            warn!("Slow request: {endpoint} took {:.1}s", elapsed.as_secs_f64());
+        }
+    }
+
+    fn report_file_downloaded(
+        &self,
+        solution: &Solution,
+        iteration: Option<i32>,
+        file: &str,
+        bytes: usize,
+    ) {
+        self.metrics
+            .solution_bytes_downloaded(&solution.uuid, bytes);
+        self.progress.report(ProgressEvent::FileDownloaded {
+            track: &solution.track.name,
+            exercise: &solution.exercise.name,
+            iteration,
+            file,
+            bytes: bytes as u64,
+        });
+    }
+
+    #[instrument(level = "trace", skip(self, solution, iteration), fields(solution.track.name, solution.exercise.name, iteration.index))]
+    async fn save_iteration_metadata(
+        &self,
+        solution: &Solution,
+        iteration: &Iteration,
+        iteration_output_path: &Path,
+    ) -> Result<()> {
+        let metadata = serde_json::to_string_pretty(&IterationMetadata::from(iteration))?;
+
+        let metadata_file_path = iteration_output_path.join(ITERATION_METADATA_FILE_NAME);
+        self.create_file_parent_directory(&metadata_file_path)
+            .await?;
+        fs::write(&metadata_file_path, metadata)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to save metadata of iteration {} of solution to {} to {}",
+                    iteration.index,
+                    solution_desc(solution),
+                    metadata_file_path.display()
+                )
+            })
+    }
+
     #[instrument(level = "trace", skip(self, solution), fields(solution.track.name, solution.exercise.name))]
     async fn save_backup_state(
         &self,
         solution: &Solution,
+        files: Vec<String>,
+        iterations: Option<Vec<i32>>,
         solution_output_path: &Path,
     ) -> Result<()> {
-        let state = BackupState::for_solution(solution.clone());
+        let mut state = BackupState {
+            files: Some(files),
+            iterations,
+            ..BackupState::for_solution(solution.clone())
+        };
+        if self.args.anonymize {
+            state.anonymize();
+        }
         let state = serde_json::to_string_pretty(&state).with_context(|| {
             format!(
-                "failed to persist backup state for solution to {}/{} to JSON",
-                solution.track.name, solution.exercise.name
+                "failed to persist backup state for solution to {} to JSON",
+                solution_desc(solution)
             )
         })?;
 
@@ -452,13 +1696,13 @@ impl BackupCommand {
         temp_state_file_path.push(BACKUP_STATE_TEMP_FILE_NAME);
         self.create_file_parent_directory(&temp_state_file_path)
             .await?;
+        self.pending_paths.add(&temp_state_file_path);
         fs::write(&temp_state_file_path, state)
             .await
             .with_context(|| {
                 format!(
-                    "failed to save backup state for solution to {}/{} to {}",
-                    solution.track.name,
-                    solution.exercise.name,
+                    "failed to save backup state for solution to {} to {}",
+                    solution_desc(solution),
                     temp_state_file_path.display()
                 )
             })?;
@@ -469,12 +1713,208 @@ impl BackupCommand {
             .await
             .with_context(|| {
                 format!(
-                    "failed to rename backup state for solution to {}/{}, from {} to {}",
-                    solution.track.name,
-                    solution.exercise.name,
+                    "failed to rename backup state for solution to {}, from {} to {}",
+                    solution_desc(solution),
                     temp_state_file_path.display(),
                     state_file_path.display()
                 )
+            })?;
+        self.pending_paths.complete(&temp_state_file_path);
+
+        Ok(())
+    }
+
+    /// Makes sure the files of all solutions downloaded during this backup are stored on disk
+    /// as they were downloaded (see [`BackupArgs::verify_after`]).
+    ///
+    /// The list of files of each solution is fetched again, so that files that were added to
+    /// a solution during the backup are also reported.
+    #[instrument(level = "debug", skip_all)]
+    async fn verify_backup(&self) -> Result<()> {
+        let solutions = self.downloaded_solutions.lock().unwrap().clone();
+
+        let mut problems = 0;
+        for (solution, solution_output_path) in &solutions {
+            for problem in self.verify_solution(solution, solution_output_path).await? {
+                error!(
+                    "Verification failed for solution to {}/{}: {problem}",
+                    solution.track.name, solution.exercise.name
+                );
+                problems += 1;
+            }
+        }
+
+        if problems > 0 {
+            bail!("backup verification found {problems} problem(s)");
+        }
+        info!("Backup verified ({} solution(s))", solutions.len());
+        Ok(())
+    }
+
+    #[instrument(level = "trace", skip(self, solution), fields(solution.track.name, solution.exercise.name))]
+    async fn verify_solution(
+        &self,
+        solution: &Solution,
+        solution_output_path: &Path,
+    ) -> Result<Vec<String>> {
+        let remote_solution = {
+            let _permit = self
+                .metadata_limiter
+                .get_permit_for(&solution.track.name)
+                .await;
+            self.rate_limiter.acquire().await;
+            self.timed_request(
+                &format!("v1/solutions/{}", solution.uuid),
+                self.v1_client.get_solution(&solution.uuid),
+            )
+            .await
+            .with_context(|| {
+                format!("failed to get list of files for solution to {}", solution_desc(solution))
+            })?
+            .solution
+        };
+        let shortened_paths = ShortenedPaths::load(solution_output_path).await?;
+
+        let mut problems = Vec::new();
+        for file in remote_solution
+            .files
+            .iter()
+            .filter(|file| self.args.file_matches(file))
+            .filter(|file| !self.is_skipped_file(solution, file))
+        {
+            let mut file_path = solution_output_path.to_path_buf();
+            file_path.extend(shortened_paths.local_path(file).split('/'));
+
+            let Ok(metadata) = fs::metadata(&file_path).await else {
+                problems.push(format!("file {file} is missing"));
+                continue;
+            };
+            let expected = self
+                .downloaded_files
+                .lock()
+                .unwrap()
+                .get(&file_path)
+                .cloned();
+            match expected {
+                Some((size, _)) if metadata.len() != size => problems.push(format!(
+                    "file {file} has a size of {} byte(s) instead of {size}",
+                    metadata.len()
+                )),
+                Some((_, hash)) if hash_file(&file_path, self.args.checksum).await? != hash => {
+                    problems.push(format!("file {file} does not match the downloaded content"))
+                },
+                _ => (),
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Applies the [modes](BackupArgs::file_mode) to the directories and files of the backup
+    /// directory that are not part of a solution, like track directories and metadata files.
+    ///
+    /// Solutions are handled as they are backed up, so that modes can be applied before
+    /// solutions are made [immutable](BackupArgs::immutable).
+    #[instrument(level = "debug", skip_all)]
+    async fn apply_modes_outside_solutions(&self) -> Result<()> {
+        if !self.modes.is_set() {
+            return Ok(());
+        }
+
+        self.modes.apply(&self.output_path, false).await?;
+        let mut state_paths = vec![self.output_path.join(AUXILIAIRE_STATE_DIR_NAME)];
+        for (_, track_path) in subdirectories(&self.output_path).await? {
+            self.modes.apply(&track_path, false).await?;
+            state_paths.push(track_path.join(AUXILIAIRE_STATE_DIR_NAME));
+        }
+        for state_path in state_paths {
+            if self.directory_exists(&state_path).await {
+                self.modes.apply(&state_path, true).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Marks the backup of the given solution read-only (see [`BackupArgs::immutable`]).
+    async fn make_immutable(&self, solution: &Solution, solution_output_path: &Path) -> Result<()> {
+        set_immutable(solution_output_path, true)
+            .await
+            .with_context(|| {
+                format!("failed to make solution to {} immutable", solution_desc(solution))
+            })?;
+        debug!("Solution to {}/{} marked immutable", solution.track.name, solution.exercise.name);
+        Ok(())
+    }
+
+    /// Copies the backup of the given solution to all [mirrors](BackupArgs::mirror) that do not
+    /// already contain it (see [`mirror_solution`]).
+    #[instrument(level = "trace", skip(self, solution, local_names), fields(solution.track.name, solution.exercise.name))]
+    async fn sync_mirrors(
+        &self,
+        solution: &Solution,
+        local_names: &SolutionRef,
+        solution_output_path: &Path,
+    ) -> Result<()> {
+        if self.args.dry_run {
+            return Ok(());
+        }
+
+        for mirror in &self.args.mirror {
+            let mirror_solution_path = local_names.path_in(mirror);
+            let mirrored =
+                mirror_solution(solution_output_path, &mirror_solution_path, !self.args.no_delete)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to mirror solution to {} to {}",
+                            solution_desc(solution),
+                            mirror.display(),
+                        )
+                    })?;
+            if mirrored {
+                debug!(
+                    "Solution to {}/{} mirrored to {}",
+                    solution.track.name,
+                    solution.exercise.name,
+                    mirror_solution_path.display(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[instrument(level = "trace", skip(self, solution, remote_solution), fields(solution.track.name, solution.exercise.name))]
+    async fn save_exercism_metadata(
+        &self,
+        solution: &Solution,
+        remote_solution: api::v1::solution::Solution,
+        solution_output_path: &Path,
+    ) -> Result<()> {
+        let mut metadata = ExercismMetadata::from(remote_solution);
+        if self.args.anonymize {
+            metadata.anonymize();
+        }
+        let metadata = serde_json::to_string(&metadata).with_context(|| {
+            format!(
+                "failed to persist Exercism metadata for solution to {} to JSON",
+                solution_desc(solution)
+            )
+        })?;
+
+        let mut metadata_file_path = solution_output_path.to_path_buf();
+        metadata_file_path.extend(EXERCISM_METADATA_FILE_NAME.split('/'));
+        self.create_file_parent_directory(&metadata_file_path)
+            .await?;
+        fs::write(&metadata_file_path, metadata)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to save Exercism metadata for solution to {} to {}",
+                    solution_desc(solution),
+                    metadata_file_path.display()
+                )
             })
     }
 
@@ -487,6 +1927,45 @@ impl BackupCommand {
         Ok(())
     }
 
+    /// Makes sure Exercism.org can be reached and that our API token is valid before starting
+    /// the backup, so that we can fail fast with a clear message instead of failing to back up
+    /// every solution with the same error.
+    #[instrument(level = "debug", skip_all)]
+    async fn check_exercism_availability(&self) -> Result<()> {
+        self.rate_limiter.acquire().await;
+        let token_valid = self
+            .timed_request("v1/validate_token", self.v1_client.validate_token())
+            .await
+            .with_context(|| "failed to reach Exercism.org; the website appears to be down")?;
+        if !token_valid {
+            bail!("Exercism API token is invalid; see --token to specify a valid token");
+        }
+
+        debug!("Exercism.org is reachable and API token is valid");
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn get_single_solution(&self, solution_id: &SolutionId) -> Result<Solution> {
+        let uuid = match solution_id {
+            SolutionId::Uuid(uuid) => uuid.clone(),
+            SolutionId::Url(solution_ref) => {
+                solution_uuid(&self.v2_client, solution_ref, None).await?
+            },
+        };
+
+        let _permit = self.metadata_limiter.get_permit().await;
+        self.rate_limiter.acquire().await;
+        Ok(self
+            .timed_request(
+                &format!("v2/solutions/{uuid}"),
+                self.v2_client.get_solution(&uuid, false),
+            )
+            .await
+            .with_context(|| format!("failed to get solution {uuid}"))?
+            .solution)
+    }
+
     #[instrument(level = "debug", skip(self))]
     async fn get_solutions_for_page(
         &self,
@@ -495,22 +1974,71 @@ impl BackupCommand {
         let filters = self.get_solutions_filters();
         let paging = solutions::Paging::for_page(page);
 
-        let _permit = self.limiter.get_permit().await;
-        let response = self
-            .v2_client
-            .get_solutions(Some(filters), Some(paging), Some(solutions::SortOrder::NewestFirst))
-            .await
-            .with_context(|| format!("failed to fetch solutions for page {page}"))?;
-        let solutions = response
+        let key = self.get_solutions_page_key(page);
+        let response: solutions::Response = self
+            .response_cache
+            .get_or_fetch(&key, Expiry::Ttl, || async {
+                let _permit = self.metadata_limiter.get_permit().await;
+                self.rate_limiter.acquire().await;
+                self.timed_request(
+                    &format!("v2/solutions?page={page}"),
+                    self.v2_client.get_solutions(
+                        Some(filters),
+                        Some(paging),
+                        Some(solutions::SortOrder::NewestFirst),
+                    ),
+                )
+                .await
+                .with_context(|| format!("failed to fetch solutions for page {page}"))
+            })
+            .await?;
+        self.remote_solutions.lock().unwrap().extend(
+            response
+                .results
+                .iter()
+                .map(|solution| (solution.track.name.clone(), solution.exercise.name.clone())),
+        );
+        let mut solutions = Vec::new();
+        for solution in response
             .results
             .into_iter()
             .filter(|solution| self.args.solution_matches(solution))
-            .collect();
+            .filter(|solution| match &self.solutions_to_retry {
+                Some(uuids) => uuids.contains(&solution.uuid),
+                None => true,
+            })
+        {
+            if self.difficulty_matches(&solution).await? {
+                solutions.push(solution);
+            }
+        }
         Ok((solutions, response.meta))
     }
 
+    async fn difficulty_matches(&self, solution: &Solution) -> Result<bool> {
+        let track = &solution.track.name;
+        self.exercise_difficulties
+            .matches(
+                &self.response_cache,
+                &self.args.difficulty,
+                track,
+                &solution.exercise.name,
+                || async {
+                    let _permit = self.metadata_limiter.get_permit_for(track).await;
+                    self.rate_limiter.acquire().await;
+                    self.timed_request(
+                        &format!("v2/tracks/{track}/exercises"),
+                        self.v2_client.get_exercises(track, None),
+                    )
+                    .await
+                    .with_context(|| format!("failed to fetch exercises for track {track}"))
+                },
+            )
+            .await
+    }
+
     #[instrument(level = "trace", skip_all, ret(level = "trace"))]
-    fn get_solutions_filters(&self) -> solutions::Filters {
+    fn get_solutions_filters(&self) -> solutions::Filters<'_> {
         let mut builder = solutions::Filters::builder();
 
         // These are more optimizations - it works even if we don't specify them since the
@@ -528,7 +2056,9 @@ impl BackupCommand {
                     .unwrap(),
             );
         }
-        if self.args.status == SolutionStatus::Published {
+        if self.args.status == SolutionStatus::Published
+            || self.args.published_tests_status != TestsStatus::Any
+        {
             // Published is the only status we can actually pass as a filter,
             // because otherwise we only get solutions with that specific status
             // (and not any status that is higher).
@@ -538,6 +2068,80 @@ impl BackupCommand {
         builder.build()
     }
 
+    fn should_process_solutions(&self) -> bool {
+        // In dry-run mode, we only need to process solutions if we need to output details
+        // about what would be downloaded or to estimate the download size.
+        !self.args.dry_run
+            || self.args.estimate_size
+            || self.args.show_diff
+            || self.plan.is_some()
+            || enabled!(Level::DEBUG)
+    }
+
+    fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Relaxed)
+    }
+
+    fn file_too_large(&self, file_size: usize) -> bool {
+        self.args
+            .max_file_size
+            .is_some_and(|max_file_size| file_size as u64 > max_file_size)
+    }
+
+    fn skip_file(&self, solution: &Solution, file: &str) {
+        warn!(
+            "File {file} in solution to {} is larger than the maximum file size and will not be backed up (see --max-file-size)",
+            solution_desc(solution),
+        );
+        self.skipped_files
+            .lock()
+            .unwrap()
+            .push((solution.clone(), file.into()));
+    }
+
+    fn is_skipped_file(&self, solution: &Solution, file: &str) -> bool {
+        self.skipped_files
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(skipped_solution, skipped_file)| {
+                skipped_solution.uuid == solution.uuid && skipped_file == file
+            })
+    }
+
+    fn without_skipped_files(&self, solution: &Solution, files: Vec<String>) -> Vec<String> {
+        files
+            .into_iter()
+            .filter(|file| !self.is_skipped_file(solution, file))
+            .collect()
+    }
+
+    fn max_total_size_exceeded(&self) -> bool {
+        let exceeded = self
+            .args
+            .max_total_size
+            .is_some_and(|max_total_size| self.metrics.total_bytes_downloaded() >= max_total_size);
+
+        if exceeded && !self.max_total_size_reached.swap(true, Ordering::Relaxed) {
+            warn!(
+                "Maximum total download size reached ({} bytes); remaining solutions will not be backed up",
+                self.metrics.total_bytes_downloaded()
+            );
+        }
+
+        exceeded
+    }
+
+    fn get_solutions_page_key(&self, page: i64) -> String {
+        // Must produce a different key for each possible result of `get_solutions_filters`.
+        let track = (self.args.track.len() == 1).then(|| self.args.track[0].as_str());
+        let exercise = (self.args.exercise.len() == 1).then(|| self.args.exercise[0].as_str());
+        let published = self.args.status == SolutionStatus::Published
+            || self.args.published_tests_status != TestsStatus::Any;
+
+        solutions_page_key(track, exercise, published, page)
+    }
+
     #[instrument(level = "trace", skip(self, solutions))]
     async fn create_track_directories(
         &self,
@@ -547,7 +2151,11 @@ impl BackupCommand {
         if !self.args.dry_run {
             let track_names = solutions
                 .iter()
-                .map(|solution| solution.track.name.as_str())
+                .map(|solution| {
+                    self.args
+                        .local_names(&solution.track.name, &solution.exercise.name)
+                        .track
+                })
                 .collect::<HashSet<_>>();
 
             for track_name in track_names {
@@ -561,25 +2169,49 @@ impl BackupCommand {
     }
 
     #[instrument(level = "trace", skip_all, fields(solution.track.name, solution.exercise.name))]
-    async fn get_solution_files(&self, solution: &Solution) -> Result<Vec<String>> {
-        let _permit = self.limiter.get_permit().await;
-        Ok(self
-            .v1_client
-            .get_solution(&solution.uuid)
+    async fn get_remote_solution(
+        &self,
+        solution: &Solution,
+    ) -> Result<api::v1::solution::Solution> {
+        // The list of files of a solution can only change when its `updated_at` timestamp does.
+        let key = format!("v1/solutions/{}/{}", solution.uuid, solution.updated_at);
+        self.response_cache
+            .get_or_fetch(&key, Expiry::Never, || async {
+                let _permit = self
+                    .metadata_limiter
+                    .get_permit_for(&solution.track.name)
+                    .await;
+                self.rate_limiter.acquire().await;
+                Ok(self
+                    .timed_request(
+                        &format!("v1/solutions/{}", solution.uuid),
+                        self.v1_client.get_solution(&solution.uuid),
+                    )
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to get list of files for solution to {}",
+                            solution_desc(solution),
+                        )
+                    })?
+                    .solution)
+            })
             .await
-            .with_context(|| {
-                format!(
-                    "failed to get list of files for solution to {}/{}",
-                    solution.track.name, solution.exercise.name,
-                )
-            })?
-            .solution
-            .files)
+    }
+
+    #[instrument(level = "trace", skip(self, solution), fields(solution.track.name, solution.exercise.name))]
+    async fn load_backup_state(
+        &self,
+        solution: &Solution,
+        solution_output_path: &Path,
+    ) -> BackupState {
+        let _permit = self.limiter.get_permit_for(&solution.track.name).await;
+        BackupState::for_backup(solution, solution_output_path).await
     }
 
     #[instrument(
         level = "trace",
-        skip(self, solution),
+        skip(self, solution, state),
         fields(solution.track.name, solution.exercise.name),
         ret(level = "trace")
     )]
@@ -587,11 +2219,12 @@ impl BackupCommand {
         &self,
         solution: &Solution,
         solution_output_path: &Path,
+        state: &BackupState,
     ) -> Result<(bool, bool)> {
-        let _permit = self.limiter.get_permit().await;
-        let state = BackupState::for_backup(solution, solution_output_path).await;
-
-        let solution_exists = self.directory_exists(solution_output_path).await;
+        let solution_exists = {
+            let _permit = self.limiter.get_permit_for(&solution.track.name).await;
+            self.directory_exists(solution_output_path).await
+        };
         let solution_needs_update = state.needs_update(solution)?;
 
         let needs_backup = match (solution_exists, solution_needs_update, self.args.overwrite) {
@@ -600,7 +2233,10 @@ impl BackupCommand {
                     solution.track.name, solution.exercise.name);
                 true
             },
-            (true, false, OverwritePolicy::IfNewer) | (true, false, OverwritePolicy::Never) => {
+            (true, false, OverwritePolicy::IfNewer)
+            | (true, false, OverwritePolicy::Never)
+            | (true, false, OverwritePolicy::Backup)
+            | (true, false, OverwritePolicy::Ask) => {
                 trace!(
                     "Solution to {}/{} already exists on disk and is up-to-date; skipping",
                     solution.track.name,
@@ -616,7 +2252,9 @@ impl BackupCommand {
                 );
                 false
             },
-            (true, true, OverwritePolicy::IfNewer) | (true, true, OverwritePolicy::Always) => {
+            (true, true, OverwritePolicy::IfNewer)
+            | (true, true, OverwritePolicy::Always)
+            | (true, true, OverwritePolicy::Backup) => {
                 trace!(
                     "Solution to {}/{} already exists on disk but needs updating; will be cleaned up",
                     solution.track.name,
@@ -624,12 +2262,180 @@ impl BackupCommand {
                 );
                 true
             },
+            (true, true, OverwritePolicy::Ask) if self.args.dry_run => {
+                trace!(
+                    "Solution to {}/{} already exists on disk but needs updating; would ask whether to overwrite",
+                    solution.track.name,
+                    solution.exercise.name
+                );
+                true
+            },
+            (true, true, OverwritePolicy::Ask) => {
+                self.ask_overwrite(solution, state, solution_output_path)
+                    .await?
+            },
             (false, _, _) => true,
         };
 
+        if needs_backup && solution_exists && self.args.no_delete {
+            bail!(
+                "solution to {} needs updating, but existing files cannot be overwritten with --no-delete",
+                solution_desc(solution),
+            );
+        }
+
         Ok((needs_backup, solution_exists))
     }
 
+    async fn ask_overwrite(
+        &self,
+        solution: &Solution,
+        state: &BackupState,
+        solution_output_path: &Path,
+    ) -> Result<bool> {
+        if *self.overwrite_all.lock().await {
+            return Ok(true);
+        }
+
+        // Computing changes requires downloading files, so do it before waiting for our turn
+        // to prompt, to avoid holding up other solutions' prompts.
+        let file_changes = self
+            .get_file_changes(solution, solution_output_path)
+            .await?;
+
+        let changes = match (&state.last_iteration_marker, &solution.last_iterated_at) {
+            (LastIterationMarker::LastIteratedAt(local), Some(remote)) => {
+                format!("last iterated at {local} locally, at {remote} on Exercism")
+            },
+            (LastIterationMarker::NumIterations(local), _) => {
+                format!("{local} iteration(s) locally, {} on Exercism", solution.num_iterations)
+            },
+            _ => format!("{} iteration(s) on Exercism", solution.num_iterations),
+        };
+        let question = format!(
+            "Solution to {}/{} has changed ({changes}):\n{}Overwrite?",
+            solution.track.name,
+            solution.exercise.name,
+            file_changes_summary(file_changes),
+        );
+
+        // Prompts must not be interleaved, so only one task can ask at a time. The user might
+        // have chosen to overwrite all solutions while we were computing changes.
+        let mut overwrite_all = self.overwrite_all.lock().await;
+        if *overwrite_all {
+            return Ok(true);
+        }
+
+        Ok(match ask_overwrite(question).await? {
+            OverwriteAnswer::Overwrite => true,
+            OverwriteAnswer::Skip => false,
+            OverwriteAnswer::OverwriteAll => {
+                *overwrite_all = true;
+                true
+            },
+        })
+    }
+
+    /// Compares the local files of a solution with its files on Exercism, so that the user
+    /// can see what would change before [choosing to overwrite it](OverwritePolicy::Ask).
+    ///
+    /// Files that exist both locally and on Exercism are downloaded to compare their content.
+    #[instrument(level = "trace", skip_all, fields(solution.track.name, solution.exercise.name))]
+    async fn get_file_changes(
+        &self,
+        solution: &Solution,
+        solution_output_path: &Path,
+    ) -> Result<Vec<FileChange>> {
+        let remote_solution = self.get_remote_solution(solution).await?;
+        let shortened_paths = ShortenedPaths::load(solution_output_path).await?;
+        let iterations_prefix = format!("{}/", self.iterations_dir_name);
+        let mut local_files = backup_files(solution_output_path)
+            .await?
+            .into_iter()
+            .map(|(file, _)| file)
+            .filter(|file| !file.starts_with(&iterations_prefix) && self.args.file_matches(file))
+            .collect::<HashSet<_>>();
+
+        let mut changes = Vec::new();
+        for file in remote_solution
+            .files
+            .iter()
+            .filter(|file| self.args.file_matches(file))
+        {
+            let local_file = shortened_paths.local_path(file);
+            if !local_files.remove(local_file) {
+                changes.push(FileChange::Added(file.clone()));
+                continue;
+            }
+
+            let mut file_path = solution_output_path.to_path_buf();
+            file_path.extend(local_file.split('/'));
+            let local_content = fs::read(&file_path)
+                .await
+                .with_context(|| format!("failed to read {}", file_path.display()))?;
+            if self.fetch_file(solution, file).await? != local_content {
+                changes.push(FileChange::Modified(file.clone()));
+            }
+        }
+        changes.extend(local_files.into_iter().map(FileChange::Removed));
+
+        Ok(changes)
+    }
+
+    /// Downloads the content of the given file of a solution, without saving it.
+    async fn fetch_file(&self, solution: &Solution, file: &str) -> Result<Vec<u8>> {
+        let _permit = self
+            .download_limiter
+            .get_permit_for(&solution.track.name)
+            .await;
+        self.rate_limiter.acquire().await;
+
+        let mut content = Vec::new();
+        let mut file_stream = self.v1_client.get_file(&solution.uuid, file).await;
+        while let Some(bytes) = file_stream.next().await {
+            let bytes = bytes.with_context(|| {
+                format!("failed to fetch file {file} in solution to {}", solution_desc(solution))
+            })?;
+            self.metrics.bytes_downloaded(bytes.len());
+            content.extend_from_slice(&bytes);
+        }
+
+        Ok(content)
+    }
+
+    /// Makes sure the paths of all `files` of the given solution fit the platform's limits,
+    /// shortening them if allowed (see [`BackupArgs::long_paths`]).
+    #[instrument(level = "trace", skip(self, solution, files), fields(solution.track.name, solution.exercise.name))]
+    fn shorten_file_paths(
+        &self,
+        solution: &Solution,
+        files: &[String],
+        solution_output_path: &Path,
+    ) -> Result<ShortenedPaths> {
+        let mut shortened_paths = ShortenedPaths::default();
+
+        for file in files {
+            let shortened = match self.args.long_paths {
+                LongPathsPolicy::Fail => {
+                    let mut file_path = solution_output_path.to_path_buf();
+                    file_path.extend(file.split('/'));
+                    check_path_length(&file_path).map(|_| None)
+                },
+                LongPathsPolicy::Shorten => shorten_file_path(solution_output_path, file),
+            }
+            .with_context(|| {
+                format!("failed to back up file {file} of solution to {}", solution_desc(solution))
+            })?;
+
+            if let Some(shortened) = shortened {
+                debug!("Path of file {file} too long, storing it as {shortened}");
+                shortened_paths.files.insert(file.clone(), shortened);
+            }
+        }
+
+        Ok(shortened_paths)
+    }
+
     #[instrument(level = "trace", skip(self, solution), fields(solution.track.name, solution.exercise.name))]
     async fn create_solution_directories(
         &self,
@@ -639,13 +2445,38 @@ impl BackupCommand {
         solution_output_path: &Path,
     ) -> Result<()> {
         if needs_backup {
+            if solution_exists && self.args.overwrite == OverwritePolicy::Backup {
+                let local_names = self
+                    .args
+                    .local_names(&solution.track.name, &solution.exercise.name);
+                let snapshots_path = solution_snapshots_path(
+                    &self.output_path,
+                    &local_names.track,
+                    &local_names.exercise,
+                );
+                let snapshot_path = self
+                    .snapshot_directory(solution_output_path, &snapshots_path)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to save existing files of solution to {}",
+                            solution_desc(solution),
+                        )
+                    })?;
+                info!(
+                    "Existing files of solution to {}/{} moved to {}",
+                    solution.track.name,
+                    solution.exercise.name,
+                    snapshot_path.display()
+                );
+            }
             if solution_exists {
                 self.remove_directory(solution_output_path)
                     .await
                     .with_context(|| {
                         format!(
-                            "failed to clean up existing directory for solution to {}/{}",
-                            solution.track.name, solution.exercise.name,
+                            "failed to clean up existing directory for solution to {}",
+                            solution_desc(solution),
                         )
                     })?;
             }
@@ -654,9 +2485,8 @@ impl BackupCommand {
                 .await
                 .with_context(|| {
                     format!(
-                        "failed to create destination directory for solution to {}/{}: {}",
-                        solution.track.name,
-                        solution.exercise.name,
+                        "failed to create destination directory for solution to {}: {}",
+                        solution_desc(solution),
                         solution_output_path.display(),
                     )
                 })?;
@@ -670,9 +2500,8 @@ impl BackupCommand {
                 .await
                 .with_context(|| {
                     format!(
-                        "failed to create destination directory for iterations of solution to {}/{}: {}",
-                        solution.track.name,
-                        solution.exercise.name,
+                        "failed to create destination directory for iterations of solution to {}: {}",
+                        solution_desc(solution),
                         iterations_output_path.display(),
                     )
                 })?;
@@ -690,25 +2519,41 @@ impl BackupCommand {
             return Ok(vec![]);
         }
 
-        let iterations = {
-            let _permit = self.limiter.get_permit().await;
-            self.v2_client
-                .get_solution(&solution.uuid, true)
-                .await
-                .with_context(|| {
-                    format!(
-                        "failed to get list of iterations for solution to {}/{}",
-                        solution.track.name, solution.exercise.name,
+        let key = format!("v2/solutions/{}/iterations/{}", solution.uuid, solution.updated_at);
+        let iterations: Vec<Iteration> = self
+            .response_cache
+            .get_or_fetch(&key, Expiry::Ttl, || async {
+                let _permit = self
+                    .metadata_limiter
+                    .get_permit_for(&solution.track.name)
+                    .await;
+                self.rate_limiter.acquire().await;
+                Ok(self
+                    .timed_request(
+                        &format!("v2/solutions/{}", solution.uuid),
+                        self.v2_client.get_solution(&solution.uuid, true),
                     )
-                })?
-                .iterations
-        };
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to get list of iterations for solution to {}",
+                            solution_desc(solution),
+                        )
+                    })?
+                    .iterations)
+            })
+            .await?;
 
-        Ok(iterations
+        let mut iterations = iterations
             .into_iter()
             .filter(|iter| self.args.iteration_matches(iter))
             .sorted_unstable_by_key(|iter| iter.index)
-            .collect_vec())
+            .collect_vec();
+        if let Some(latest) = self.args.iterations_latest {
+            iterations.drain(..iterations.len().saturating_sub(latest));
+        }
+
+        Ok(iterations)
     }
 
     #[instrument(level = "trace", skip(self, solution), fields(solution.track.name, solution.exercise.name))]
@@ -727,80 +2572,30 @@ impl BackupCommand {
             return Ok(vec![]);
         }
 
-        let _permit = self.limiter.get_permit().await;
-        let mut iterations_dir_content =
-            fs::read_dir(&iterations_path).await.with_context(|| {
-                format!(
-                    "failed to list existing backed up iterations for solution to {}/{}",
-                    solution.track.name, solution.exercise.name,
-                )
-            })?;
-
-        let mut iterations = Vec::new();
-        loop {
-            match iterations_dir_content.next_entry().await {
-                Ok(Some(entry)) => {
-                    let iteration = entry
-                        .file_type()
-                        .await
-                        .ok()
-                        .and_then(|file_type| {
-                            file_type.is_dir().then(|| entry.file_name().into_string().ok())
-                        })
-                        .flatten()
-                        .and_then(|file_name| {
-                            file_name.parse::<i32>().ok()
-                        });
-                    if let Some(iteration) = iteration {
-                        iterations.push(iteration);
-                    }
-                },
-                Ok(None) => break,
-                Err(err) => return Err(err).with_context(|| {
-                    format!(
-                        "failed to scan existing iterations back up directory for solution to {}/{}",
-                        solution.track.name,
-                        solution.exercise.name,
-                    )
-                }),
-            }
-        }
-
-        iterations.sort_unstable();
-        Ok(iterations)
+        let _permit = self.limiter.get_permit_for(&solution.track.name).await;
+        list_iterations_in(&iterations_path).await.with_context(|| {
+            format!(
+                "failed to list existing backed up iterations for solution to {}",
+                solution_desc(solution),
+            )
+        })
     }
 
-    #[instrument(level = "trace", skip_all, ret(level = "trace"))]
-    fn get_iteration_sync_ops<M, E>(
+    fn cached_iterations_in_sync(
         &self,
-        matching_iterations: M,
-        existing_iterations: E,
-    ) -> SyncOps
-    where
-        M: IntoIterator<Item = Iteration>,
-        E: IntoIterator<Item = i32>,
-    {
-        let mut existing_it = existing_iterations.into_iter().peekable();
-
-        let mut ops = SyncOps::default();
-        for matching in matching_iterations {
-            while let Some(existing) = existing_it.next_if(|&ne| ne < matching.index) {
-                ops.existing_iterations_to_clean_up.push(existing);
-            }
-            if existing_it.next_if_eq(&matching.index).is_none() {
-                ops.iterations_to_backup.push(matching.clone());
-            }
-        }
-        ops.existing_iterations_to_clean_up.extend(existing_it);
-
-        // Existing iterations are fetched even if we don't want to clean them up, because
-        // we need them to compute which iterations are new. However, if we don't want to
-        // clean them up, remove them here.
-        if !self.args.iterations_sync_policy.clean_up_old() {
-            ops.existing_iterations_to_clean_up.clear();
-        }
-
-        ops
+        cached_iterations: &[i32],
+        existing_iterations: &[i32],
+    ) -> bool {
+        // Cached iterations are those that matched our filters during the last backup;
+        // if nothing changed since then, we only need to make sure they're all on disk
+        // (and that there is nothing to clean up, if needed).
+        cached_iterations
+            .iter()
+            .all(|iteration| existing_iterations.contains(iteration))
+            && (!self.args.iterations_sync_policy.clean_up_old()
+                || existing_iterations
+                    .iter()
+                    .all(|iteration| cached_iterations.contains(iteration)))
     }
 
     #[instrument(level = "trace", skip(self))]
@@ -853,6 +2648,56 @@ impl BackupCommand {
         Ok(())
     }
 
+    /// Moves the content of the given solution directory to a new snapshot in `snapshots_path`,
+    /// then removes the oldest snapshots (see [`BackupArgs::keep_overwritten`]).
+    #[instrument(level = "trace", skip(self))]
+    async fn snapshot_directory(&self, dir_path: &Path, snapshots_path: &Path) -> Result<PathBuf> {
+        let snapshot_path = snapshot_output_path(snapshots_path, false)?;
+        fs::create_dir_all(&snapshot_path).await?;
+
+        // Like `remove_directory`, we leave iterations and backup state where they are,
+        // since they are managed separately.
+        let mut dir_content = fs::read_dir(dir_path).await?;
+        while let Some(entry) = dir_content.next_entry().await? {
+            let entry_path = entry.path();
+            if !self.should_skip_dir_entry(&entry_path) {
+                fs::rename(&entry_path, snapshot_path.join(entry.file_name())).await?;
+            }
+        }
+
+        prune_snapshots(snapshots_path, self.args.keep_overwritten as usize).await?;
+
+        Ok(snapshot_path)
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn archive_directory(
+        &self,
+        output_path: &Path,
+        track: &str,
+        exercise: &str,
+    ) -> Result<PathBuf> {
+        let mut archive_path: PathBuf =
+            [output_path, Path::new(ARCHIVE_DIR_NAME), Path::new(track)]
+                .iter()
+                .collect();
+        fs::create_dir_all(&archive_path).await?;
+
+        // Do not overwrite a previously archived solution for the same exercise.
+        archive_path.push(exercise);
+        if self.directory_exists(&archive_path).await {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            archive_path.set_file_name(format!("{exercise}.{timestamp}"));
+        }
+
+        fs::rename(output_path.join(track).join(exercise), &archive_path).await?;
+
+        Ok(archive_path)
+    }
+
     #[instrument(level = "trace", skip(self), ret(level = "trace"))]
     fn should_skip_dir_entry(&self, entry_path: &Path) -> bool {
         entry_path
@@ -871,3 +2716,608 @@ impl BackupCommand {
         })
     }
 }
+
+/// Returns the Exercism API endpoint used to download the given file of a solution,
+/// for [slow request warnings](BackupCommand::warn_if_slow) and HTTP debug logs.
+fn file_endpoint(solution: &Solution, file: &str) -> String {
+    format!("v1/solutions/{}/files/{file}", solution.uuid)
+}
+
+/// Returns a unified diff between the local and remote content of a solution file,
+/// or an empty string if they are identical.
+fn file_diff(path: &str, local_content: &[u8], remote_content: &[u8]) -> String {
+    if local_content == remote_content {
+        return String::new();
+    }
+
+    match (std::str::from_utf8(local_content), std::str::from_utf8(remote_content)) {
+        (Ok(local), Ok(remote)) => TextDiff::from_lines(local, remote)
+            .unified_diff()
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string(),
+        _ => format!("Binary file {path} differs\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: BackupArgs,
+    }
+
+    mod new {
+        use super::*;
+
+        #[test]
+        fn test_no_delete() {
+            let args = TestCli::parse_from([
+                "auxiliaire",
+                "backup-dir",
+                "--token",
+                "some_token",
+                "--no-delete",
+                "--remotely-deleted",
+                "prune",
+            ])
+            .args;
+            let err = BackupCommand::new(args, None).unwrap_err();
+            assert!(err.to_string().contains("--no-delete"), "{err:#}");
+
+            let result = TestCli::try_parse_from([
+                "auxiliaire",
+                "backup-dir",
+                "--no-delete",
+                "--snapshot",
+                "--keep-snapshots",
+                "3",
+            ]);
+            assert!(result.is_err());
+        }
+    }
+
+    mod execute {
+        use serde_json::{json, Value};
+        use test_log::test;
+        use wiremock::http::Method;
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use super::*;
+        use crate::command::backup::snapshot::list_snapshots;
+        use crate::test_fixtures;
+
+        /// Files of a solution, as `(file name, content)` pairs.
+        type Files<'a> = &'a [(&'a str, &'a str)];
+
+        /// Starts a mock Exercism server returning the given solutions (see [`mount_solutions`]).
+        async fn mock_exercism(solutions: &[(Value, Files<'_>)]) -> MockServer {
+            let mock_server = MockServer::start().await;
+            mount_solutions(&mock_server, solutions).await;
+            mock_server
+        }
+
+        /// Mounts the endpoints needed to back up the given solutions (as returned by
+        /// [`test_fixtures::solution_json`]) along with their files.
+        async fn mount_solutions(mock_server: &MockServer, solutions: &[(Value, Files<'_>)]) {
+            Mock::given(method(Method::GET))
+                .and(path("/validate_token"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({ "status": { "token": "valid" } })),
+                )
+                .mount(mock_server)
+                .await;
+
+            Mock::given(method(Method::GET))
+                .and(path("/solutions"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "results": solutions.iter().map(|(solution, _)| solution).collect::<Vec<_>>(),
+                    "meta": { "current_page": 1, "total_count": solutions.len(), "total_pages": 1 },
+                })))
+                .mount(mock_server)
+                .await;
+
+            for (solution, files) in solutions {
+                let uuid = solution["uuid"].as_str().unwrap();
+                let track = &solution["track"]["slug"];
+                let exercise = &solution["exercise"]["slug"];
+
+                Mock::given(method(Method::GET))
+                    .and(path(format!("/solutions/{uuid}")))
+                    .and(query_param_is_missing("sideload"))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "solution": {
+                            "id": uuid,
+                            "url": solution["private_url"],
+                            "user": { "handle": "clechasseur", "is_requester": true },
+                            "exercise": {
+                                "id": exercise,
+                                "instructions_url": solution["private_url"],
+                                "track": { "id": track, "language": track },
+                            },
+                            "file_download_base_url": format!("{}/solutions/{uuid}/files/", mock_server.uri()),
+                            "files": files.iter().map(|(file, _)| file).collect::<Vec<_>>(),
+                        },
+                    })))
+                    .mount(mock_server)
+                    .await;
+
+                for (file, content) in *files {
+                    Mock::given(method(Method::GET))
+                        .and(path(format!("/solutions/{uuid}/files/{file}")))
+                        .respond_with(ResponseTemplate::new(200).set_body_string(*content))
+                        .mount(mock_server)
+                        .await;
+                }
+            }
+        }
+
+        /// Mounts the endpoints needed to back up the given iterations (as `(index, files)` pairs)
+        /// of a solution (as returned by [`test_fixtures::solution_json`]).
+        async fn mount_iterations(
+            mock_server: &MockServer,
+            solution: &Value,
+            iterations: &[(i32, Files<'_>)],
+        ) {
+            let uuid = solution["uuid"].as_str().unwrap();
+
+            Mock::given(method(Method::GET))
+                .and(path(format!("/solutions/{uuid}")))
+                .and(query_param("sideload", "iterations"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "solution": solution,
+                    "iterations": iterations
+                        .iter()
+                        .map(|(index, _)| test_fixtures::iteration_json(*index, false))
+                        .collect::<Vec<_>>(),
+                })))
+                .mount(mock_server)
+                .await;
+
+            for (index, files) in iterations {
+                Mock::given(method(Method::GET))
+                    .and(path(format!("/solutions/{uuid}/submissions/submission-{index}/files")))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "files": files
+                            .iter()
+                            .map(|(file, content)| json!({ "filename": file, "content": content, "digest": "" }))
+                            .collect::<Vec<_>>(),
+                    })))
+                    .mount(mock_server)
+                    .await;
+            }
+        }
+
+        fn get_args(output_path: &Path, args: &[&str]) -> BackupArgs {
+            let output_path = output_path.to_str().unwrap();
+            TestCli::parse_from(
+                ["auxiliaire", output_path, "--token", "some_token"]
+                    .iter()
+                    .chain(args),
+            )
+            .args
+        }
+
+        async fn backup(mock_server: &MockServer, output_path: &Path, args: &[&str]) -> Result<()> {
+            let command =
+                BackupCommand::new(get_args(output_path, args), Some(&mock_server.uri()))?;
+            BackupCommand::execute(command).await
+        }
+
+        fn read(file_path: &Path) -> String {
+            std::fs::read_to_string(file_path)
+                .unwrap_or_else(|err| panic!("failed to read {}: {err}", file_path.display()))
+        }
+
+        fn names(subdirectories: Vec<(String, PathBuf)>) -> Vec<String> {
+            subdirectories.into_iter().map(|(name, _)| name).collect()
+        }
+
+        #[test(tokio::test)]
+        async fn test_overwrite_backup() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution = test_fixtures::solution_json("rust", "poker", json!({}));
+            let mock_server =
+                mock_exercism(&[(solution, &[("src/lib.rs", "pub fn old() {}")])]).await;
+            backup(&mock_server, temp_dir.path(), &[]).await.unwrap();
+
+            mock_server.reset().await;
+            let solution = test_fixtures::solution_json(
+                "rust",
+                "poker",
+                json!({ "last_iterated_at": "2023-06-01T12:00:00Z" }),
+            );
+            mount_solutions(&mock_server, &[(solution, &[("src/lib.rs", "pub fn new() {}")])])
+                .await;
+            backup(&mock_server, temp_dir.path(), &["--overwrite", "backup"])
+                .await
+                .unwrap();
+
+            let solution_path = temp_dir.path().join("rust").join("poker");
+            assert_eq!("pub fn new() {}", read(&solution_path.join("src").join("lib.rs")));
+
+            let snapshots =
+                list_snapshots(&solution_snapshots_path(temp_dir.path(), "rust", "poker")).unwrap();
+            assert_eq!(1, snapshots.len());
+            assert_eq!("pub fn old() {}", read(&snapshots[0].join("src").join("lib.rs")));
+
+            // The previous version must not be mistaken for a solution by other commands.
+            let tracks = subdirectories(temp_dir.path()).await.unwrap();
+            assert_eq!(vec!["rust"], names(tracks));
+            let exercises = subdirectories(&temp_dir.path().join("rust")).await.unwrap();
+            assert_eq!(vec!["poker"], names(exercises));
+        }
+
+        #[test(tokio::test)]
+        async fn test_max_file_size_for_iterations() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution = test_fixtures::solution_json("rust", "poker", json!({}));
+            let mock_server =
+                mock_exercism(&[(solution.clone(), &[("src/lib.rs", "pub fn poker() {}")])]).await;
+            let large_content = "x".repeat(1024);
+            mount_iterations(
+                &mock_server,
+                &solution,
+                &[(1, &[("src/lib.rs", "pub fn poker() {}"), ("data.txt", &large_content)])],
+            )
+            .await;
+
+            let args =
+                get_args(temp_dir.path(), &["--iterations", "new", "--max-file-size", "100"]);
+            let command = BackupCommand::new(args, Some(&mock_server.uri())).unwrap();
+            BackupCommand::execute(Arc::clone(&command)).await.unwrap();
+
+            let iteration_path = temp_dir
+                .path()
+                .join("rust")
+                .join("poker")
+                .join(get_iterations_dir_name())
+                .join("1");
+            assert_eq!("pub fn poker() {}", read(&iteration_path.join("src").join("lib.rs")));
+            assert!(!iteration_path.join("data.txt").exists());
+
+            let skipped_files = command.skipped_files.lock().unwrap().clone();
+            assert_eq!(1, skipped_files.len());
+            assert_eq!("rust-poker", skipped_files[0].0.uuid);
+            assert_eq!(format!("{}/1/data.txt", get_iterations_dir_name()), skipped_files[0].1);
+        }
+
+        #[test(tokio::test)]
+        async fn test_iterations() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution =
+                test_fixtures::solution_json("rust", "poker", json!({ "num_iterations": 3 }));
+            let mock_server =
+                mock_exercism(&[(solution.clone(), &[("src/lib.rs", "pub fn v3() {}")])]).await;
+            let long_file_name = "a".repeat(300);
+            mount_iterations(
+                &mock_server,
+                &solution,
+                &[
+                    (1, &[("src/lib.rs", "pub fn v1() {}"), ("Cargo.toml", "[package]")]),
+                    (2, &[("src/lib.rs", "pub fn v2() {}"), ("tests/poker.rs", "#[test]")]),
+                    (3, &[("src/lib.rs", "pub fn v3() {}"), (&long_file_name, "too long")]),
+                ],
+            )
+            .await;
+
+            let err = backup(&mock_server, temp_dir.path(), &["--iterations", "new"])
+                .await
+                .unwrap_err();
+
+            // The file that could not be saved must be identifiable in the error.
+            let err = format!("{err:?}");
+            assert!(err.contains(&format!("({long_file_name})")), "{err}");
+            assert!(err.contains("of iteration 3 of solution to"), "{err}");
+
+            let iterations_path = temp_dir
+                .path()
+                .join("rust")
+                .join("poker")
+                .join(get_iterations_dir_name());
+            let iteration_file = |iteration: &str, file: &str| {
+                let mut file_path = iterations_path.join(iteration);
+                file_path.extend(file.split('/'));
+                read(&file_path)
+            };
+            assert_eq!("pub fn v1() {}", iteration_file("1", "src/lib.rs"));
+            assert_eq!("[package]", iteration_file("1", "Cargo.toml"));
+            assert_eq!("pub fn v2() {}", iteration_file("2", "src/lib.rs"));
+            assert_eq!("#[test]", iteration_file("2", "tests/poker.rs"));
+            assert!(!iterations_path.join("1").join("tests").exists());
+            assert!(!iterations_path.join("2").join("Cargo.toml").exists());
+        }
+
+        #[test(tokio::test)]
+        async fn test_file_download_error() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mock_server = MockServer::start().await;
+            // Mounted first so that it takes precedence over the mock returning the file.
+            Mock::given(method(Method::GET))
+                .and(path("/solutions/rust-poker/files/src/lib.rs"))
+                .respond_with(ResponseTemplate::new(500))
+                .mount(&mock_server)
+                .await;
+            let solution = test_fixtures::solution_json("rust", "poker", json!({}));
+            mount_solutions(&mock_server, &[(solution, &[("src/lib.rs", "")])]).await;
+
+            let err = backup(&mock_server, temp_dir.path(), &[])
+                .await
+                .unwrap_err();
+
+            let err = format!("{err:?}");
+            assert!(
+                err.contains(
+                    "failed to download file src/lib.rs in solution to rust/poker (https://exercism.org/tracks/rust/exercises/poker)"
+                ),
+                "{err}"
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_estimate_size() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let clock = test_fixtures::solution_json("rust", "clock", json!({}));
+            let poker = test_fixtures::solution_json("rust", "poker", json!({}));
+            let mock_server = mock_exercism(&[
+                (
+                    clock.clone(),
+                    &[("src/lib.rs", "pub struct Clock;"), ("Cargo.toml", "[package]")],
+                ),
+                (poker.clone(), &[("src/lib.rs", "pub fn poker() {}")]),
+            ])
+            .await;
+            mount_iterations(&mock_server, &clock, &[]).await;
+            mount_iterations(&mock_server, &poker, &[(1, &[("src/lib.rs", "pub fn v1() {}")])])
+                .await;
+
+            let args =
+                get_args(temp_dir.path(), &["--dry-run", "--estimate-size", "--iterations", "new"]);
+            let command = BackupCommand::new(args, Some(&mock_server.uri())).unwrap();
+            BackupCommand::execute(Arc::clone(&command)).await.unwrap();
+
+            let expected_size =
+                ["pub struct Clock;", "[package]", "pub fn poker() {}", "pub fn v1() {}"]
+                    .iter()
+                    .map(|content| content.len() as u64)
+                    .sum::<u64>();
+            assert_eq!(expected_size, command.metrics.total_bytes_downloaded());
+            assert_eq!(4, command.metrics.total_files_downloaded());
+
+            let written = std::fs::read_dir(temp_dir.path()).unwrap().count();
+            assert_eq!(0, written, "nothing should be written to disk in a dry run");
+        }
+
+        #[test(tokio::test)]
+        async fn test_verify_after() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution = test_fixtures::solution_json("rust", "poker", json!({}));
+            let mock_server = mock_exercism(&[(
+                solution,
+                &[("src/lib.rs", "pub fn poker() {}"), ("Cargo.toml", "[package]")],
+            )])
+            .await;
+
+            let args = get_args(temp_dir.path(), &["--verify-after"]);
+            let command = BackupCommand::new(args, Some(&mock_server.uri())).unwrap();
+            BackupCommand::execute(Arc::clone(&command)).await.unwrap();
+
+            assert_eq!(1, command.downloaded_solutions.lock().unwrap().len());
+            assert_eq!(2, command.downloaded_files.lock().unwrap().len());
+        }
+
+        #[test(tokio::test)]
+        async fn test_verify_after_failure() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution = test_fixtures::solution_json("rust", "poker", json!({}));
+            let mock_server = mock_exercism(&[(
+                solution,
+                &[("src/lib.rs", "pub fn poker() {}"), ("Cargo.toml", "[package]")],
+            )])
+            .await;
+
+            // Alter the backup between the download and the verification, which is what
+            // `execute` does when `--verify-after` is used.
+            let args = get_args(temp_dir.path(), &["--verify-after"]);
+            let command = BackupCommand::new(args, Some(&mock_server.uri())).unwrap();
+            BackupCommand::backup_until_interrupted(Arc::clone(&command))
+                .await
+                .unwrap();
+
+            let solution_path = temp_dir.path().join("rust").join("poker");
+            std::fs::write(solution_path.join("src").join("lib.rs"), "pub fn").unwrap();
+            std::fs::remove_file(solution_path.join("Cargo.toml")).unwrap();
+
+            let err = command.verify_backup().await.unwrap_err();
+            assert_eq!("backup verification found 2 problem(s)", err.to_string());
+        }
+
+        #[cfg(unix)]
+        #[test(tokio::test)]
+        async fn test_accounts_progress() {
+            use std::os::fd::AsRawFd;
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution = test_fixtures::solution_json("rust", "poker", json!({}));
+            let mock_server =
+                mock_exercism(&[(solution, &[("src/lib.rs", "pub fn poker() {}")])]).await;
+
+            let progress_file = tempfile::NamedTempFile::new().unwrap();
+            let progress_fd = progress_file.as_file().as_raw_fd().to_string();
+            let args = TestCli::parse_from([
+                "auxiliaire",
+                temp_dir.path().to_str().unwrap(),
+                "--account",
+                "a=token_a,b=token_b",
+                "--progress",
+                "json",
+                "--progress-fd",
+                &progress_fd,
+            ])
+            .args;
+            BackupCommand::execute_accounts(args, Some(&mock_server.uri()))
+                .await
+                .unwrap();
+
+            // Events of both accounts must be intact and tell which account they relate to.
+            let events = read(progress_file.path())
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .collect_vec();
+            assert_eq!(8, events.len());
+            for account in ["a", "b"] {
+                let account_events = events
+                    .iter()
+                    .filter(|event| event["account"] == account)
+                    .map(|event| event["event"].as_str().unwrap())
+                    .collect_vec();
+                assert_eq!(
+                    vec!["solution_started", "file_downloaded", "solution_finished", "finished"],
+                    account_events
+                );
+            }
+        }
+
+        #[test(tokio::test)]
+        async fn test_max_total_size() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mock_server = mock_exercism(&[
+                (
+                    test_fixtures::solution_json("rust", "clock", json!({})),
+                    &[("src/lib.rs", "pub struct Clock;")],
+                ),
+                (
+                    test_fixtures::solution_json("rust", "poker", json!({})),
+                    &[("src/lib.rs", "pub fn poker() {}")],
+                ),
+            ])
+            .await;
+
+            // Queuing a single solution at a time ensures the first one is done when the second
+            // one starts; the cap is smaller than the first solution, which must still complete.
+            let args = get_args(
+                temp_dir.path(),
+                &["--max-total-size", "10", "--max-queued-solutions", "1"],
+            );
+            let command = BackupCommand::new(args, Some(&mock_server.uri())).unwrap();
+            let result = BackupCommand::execute(Arc::clone(&command)).await;
+
+            // Reaching the cap is not an error: it is only reported with a warning.
+            assert!(result.is_ok(), "{result:?}");
+            assert!(command.max_total_size_reached.load(Ordering::Relaxed));
+
+            let rust_path = temp_dir.path().join("rust");
+            assert_eq!(
+                "pub struct Clock;",
+                read(&rust_path.join("clock").join("src").join("lib.rs"))
+            );
+            assert!(!rust_path.join("poker").exists());
+            assert_eq!(1, command.metrics.total_files_downloaded());
+            assert_eq!(
+                vec![("rust".to_string(), "clock".to_string())],
+                *command.backed_up_solutions.lock().unwrap()
+            );
+
+            let requests = mock_server.received_requests().await.unwrap();
+            assert!(!requests
+                .iter()
+                .any(|request| request.url.path() == "/solutions/rust-poker/files/src/lib.rs"));
+        }
+    }
+
+    mod check_exercism_availability {
+        use serde_json::json;
+        use test_log::test;
+        use wiremock::http::Method;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use super::*;
+
+        async fn check(status: u16) -> Result<()> {
+            let mock_server = MockServer::start().await;
+            Mock::given(method(Method::GET))
+                .and(path("/validate_token"))
+                .respond_with(
+                    ResponseTemplate::new(status)
+                        .set_body_json(json!({ "status": { "token": "valid" } })),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let args =
+                TestCli::parse_from(["auxiliaire", "backup-dir", "--token", "some_token"]).args;
+            let command = BackupCommand::new(args, Some(&mock_server.uri())).unwrap();
+            command.check_exercism_availability().await
+        }
+
+        #[test(tokio::test)]
+        async fn test_valid_token() {
+            assert!(check(200).await.is_ok());
+        }
+
+        #[test(tokio::test)]
+        async fn test_invalid_token() {
+            let err = check(401).await.unwrap_err();
+            assert!(err.to_string().contains("token is invalid"), "{err:#}");
+        }
+
+        #[test(tokio::test)]
+        async fn test_exercism_down() {
+            let err = check(503).await.unwrap_err();
+            assert!(err.to_string().contains("appears to be down"), "{err:#}");
+        }
+
+        #[test(tokio::test)]
+        async fn test_v1_api_base_url() {
+            let mock_server = MockServer::start().await;
+            Mock::given(method(Method::GET))
+                .and(path("/api/v1/validate_token"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({ "status": { "token": "valid" } })),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let v1_api_base_url = format!("{}/api/v1/", mock_server.uri());
+            let args = TestCli::parse_from([
+                "auxiliaire",
+                "backup-dir",
+                "--token",
+                "some_token",
+                "--v1-api-base-url",
+                &v1_api_base_url,
+            ])
+            .args;
+            let command = BackupCommand::new(args, Some("http://localhost:1")).unwrap();
+            assert!(command.check_exercism_availability().await.is_ok());
+        }
+    }
+
+    mod file_diff {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            assert_eq!("", file_diff("rust/poker/src/lib.rs", b"fn a() {}\n", b"fn a() {}\n"));
+
+            let diff = file_diff("rust/poker/src/lib.rs", b"fn a() {}\n", b"fn b() {}\n");
+            assert_eq!(
+                "--- a/rust/poker/src/lib.rs\n+++ b/rust/poker/src/lib.rs\n@@ -1 +1 @@\n-fn a() {}\n+fn b() {}\n",
+                diff
+            );
+
+            assert_eq!(
+                "Binary file rust/poker/data.bin differs\n",
+                file_diff("rust/poker/data.bin", &[0xff, 0xfe], &[0xff])
+            );
+        }
+    }
+}
@@ -1,49 +1,335 @@
 //! Arguments that can be passed to the [`Backup`](crate::command::Command::Backup) command.
 
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fmt::{Debug, Formatter};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
+use anyhow::{anyhow, Context};
+use clap::builder::{RangedU64ValueParser, TypedValueParser};
+use clap::error::ErrorKind;
 use clap::{Args, ValueEnum};
+use itertools::Itertools;
 use mini_exercism::api::v2::iteration::Iteration;
 use mini_exercism::api::v2::solution::Solution;
-use mini_exercism::api::v2::{iteration, solution};
+use mini_exercism::api::v2::tests::Status as TestRunStatus;
+use mini_exercism::api::v2::{exercise, iteration, solution};
+use mini_exercism::http::header::{HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use crate::command::args::{FilePattern, ResponseCacheArgs, SolutionFilter, SolutionRef};
+use crate::command::backup::permissions::parse_mode;
+use crate::Error;
 
 /// Command-line arguments accepted by the [`Backup`](crate::command::Command::Backup) command.
 #[derive(Debug, Clone, Args)]
 pub struct BackupArgs {
     /// Path where to store the downloaded solutions
+    #[arg(env = "AUXILIAIRE_PATH")]
     pub path: PathBuf,
 
     /// Exercism.org API token; if unspecified, CLI token will be used instead
-    #[arg(long)]
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
     pub token: Option<String>,
 
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Back up the solutions of the given Exercism.org account (as NAME=TOKEN, or NAME=@COMMAND to get the token from a command) in its own subdirectory (can be used multiple times)
+    #[arg(
+        long,
+        value_name = "NAME=TOKEN",
+        value_parser = AccountValueParser,
+        conflicts_with_all = ["token", "token_command", "solution_uuid", "metrics_file", "error_report", "mentoring_requests"],
+        env = "AUXILIAIRE_ACCOUNT",
+        hide_env_values = true,
+        value_delimiter = ','
+    )]
+    pub account: Vec<Account>,
+
+    /// Additional HTTP header to send with Exercism API requests, as `NAME: VALUE` (can be used multiple times)
+    #[arg(
+        long = "header",
+        value_name = "HEADER",
+        env = "AUXILIAIRE_HEADER",
+        hide_env_values = true
+    )]
+    pub headers: Vec<HttpHeader>,
+
+    /// Base URL of the Exercism API v1, used to download solution files; if unspecified, Exercism.org's API is used
+    #[arg(long, value_name = "URL", env = "AUXILIAIRE_V1_API_BASE_URL")]
+    pub v1_api_base_url: Option<String>,
+
+    /// Base URL of the Exercism API v2, used to fetch solutions and iterations; if unspecified, Exercism.org's API is used
+    #[arg(long, value_name = "URL", env = "AUXILIAIRE_V2_API_BASE_URL")]
+    pub v2_api_base_url: Option<String>,
+
+    /// Only back up the solution with the given UUID or private URL, ignoring other filters
+    #[arg(
+        long,
+        value_name = "UUID|URL",
+        conflicts_with = "retry_failed",
+        env = "AUXILIAIRE_SOLUTION_UUID"
+    )]
+    pub solution_uuid: Option<SolutionId>,
+
     /// Only download solutions in the given track(s) (can be used multiple times)
-    #[arg(short, long)]
+    #[arg(short, long, env = "AUXILIAIRE_TRACK", value_delimiter = ',')]
     pub track: Vec<String>,
 
     /// Only download solutions for the given exercise(s) (can be used multiple times)
-    #[arg(short, long)]
+    #[arg(short, long, env = "AUXILIAIRE_EXERCISE", value_delimiter = ',')]
     pub exercise: Vec<String>,
 
+    /// Only download solutions matching the `track/exercise` patterns listed in the given file (see README)
+    #[arg(long, value_name = "PATH", value_parser = SolutionFilter::load, env = "AUXILIAIRE_FILTER_FILE")]
+    pub filter_file: Option<SolutionFilter>,
+
+    /// Store solutions under the track/exercise names mapped in the given file (see README)
+    #[arg(long, value_name = "PATH", value_parser = NameMapping::load, env = "AUXILIAIRE_RENAME_FILE")]
+    pub rename_file: Option<NameMapping>,
+
+    /// Do not back up hidden files of solutions (files or directories whose name starts with a dot)
+    #[arg(
+        long,
+        default_value_t = false,
+        overrides_with = "include_hidden",
+        env = "AUXILIAIRE_EXCLUDE_HIDDEN"
+    )]
+    pub exclude_hidden: bool,
+
+    /// Back up hidden files of solutions (the default); overrides --exclude-hidden
+    #[arg(
+        long,
+        default_value_t = false,
+        overrides_with = "exclude_hidden",
+        env = "AUXILIAIRE_INCLUDE_HIDDEN"
+    )]
+    pub include_hidden: bool,
+
+    /// Do not back up solution files matching the given glob pattern, like `*.png` (can be used multiple times)
+    #[arg(long, value_name = "PATTERN", env = "AUXILIAIRE_EXCLUDE_FILE", value_delimiter = ',')]
+    pub exclude_file: Vec<FilePattern>,
+
     /// Only download solutions with the given status (or greater)
-    #[arg(short, long, value_enum, default_value_t = SolutionStatus::Any)]
+    #[arg(short, long, value_enum, default_value_t = SolutionStatus::Any, env = "AUXILIAIRE_STATUS")]
     pub status: SolutionStatus,
 
+    /// Only back up published solutions whose published iteration's tests have the given status
+    #[arg(long, value_enum, default_value_t = TestsStatus::Any, env = "AUXILIAIRE_PUBLISHED_TESTS_STATUS")]
+    pub published_tests_status: TestsStatus,
+
+    /// Only back up solutions to exercises with the given difficulty (can be used multiple times)
+    #[arg(long, value_enum, env = "AUXILIAIRE_DIFFICULTY", value_delimiter = ',')]
+    pub difficulty: Vec<ExerciseDifficulty>,
+
     /// How to handle solutions that already exist on disk
-    #[arg(short, long, value_enum, default_value_t = OverwritePolicy::IfNewer)]
+    #[arg(short, long, value_enum, default_value_t = OverwritePolicy::IfNewer, env = "AUXILIAIRE_OVERWRITE")]
     pub overwrite: OverwritePolicy,
 
+    /// With --overwrite backup, number of previous versions of each solution to keep; older versions are removed
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..), env = "AUXILIAIRE_KEEP_OVERWRITTEN")]
+    pub keep_overwritten: u64,
+
+    /// Never delete, move or overwrite existing files; solutions that would require it fail instead
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "keep_snapshots",
+        env = "AUXILIAIRE_NO_DELETE"
+    )]
+    pub no_delete: bool,
+
+    /// Mark solutions read-only once backed up, so that they cannot be modified by accident
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_IMMUTABLE")]
+    pub immutable: bool,
+
+    /// Allow updating or removing solutions that were marked read-only via --immutable
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_ALLOW_IMMUTABLE_UPDATE")]
+    pub allow_immutable_update: bool,
+
+    /// Mode to apply to files created during the backup, in octal (e.g. 644); not affected by the umask (Unix only)
+    #[arg(long, value_name = "MODE", value_parser = parse_mode, env = "AUXILIAIRE_FILE_MODE")]
+    pub file_mode: Option<u32>,
+
+    /// Mode to apply to directories created during the backup, in octal (e.g. 755); not affected by the umask (Unix only)
+    #[arg(long, value_name = "MODE", value_parser = parse_mode, env = "AUXILIAIRE_DIR_MODE")]
+    pub dir_mode: Option<u32>,
+
     /// Whether to also back up iterations and how
-    #[arg(short, long = "iterations", value_enum, default_value_t = IterationsSyncPolicy::DoNotSync)]
+    #[arg(short, long = "iterations", value_enum, default_value_t = IterationsSyncPolicy::DoNotSync, env = "AUXILIAIRE_ITERATIONS")]
     pub iterations_sync_policy: IterationsSyncPolicy,
 
+    /// Only back up published iterations
+    #[arg(long, env = "AUXILIAIRE_ITERATIONS_PUBLISHED_ONLY")]
+    pub iterations_published_only: bool,
+
+    /// Only back up iterations whose tests have the given status
+    #[arg(long, value_enum, default_value_t = TestsStatus::Any, env = "AUXILIAIRE_ITERATIONS_TESTS_STATUS")]
+    pub iterations_tests_status: TestsStatus,
+
+    /// Only back up iterations whose index is in the given range (e.g. `3..=7`, `3..`, `..=7` or `5`)
+    #[arg(long, value_name = "RANGE", env = "AUXILIAIRE_ITERATIONS_RANGE")]
+    pub iterations_range: Option<IterationRange>,
+
+    /// Only back up the given number of latest iterations
+    #[arg(long, value_name = "N", env = "AUXILIAIRE_ITERATIONS_LATEST")]
+    pub iterations_latest: Option<usize>,
+
+    /// Layout to use when storing solutions on disk
+    #[arg(long, value_enum, default_value_t = OutputLayout::Default, env = "AUXILIAIRE_LAYOUT")]
+    pub layout: OutputLayout,
+
+    /// What to do with solution files whose path is too long for the platform
+    #[arg(long, value_enum, default_value_t = LongPathsPolicy::Fail, env = "AUXILIAIRE_LONG_PATHS")]
+    pub long_paths: LongPathsPolicy,
+
+    /// Store each backup in a new snapshot directory named after the current time, hard-linking unchanged files from the previous snapshot
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_SNAPSHOT")]
+    pub snapshot: bool,
+
+    /// With --snapshot, number of snapshots to keep; older snapshots are removed after a successful backup
+    #[arg(long, value_name = "N", requires = "snapshot", value_parser = clap::value_parser!(u64).range(1..), env = "AUXILIAIRE_KEEP_SNAPSHOTS")]
+    pub keep_snapshots: Option<u64>,
+
+    /// Also write the backup to the given directory (can be used multiple times); files are only downloaded once
+    #[arg(long, value_name = "PATH", conflicts_with = "snapshot", env = "AUXILIAIRE_MIRROR")]
+    pub mirror: Vec<PathBuf>,
+
     /// Determine what solutions to back up without downloading them
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_DRY_RUN")]
     pub dry_run: bool,
 
+    /// With --dry-run, fetch files that would be downloaded to estimate the total download size
+    #[arg(long, default_value_t = false, requires = "dry_run", env = "AUXILIAIRE_ESTIMATE_SIZE")]
+    pub estimate_size: bool,
+
+    /// With --dry-run, print the differences between local files and those of solutions that would be updated
+    #[arg(long, default_value_t = false, requires = "dry_run", env = "AUXILIAIRE_SHOW_DIFF")]
+    pub show_diff: bool,
+
+    /// After the backup, list the files of the downloaded solutions again and make sure they were all saved intact
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "dry_run",
+        env = "AUXILIAIRE_VERIFY_AFTER"
+    )]
+    pub verify_after: bool,
+
+    /// Algorithm used to hash files in the backup manifest
+    #[arg(long, value_enum, default_value_t = ChecksumAlgorithm::Sha256, env = "AUXILIAIRE_CHECKSUM")]
+    pub checksum: ChecksumAlgorithm,
+
+    /// After writing the backup manifest, sign it with GPG using the given key (see the verify command)
+    #[arg(long, value_name = "KEY_ID", conflicts_with = "dry_run", env = "AUXILIAIRE_SIGN_KEY")]
+    pub sign_key: Option<String>,
+
     /// Maximum number of concurrent downloads
-    #[arg(short, long, default_value_t = 4)]
+    #[arg(
+        short,
+        long,
+        default_value_t = 4,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        env = "AUXILIAIRE_MAX_DOWNLOADS"
+    )]
     pub max_downloads: usize,
+
+    /// Maximum number of concurrent downloads for a single track; if unspecified, only the global limit applies
+    #[arg(long, env = "AUXILIAIRE_MAX_PER_TRACK")]
+    pub max_per_track: Option<NonZeroUsize>,
+
+    /// Maximum number of concurrent requests fetching solution and iteration metadata; if unspecified, only the global limit applies
+    #[arg(long, env = "AUXILIAIRE_MAX_METADATA_REQUESTS")]
+    pub max_metadata_requests: Option<NonZeroUsize>,
+
+    /// Maximum number of concurrent file downloads; if unspecified, only the global limit applies
+    #[arg(long, env = "AUXILIAIRE_MAX_FILE_DOWNLOADS")]
+    pub max_file_downloads: Option<NonZeroUsize>,
+
+    /// Maximum number of Exercism API requests to perform per minute; if unspecified, requests are not rate-limited
+    #[arg(long, value_name = "REQUESTS", env = "AUXILIAIRE_MAX_RPM")]
+    pub max_rpm: Option<u32>,
+
+    /// Maximum number of solutions queued for backup at once; further solutions are only queued once others are backed up
+    #[arg(long, value_name = "N", default_value_t = 100, env = "AUXILIAIRE_MAX_QUEUED_SOLUTIONS")]
+    pub max_queued_solutions: usize,
+
+    /// Maximum number of bytes to download; once reached, no new solution will be backed up
+    #[arg(long, value_name = "BYTES", env = "AUXILIAIRE_MAX_TOTAL_SIZE")]
+    pub max_total_size: Option<u64>,
+
+    /// Maximum size of a single solution file, in bytes; larger files are skipped with a warning
+    #[arg(long, value_name = "BYTES", env = "AUXILIAIRE_MAX_FILE_SIZE")]
+    pub max_file_size: Option<u64>,
+
+    /// Path of a file where to write metrics about the backup in Prometheus textfile format
+    #[arg(long, env = "AUXILIAIRE_METRICS_FILE")]
+    pub metrics_file: Option<PathBuf>,
+
+    /// Number of seconds after which an Exercism API request or file download is reported as slow; 0 disables warnings
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 10,
+        env = "AUXILIAIRE_SLOW_REQUEST_THRESHOLD"
+    )]
+    pub slow_request_threshold: u64,
+
+    /// Log the URL, outcome and duration of every Exercism API request; with `bodies`, also log responses, with secrets redacted
+    #[arg(
+        long,
+        value_enum,
+        value_name = "DETAIL",
+        num_args = 0..=1,
+        default_missing_value = "requests",
+        env = "AUXILIAIRE_DEBUG_HTTP"
+    )]
+    pub debug_http: Option<DebugHttp>,
+
+    /// Number of solutions that took the most time to back up to report at the end of the backup
+    #[arg(long, value_name = "N", default_value_t = 5, env = "AUXILIAIRE_SLOWEST_SOLUTIONS")]
+    pub slowest_solutions: usize,
+
+    /// Path of a file where to write a JSON report of the errors that occurred if the backup fails
+    #[arg(long, value_name = "PATH", env = "AUXILIAIRE_ERROR_REPORT")]
+    pub error_report: Option<PathBuf>,
+
+    /// Only back up solutions that failed to be backed up according to the existing error report
+    #[arg(long, requires = "error_report", env = "AUXILIAIRE_RETRY_FAILED")]
+    pub retry_failed: bool,
+
+    /// Path of a file where to write a JSON list of solutions with a pending or in-progress mentoring request
+    #[arg(long, value_name = "PATH", env = "AUXILIAIRE_MENTORING_REQUESTS")]
+    pub mentoring_requests: Option<PathBuf>,
+
+    /// Also download the icons of the solutions' tracks and exercises, for offline browsing
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_WITH_ICONS")]
+    pub with_icons: bool,
+
+    /// Strip usernames and URLs containing them from generated metadata files, so that the backup can be shared publicly; solutions downloaded by the community command (in _community) are not anonymized
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_ANONYMIZE")]
+    pub anonymize: bool,
+
+    /// Emit progress events in the given format, on the standard error stream by default
+    #[arg(long, value_enum, env = "AUXILIAIRE_PROGRESS")]
+    pub progress: Option<ProgressFormat>,
+
+    /// Write progress events to the given file descriptor instead of the standard error stream (Unix only)
+    #[arg(long, value_name = "FD", requires = "progress", env = "AUXILIAIRE_PROGRESS_FD")]
+    pub progress_fd: Option<u32>,
+
+    /// What to do with backed up solutions that no longer exist on Exercism.org
+    #[arg(long, value_enum, default_value_t = RemotelyDeletedAction::Report, env = "AUXILIAIRE_REMOTELY_DELETED")]
+    pub remotely_deleted: RemotelyDeletedAction,
+
+    /// Options controlling the cache of Exercism API responses
+    #[command(flatten)]
+    pub cache: ResponseCacheArgs,
 }
 
 impl BackupArgs {
@@ -51,7 +337,9 @@ impl BackupArgs {
     pub fn solution_matches(&self, solution: &Solution) -> bool {
         self.track_matches(&solution.track.name)
             && self.exercise_matches(&solution.exercise.name)
+            && self.filter_file_matches(&solution.track.name, &solution.exercise.name)
             && self.solution_status_matches(solution.status.try_into().ok())
+            && self.published_tests_status_matches(solution)
     }
 
     /// Determines if the given [`Iteration`] should be backed up.
@@ -60,20 +348,99 @@ impl BackupArgs {
     ///
     /// There are currently no filters applied when fetching iterations,
     /// but we'll only keep the [published](mini_exercism::api::v2::iteration::Iteration::is_published)
-    /// ones if our [status filter](Self::status) tells us to.
+    /// ones if [told to](Self::iterations_published_only).
     pub fn iteration_matches(&self, iteration: &Iteration) -> bool {
         iteration.status != iteration::Status::Deleted
-            && (self.status < SolutionStatus::Published || iteration.is_published)
+            && (!self.iterations_published_only || iteration.is_published)
+            && self.iterations_tests_status.matches(iteration.tests_status)
+            && self
+                .iterations_range
+                .unwrap_or_default()
+                .contains(iteration.index)
+    }
+
+    /// Whether the set of [matching iterations](Self::iteration_matches) of a solution can only
+    /// change when new iterations are submitted.
+    ///
+    /// When filtering on publication or tests status, iterations can start or stop matching
+    /// even if no new iteration has been submitted.
+    pub fn iterations_cacheable(&self) -> bool {
+        !self.iterations_published_only && self.iterations_tests_status == TestsStatus::Any
     }
 
-    fn track_matches(&self, track_name: &str) -> bool {
+    /// Whether solutions that [no longer exist on Exercism.org](Self::remotely_deleted) can be
+    /// detected, which is only possible if the solutions list is not filtered by status.
+    pub fn can_detect_remotely_deleted(&self) -> bool {
+        !self.retry_failed
+            && self.status == SolutionStatus::Any
+            && self.published_tests_status == TestsStatus::Any
+    }
+
+    /// Returns the time after which requests are reported as slow (see `--slow-request-threshold`),
+    /// or `None` if slow requests should not be reported.
+    pub fn slow_request_threshold(&self) -> Option<Duration> {
+        (self.slow_request_threshold > 0).then(|| Duration::from_secs(self.slow_request_threshold))
+    }
+
+    /// Determines if solutions in the given track should be backed up.
+    pub fn track_matches(&self, track_name: &str) -> bool {
         self.track.is_empty() || self.track.iter().any(|t| t == track_name)
     }
 
-    fn exercise_matches(&self, exercise_name: &str) -> bool {
+    /// Determines if solutions for the given exercise should be backed up.
+    pub fn exercise_matches(&self, exercise_name: &str) -> bool {
         self.exercise.is_empty() || self.exercise.iter().any(|e| e == exercise_name)
     }
 
+    /// Determines if the solution to the given exercise matches the [filter file](Self::filter_file), if any.
+    pub fn filter_file_matches(&self, track_name: &str, exercise_name: &str) -> bool {
+        self.filter_file
+            .as_ref()
+            .map_or(true, |filter| filter.matches(track_name, exercise_name))
+    }
+
+    /// Determines if the given solution file should be backed up, taking
+    /// [`exclude_hidden`](Self::exclude_hidden) and [`exclude_file`](Self::exclude_file) into account.
+    pub fn file_matches(&self, file: &str) -> bool {
+        let hidden = file.split('/').any(|part| part.starts_with('.'));
+
+        if hidden && self.exclude_hidden && !self.include_hidden {
+            return false;
+        }
+
+        !self
+            .exclude_file
+            .iter()
+            .any(|pattern| pattern.matches(file))
+    }
+
+    /// Returns the track and exercise names under which the solution to the given exercise
+    /// is stored, taking the [mapping file](Self::rename_file) into account.
+    pub fn local_names(&self, track_name: &str, exercise_name: &str) -> SolutionRef {
+        match &self.rename_file {
+            Some(mapping) => mapping.local_names(track_name, exercise_name),
+            None => SolutionRef { track: track_name.into(), exercise: exercise_name.into() },
+        }
+    }
+
+    /// Returns the track and exercise names of all solutions that would be stored under the given
+    /// local names, taking the [mapping file](Self::rename_file) into account.
+    pub fn remote_names(&self, track_name: &str, exercise_name: &str) -> Vec<SolutionRef> {
+        match &self.rename_file {
+            Some(mapping) => mapping.remote_names(track_name, exercise_name),
+            None => vec![SolutionRef { track: track_name.into(), exercise: exercise_name.into() }],
+        }
+    }
+
+    fn published_tests_status_matches(&self, solution: &Solution) -> bool {
+        self.published_tests_status == TestsStatus::Any
+            || (solution.status == solution::Status::Published
+                && self
+                    .published_tests_status
+                    .matches(solution.published_iteration_head_tests_status))
+    }
+
+    #[allow(clippy::unnecessary_map_or)]
     fn solution_status_matches(&self, solution_status: Option<SolutionStatus>) -> bool {
         solution_status.map_or(false, |st| st >= self.status)
     }
@@ -110,6 +477,33 @@ impl TryFrom<solution::Status> for SolutionStatus {
     }
 }
 
+/// Possible exercise difficulty to filter for (see [`BackupArgs::difficulty`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExerciseDifficulty {
+    /// Easy exercises
+    Easy,
+
+    /// Medium exercises
+    Medium,
+
+    /// Hard exercises
+    Hard,
+}
+
+impl TryFrom<exercise::Difficulty> for ExerciseDifficulty {
+    type Error = exercise::Difficulty;
+
+    fn try_from(value: exercise::Difficulty) -> Result<Self, Self::Error> {
+        match value {
+            exercise::Difficulty::Easy => Ok(Self::Easy),
+            exercise::Difficulty::Medium => Ok(Self::Medium),
+            exercise::Difficulty::Hard => Ok(Self::Hard),
+            unsupported_difficulty => Err(unsupported_difficulty),
+        }
+    }
+}
+
 /// Policy used to decide what to do if a solution already exists on disk (see [`BackupArgs::overwrite`]).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum OverwritePolicy {
@@ -122,6 +516,12 @@ pub enum OverwritePolicy {
 
     /// Never overwrite existing solutions
     Never,
+
+    /// Like `if-newer`, but move existing files to the backup directory's `.auxiliaire/snapshots` directory instead of deleting them
+    Backup,
+
+    /// Ask before overwriting existing solutions that have a newer version (acts like `never` if input is not interactive)
+    Ask,
 }
 
 /// Policy used to decide whether to also back up iterations (see [`BackupArgs::iterations_sync_policy`]).
@@ -160,6 +560,444 @@ impl IterationsSyncPolicy {
     }
 }
 
+/// Possible tests status to filter for (see [`BackupArgs::iterations_tests_status`]
+/// and [`BackupArgs::published_tests_status`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TestsStatus {
+    /// Do not filter based on tests status
+    Any,
+
+    /// Tests have passed
+    Passed,
+
+    /// Tests have failed, errored out or could not be run
+    Failed,
+}
+
+impl TestsStatus {
+    /// Determines if the given tests status matches this filter.
+    pub fn matches(&self, tests_status: TestRunStatus) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Passed => tests_status == TestRunStatus::Passed,
+            Self::Failed => matches!(
+                tests_status,
+                TestRunStatus::Failed | TestRunStatus::Errored | TestRunStatus::Exceptioned
+            ),
+        }
+    }
+}
+
+/// Inclusive range of iteration indexes (see [`BackupArgs::iterations_range`]).
+///
+/// Can be parsed from a string like `3..=7`, `3..8`, `3..`, `..=7` or `5`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct IterationRange {
+    /// First iteration index in the range, if any.
+    pub start: Option<i32>,
+
+    /// Last iteration index in the range (inclusive), if any.
+    pub end: Option<i32>,
+}
+
+impl IterationRange {
+    /// Determines if the given iteration index is in this range.
+    pub fn contains(&self, index: i32) -> bool {
+        (self.start.unwrap_or(i32::MIN)..=self.end.unwrap_or(i32::MAX)).contains(&index)
+    }
+}
+
+impl FromStr for IterationRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || anyhow!("invalid iteration range '{s}': expected e.g. 3..=7, 3.., ..=7 or 5");
+        let parse_bound = |bound: &str| bound.trim().parse::<i32>().map_err(|_| invalid());
+        let parse_optional_bound = |bound: &str| {
+            (!bound.trim().is_empty())
+                .then(|| parse_bound(bound))
+                .transpose()
+        };
+
+        let range = match s.split_once("..") {
+            Some((start, end)) => {
+                let start = parse_optional_bound(start)?;
+                let end = match end.strip_prefix('=') {
+                    Some(end) => Some(parse_bound(end)?),
+                    None => parse_optional_bound(end)?.map(|end| end - 1),
+                };
+                Self { start, end }
+            },
+            None => {
+                let index = parse_bound(s)?;
+                Self { start: Some(index), end: Some(index) }
+            },
+        };
+
+        match range {
+            Self { start: Some(start), end: Some(end) } if start > end => Err(invalid()),
+            range => Ok(range),
+        }
+    }
+}
+
+/// Identifies a single solution to back up (see [`BackupArgs::solution_uuid`]).
+///
+/// Can be parsed from a solution UUID or from a solution's private URL
+/// (e.g. `https://exercism.org/tracks/rust/exercises/poker`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolutionId {
+    /// Solution UUID.
+    Uuid(String),
+
+    /// Solution's track and exercise, as found in its private URL.
+    Url(SolutionRef),
+}
+
+impl FromStr for SolutionId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !s.contains('/') {
+            if s.is_empty() || !s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                return Err(anyhow!("invalid solution UUID '{s}'"));
+            }
+            return Ok(Self::Uuid(s.into()));
+        }
+
+        // Private URLs look like `https://exercism.org/tracks/<track>/exercises/<exercise>`,
+        // possibly followed by other segments (like `/iterations`).
+        let segments = s
+            .split_once("/tracks/")
+            .map(|(_, path)| path.split(['/', '?', '#']).collect::<Vec<_>>());
+        match segments.as_deref() {
+            Some([track, "exercises", exercise, ..]) if !track.is_empty() && !exercise.is_empty() => {
+                Ok(Self::Url(SolutionRef { track: (*track).into(), exercise: (*exercise).into() }))
+            },
+            _ => Err(anyhow!(
+                "invalid solution URL '{s}': expected e.g. https://exercism.org/tracks/rust/exercises/poker"
+            )),
+        }
+    }
+}
+
+/// Exercism.org account whose solutions should be backed up (see [`BackupArgs::account`]).
+///
+/// Can be parsed from a string like `NAME=TOKEN` or `NAME=@COMMAND`; solutions are stored in
+/// the `NAME` subdirectory.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Account {
+    /// Name of the account, used as the name of its subdirectory.
+    pub name: String,
+
+    /// Exercism.org API token of the account.
+    pub token: AccountToken,
+}
+
+impl Debug for Account {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Make sure the token does not end up in logs.
+        f.debug_struct("Account")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FromStr for Account {
+    type Err = Error;
+
+    /// Parses an account from a string like `NAME=TOKEN` or `NAME=@COMMAND`.
+    ///
+    /// Errors never include the token, so that it does not end up in error messages.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, token) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow!("invalid account: expected NAME=TOKEN"))?;
+        let (name, token) = (name.trim(), token.trim());
+
+        if name.is_empty()
+            || name.starts_with('.')
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(anyhow!("invalid account name '{name}'"));
+        }
+        let token = match token.strip_prefix('@') {
+            Some(token_command) if token_command.trim().is_empty() => {
+                return Err(anyhow!("missing token command for account '{name}'"));
+            },
+            Some(token_command) => AccountToken::Command(token_command.trim().into()),
+            None if token.is_empty() => {
+                return Err(anyhow!("missing token for account '{name}'"));
+            },
+            None => AccountToken::Token(token.into()),
+        };
+
+        Ok(Self { name: name.into(), token })
+    }
+}
+
+/// Source of the API token of an [`Account`].
+#[derive(Clone, PartialEq, Eq)]
+pub enum AccountToken {
+    /// API token passed directly (`NAME=TOKEN`).
+    Token(String),
+
+    /// Command outputting the API token (`NAME=@COMMAND`), like [`BackupArgs::token_command`].
+    ///
+    /// This avoids passing the token itself as argument, where it could be seen by other users
+    /// of the system (for example via `ps`).
+    Command(String),
+}
+
+/// Parser for [`Account`]s.
+///
+/// Unlike the parser clap would use by default, errors do not include the rejected value,
+/// since it usually contains an API token.
+#[derive(Debug, Copy, Clone)]
+struct AccountValueParser;
+
+impl TypedValueParser for AccountValueParser {
+    type Value = Account;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let invalid_value = |err: &dyn std::fmt::Display| {
+            let arg = arg.map_or_else(|| "...".into(), ToString::to_string);
+            clap::Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value for '{arg}': {err}\n"),
+            )
+            .with_cmd(cmd)
+        };
+
+        value
+            .to_str()
+            .ok_or_else(|| invalid_value(&"value is not valid UTF-8"))?
+            .parse()
+            .map_err(|err: Error| invalid_value(&err))
+    }
+}
+
+/// Additional HTTP header sent with Exercism API requests (see [`BackupArgs::headers`]).
+///
+/// Can be parsed from a string like `NAME: VALUE`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HttpHeader {
+    /// Name of the header.
+    pub name: HeaderName,
+
+    /// Value of the header.
+    pub value: HeaderValue,
+}
+
+impl Debug for HttpHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Header values can contain secrets, so make sure they do not end up in logs.
+        f.debug_struct("HttpHeader")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FromStr for HttpHeader {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("invalid header '{s}': expected NAME: VALUE"))?;
+        let name = HeaderName::from_str(name.trim())
+            .with_context(|| format!("invalid header name '{}'", name.trim()))?;
+        let mut value = HeaderValue::from_str(value.trim())
+            .with_context(|| format!("invalid value for header '{name}'"))?;
+        value.set_sensitive(true);
+
+        Ok(Self { name, value })
+    }
+}
+
+/// Mapping used to store solutions under different track or exercise names in the backup
+/// directory, usually loaded from a mapping file (see [`load`](Self::load)).
+///
+/// Each line of a mapping file contains a rule in the form `<track> -> <new-track>`, which stores
+/// all solutions of a track in another track directory (possibly merging tracks), or
+/// `<track>/<exercise> -> <new-track>/<new-exercise>`, which stores a single solution under another
+/// name. Exercise rules take precedence over track rules. Empty lines and lines starting with `#`
+/// are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameMapping {
+    tracks: HashMap<String, String>,
+    exercises: HashMap<SolutionRef, SolutionRef>,
+}
+
+impl NameMapping {
+    /// Loads a mapping from the file at the given path.
+    pub fn load(path: &str) -> crate::Result<Self> {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read mapping file {path}"))?
+            .parse()
+            .with_context(|| format!("invalid mapping file {path}"))
+    }
+
+    /// Returns the track and exercise names under which the solution to the given exercise is stored.
+    pub fn local_names(&self, track_name: &str, exercise_name: &str) -> SolutionRef {
+        let solution = SolutionRef { track: track_name.into(), exercise: exercise_name.into() };
+
+        match self.exercises.get(&solution) {
+            Some(local) => local.clone(),
+            None => SolutionRef {
+                track: self
+                    .tracks
+                    .get(track_name)
+                    .cloned()
+                    .unwrap_or(solution.track),
+                exercise: solution.exercise,
+            },
+        }
+    }
+
+    /// Returns the track and exercise names of all solutions that would be stored under the
+    /// given local names (see [`local_names`](Self::local_names)).
+    pub fn remote_names(&self, track_name: &str, exercise_name: &str) -> Vec<SolutionRef> {
+        let local = SolutionRef { track: track_name.into(), exercise: exercise_name.into() };
+
+        let candidates = self
+            .exercises
+            .iter()
+            .filter(|(_, mapped)| **mapped == local)
+            .map(|(solution, _)| solution.clone())
+            .chain(
+                self.tracks
+                    .iter()
+                    .filter(|(_, mapped)| **mapped == local.track)
+                    .map(|(track, _)| SolutionRef {
+                        track: track.clone(),
+                        exercise: local.exercise.clone(),
+                    }),
+            )
+            .chain(std::iter::once(local.clone()));
+
+        candidates
+            .filter(|solution| self.local_names(&solution.track, &solution.exercise) == local)
+            .unique()
+            .collect()
+    }
+}
+
+impl FromStr for NameMapping {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mapping = Self::default();
+
+        for (line_number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || {
+                anyhow!(
+                    "invalid rule '{line}' on line {}: expected <track> -> <new-track> or <track>/<exercise> -> <new-track>/<new-exercise>",
+                    line_number + 1
+                )
+            };
+            let (from, to) = line.split_once("->").ok_or_else(invalid)?;
+            let (from, to) = (from.trim(), to.trim());
+            let valid_name = |name: &str| !name.is_empty() && !name.starts_with('.');
+
+            if valid_name(from) && valid_name(to) && !from.contains('/') && !to.contains('/') {
+                mapping.tracks.insert(from.into(), to.into());
+            } else {
+                match (from.parse::<SolutionRef>(), to.parse::<SolutionRef>()) {
+                    (Ok(from), Ok(to)) if valid_name(&to.track) && valid_name(&to.exercise) => {
+                        mapping.exercises.insert(from, to);
+                    },
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+
+        Ok(mapping)
+    }
+}
+
+/// Layout used to store solutions on disk (see [`BackupArgs::layout`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputLayout {
+    /// Store solutions in `<track>/<exercise>` directories
+    Default,
+
+    /// Store solutions like the Exercism CLI does, so that they can be submitted via `exercism submit`
+    #[value(alias = "workspace")]
+    ExercismWorkspace,
+}
+
+/// Policy used to handle solution files whose path is too long for the platform
+/// (see [`BackupArgs::long_paths`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum LongPathsPolicy {
+    /// Fail to back up the solution
+    Fail,
+
+    /// Shorten the file name using a hash suffix and record the original path in `.auxiliaire/shortened_paths.json`
+    Shorten,
+}
+
+/// Algorithm used to hash files (see [`BackupArgs::checksum`]).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumAlgorithm {
+    /// SHA-256 (slowest, but widely supported by other tools)
+    #[default]
+    Sha256,
+
+    /// BLAKE3 (cryptographic, much faster than SHA-256)
+    Blake3,
+
+    /// XXH3 128 bits (non-cryptographic, fastest)
+    Xxh3,
+}
+
+/// Possible actions to perform on backed up solutions that no longer exist on Exercism.org
+/// (see [`BackupArgs::remotely_deleted`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum RemotelyDeletedAction {
+    /// Report the solutions, but leave them as-is
+    Report,
+
+    /// Move the solutions to the `_archive` directory
+    Archive,
+
+    /// Remove the solutions
+    Prune,
+}
+
+/// Level of detail of HTTP debug logs (see [`BackupArgs::debug_http`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum DebugHttp {
+    /// Log the URL, outcome and duration of each request
+    Requests,
+
+    /// Also log the content of each response
+    Bodies,
+}
+
+/// Possible formats for progress events (see [`BackupArgs::progress`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ProgressFormat {
+    /// JSON Lines: one JSON object per event
+    Json,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,7 +1008,7 @@ mod tests {
         mod solution_matches {
             use super::*;
 
-            fn get_solution(status: Option<solution::Status>) -> Solution {
+            pub(super) fn get_solution(status: Option<solution::Status>) -> Solution {
                 let json = r#"{
                     "uuid": "00c717b68e1b4213b316df82636f5e0f",
                     "private_url": "https://exercism.org/tracks/rust/exercises/poker",
@@ -208,7 +1046,7 @@ mod tests {
                 solution
             }
 
-            fn get_args(
+            pub(super) fn get_args(
                 tracks: &[&str],
                 exercises: &[&str],
                 status: Option<SolutionStatus>,
@@ -216,13 +1054,66 @@ mod tests {
                 BackupArgs {
                     path: PathBuf::default(),
                     token: None,
+                    token_command: None,
+                    account: vec![],
+                    headers: vec![],
+                    v1_api_base_url: None,
+                    v2_api_base_url: None,
+                    solution_uuid: None,
                     track: tracks.iter().copied().map(Into::into).collect(),
                     exercise: exercises.iter().copied().map(Into::into).collect(),
+                    filter_file: None,
+                    rename_file: None,
+                    exclude_hidden: false,
+                    include_hidden: false,
+                    exclude_file: vec![],
                     status: status.unwrap_or(SolutionStatus::Any),
+                    published_tests_status: TestsStatus::Any,
+                    difficulty: vec![],
                     overwrite: OverwritePolicy::IfNewer,
+                    keep_overwritten: 5,
+                    no_delete: false,
+                    immutable: false,
+                    allow_immutable_update: false,
+                    file_mode: None,
+                    dir_mode: None,
                     iterations_sync_policy: IterationsSyncPolicy::DoNotSync,
+                    iterations_published_only: false,
+                    iterations_tests_status: TestsStatus::Any,
+                    iterations_range: None,
+                    iterations_latest: None,
+                    layout: OutputLayout::Default,
+                    long_paths: LongPathsPolicy::Fail,
+                    snapshot: false,
+                    keep_snapshots: None,
+                    mirror: vec![],
                     dry_run: false,
+                    estimate_size: false,
+                    show_diff: false,
+                    verify_after: false,
+                    checksum: ChecksumAlgorithm::Sha256,
+                    sign_key: None,
                     max_downloads: 4,
+                    max_per_track: None,
+                    max_metadata_requests: None,
+                    max_file_downloads: None,
+                    max_rpm: None,
+                    max_queued_solutions: 100,
+                    max_total_size: None,
+                    max_file_size: None,
+                    metrics_file: None,
+                    slow_request_threshold: 10,
+                    debug_http: None,
+                    slowest_solutions: 5,
+                    error_report: None,
+                    retry_failed: false,
+                    mentoring_requests: None,
+                    with_icons: false,
+                    anonymize: false,
+                    progress: None,
+                    progress_fd: None,
+                    remotely_deleted: RemotelyDeletedAction::Report,
+                    cache: ResponseCacheArgs::default(),
                 }
             }
 
@@ -391,6 +1282,136 @@ mod tests {
             }
         }
 
+        mod published_tests_status {
+            use super::*;
+
+            #[test]
+            fn test_all() {
+                let mut args = solution_matches::get_args(&[], &[], None);
+                let mut solution = solution_matches::get_solution(None);
+
+                args.published_tests_status = TestsStatus::Passed;
+                assert!(args.solution_matches(&solution));
+                solution.published_iteration_head_tests_status = TestRunStatus::Failed;
+                assert!(!args.solution_matches(&solution));
+
+                args.published_tests_status = TestsStatus::Failed;
+                assert!(args.solution_matches(&solution));
+                solution.status = solution::Status::Completed;
+                assert!(!args.solution_matches(&solution));
+
+                args.published_tests_status = TestsStatus::Any;
+                assert!(args.solution_matches(&solution));
+            }
+        }
+
+        mod can_detect_remotely_deleted {
+            use super::*;
+
+            #[test]
+            fn test_all() {
+                let mut args = solution_matches::get_args(&["rust"], &["poker"], None);
+                assert!(args.can_detect_remotely_deleted());
+
+                args.status = SolutionStatus::Published;
+                assert!(!args.can_detect_remotely_deleted());
+
+                args.status = SolutionStatus::Any;
+                args.published_tests_status = TestsStatus::Passed;
+                assert!(!args.can_detect_remotely_deleted());
+
+                args.published_tests_status = TestsStatus::Any;
+                args.retry_failed = true;
+                assert!(!args.can_detect_remotely_deleted());
+            }
+        }
+
+        mod file_matches {
+            use super::*;
+
+            #[test]
+            fn test_all() {
+                let mut args = solution_matches::get_args(&[], &[], None);
+                assert!(args.file_matches("src/lib.rs"));
+                assert!(args.file_matches(".editorconfig"));
+                assert!(args.file_matches("assets/logo.png"));
+
+                args.exclude_hidden = true;
+                assert!(args.file_matches("src/lib.rs"));
+                assert!(!args.file_matches(".editorconfig"));
+                assert!(!args.file_matches(".config/settings.json"));
+
+                args.include_hidden = true;
+                assert!(args.file_matches(".editorconfig"));
+
+                args.exclude_file = vec!["*.png".parse().unwrap(), "src/*.md".parse().unwrap()];
+                assert!(args.file_matches("src/lib.rs"));
+                assert!(!args.file_matches("assets/logo.png"));
+                assert!(!args.file_matches("src/notes.md"));
+                assert!(args.file_matches("README.md"));
+            }
+        }
+
+        mod concurrency_limits {
+            use clap::Parser;
+
+            use super::*;
+
+            #[derive(Debug, Parser)]
+            struct TestCli {
+                #[command(flatten)]
+                args: BackupArgs,
+            }
+
+            #[test]
+            fn test_valid() {
+                let args = TestCli::parse_from([
+                    "auxiliaire",
+                    "backup-dir",
+                    "--max-downloads",
+                    "8",
+                    "--max-per-track",
+                    "2",
+                    "--max-metadata-requests",
+                    "3",
+                    "--max-file-downloads",
+                    "4",
+                ])
+                .args;
+                assert_eq!(8, args.max_downloads);
+                assert_eq!(NonZeroUsize::new(2), args.max_per_track);
+                assert_eq!(NonZeroUsize::new(3), args.max_metadata_requests);
+                assert_eq!(NonZeroUsize::new(4), args.max_file_downloads);
+            }
+
+            #[test]
+            fn test_zero() {
+                // A limit of 0 would make the backup wait forever for a permit.
+                for arg in [
+                    "--max-downloads",
+                    "--max-per-track",
+                    "--max-metadata-requests",
+                    "--max-file-downloads",
+                ] {
+                    let result = TestCli::try_parse_from(["auxiliaire", "backup-dir", arg, "0"]);
+                    assert!(result.is_err(), "{arg} 0 should be rejected");
+                }
+            }
+        }
+
+        mod slow_request_threshold {
+            use super::*;
+
+            #[test]
+            fn test_all() {
+                let mut args = solution_matches::get_args(&["rust"], &["poker"], None);
+                assert_eq!(Some(Duration::from_secs(10)), args.slow_request_threshold());
+
+                args.slow_request_threshold = 0;
+                assert_eq!(None, args.slow_request_threshold());
+            }
+        }
+
         mod iteration_matches {
             use super::*;
 
@@ -436,13 +1457,66 @@ mod tests {
                 BackupArgs {
                     path: PathBuf::default(),
                     token: None,
+                    token_command: None,
+                    account: vec![],
+                    headers: vec![],
+                    v1_api_base_url: None,
+                    v2_api_base_url: None,
+                    solution_uuid: None,
                     track: vec![],
                     exercise: vec![],
+                    filter_file: None,
+                    rename_file: None,
+                    exclude_hidden: false,
+                    include_hidden: false,
+                    exclude_file: vec![],
                     status: status.unwrap_or(SolutionStatus::Any),
+                    published_tests_status: TestsStatus::Any,
+                    difficulty: vec![],
                     overwrite: OverwritePolicy::IfNewer,
+                    keep_overwritten: 5,
+                    no_delete: false,
+                    immutable: false,
+                    allow_immutable_update: false,
+                    file_mode: None,
+                    dir_mode: None,
                     iterations_sync_policy: IterationsSyncPolicy::FullSync,
+                    iterations_published_only: false,
+                    iterations_tests_status: TestsStatus::Any,
+                    iterations_range: None,
+                    iterations_latest: None,
+                    layout: OutputLayout::Default,
+                    long_paths: LongPathsPolicy::Fail,
+                    snapshot: false,
+                    keep_snapshots: None,
+                    mirror: vec![],
                     dry_run: false,
+                    estimate_size: false,
+                    show_diff: false,
+                    verify_after: false,
+                    checksum: ChecksumAlgorithm::Sha256,
+                    sign_key: None,
                     max_downloads: 4,
+                    max_per_track: None,
+                    max_metadata_requests: None,
+                    max_file_downloads: None,
+                    max_rpm: None,
+                    max_queued_solutions: 100,
+                    max_total_size: None,
+                    max_file_size: None,
+                    metrics_file: None,
+                    slow_request_threshold: 10,
+                    debug_http: None,
+                    slowest_solutions: 5,
+                    error_report: None,
+                    retry_failed: false,
+                    mentoring_requests: None,
+                    with_icons: false,
+                    anonymize: false,
+                    progress: None,
+                    progress_fd: None,
+                    remotely_deleted: RemotelyDeletedAction::Report,
+                    cache: ResponseCacheArgs::default(),
                 }
             }
 
@@ -450,55 +1524,312 @@ mod tests {
                 status: Option<SolutionStatus>,
                 iteration_status: Option<iteration::Status>,
                 iteration_is_published: Option<bool>,
+                published_only: bool,
                 should_match: bool,
             ) {
-                let args = get_args(status);
+                let mut args = get_args(status);
+                args.iterations_published_only = published_only;
                 let iteration = get_iteration(iteration_status, iteration_is_published);
                 assert_eq!(should_match, args.iteration_matches(&iteration));
             }
 
             #[test]
             fn test_iteration_status() {
-                perform_test(None, Some(iteration::Status::Untested), None, true);
-                perform_test(None, Some(iteration::Status::Testing), None, true);
-                perform_test(None, Some(iteration::Status::TestsFailed), None, true);
-                perform_test(None, Some(iteration::Status::Analyzing), None, true);
-                perform_test(None, Some(iteration::Status::EssentialAutomatedFeedback), None, true);
+                perform_test(None, Some(iteration::Status::Untested), None, false, true);
+                perform_test(None, Some(iteration::Status::Testing), None, false, true);
+                perform_test(None, Some(iteration::Status::TestsFailed), None, false, true);
+                perform_test(None, Some(iteration::Status::Analyzing), None, false, true);
+                perform_test(
+                    None,
+                    Some(iteration::Status::EssentialAutomatedFeedback),
+                    None,
+                    false,
+                    true,
+                );
                 perform_test(
                     None,
                     Some(iteration::Status::ActionableAutomatedFeedback),
                     None,
+                    false,
                     true,
                 );
                 perform_test(
                     None,
                     Some(iteration::Status::CelebratoryAutomatedFeedback),
                     None,
+                    false,
                     true,
                 );
                 perform_test(
                     None,
                     Some(iteration::Status::NonActionableAutomatedFeedback),
                     None,
+                    false,
                     true,
                 );
-                perform_test(None, Some(iteration::Status::NoAutomatedFeedback), None, true);
-                perform_test(None, Some(iteration::Status::Deleted), None, false);
+                perform_test(None, Some(iteration::Status::NoAutomatedFeedback), None, false, true);
+                perform_test(None, Some(iteration::Status::Deleted), None, false, false);
             }
 
             #[test]
             fn test_iteration_is_published() {
-                perform_test(Some(SolutionStatus::Any), None, Some(true), true);
-                perform_test(Some(SolutionStatus::Submitted), None, Some(true), true);
-                perform_test(Some(SolutionStatus::Completed), None, Some(true), true);
-                perform_test(Some(SolutionStatus::Published), None, Some(true), true);
-
-                perform_test(Some(SolutionStatus::Any), None, Some(false), true);
-                perform_test(Some(SolutionStatus::Submitted), None, Some(false), true);
-                perform_test(Some(SolutionStatus::Completed), None, Some(false), true);
-                perform_test(Some(SolutionStatus::Published), None, Some(false), false);
+                for status in [
+                    SolutionStatus::Any,
+                    SolutionStatus::Submitted,
+                    SolutionStatus::Completed,
+                    SolutionStatus::Published,
+                ] {
+                    perform_test(Some(status), None, Some(true), false, true);
+                    perform_test(Some(status), None, Some(false), false, true);
+                    perform_test(Some(status), None, Some(true), true, true);
+                    perform_test(Some(status), None, Some(false), true, false);
+                }
+            }
+
+            #[test]
+            fn test_iteration_tests_status() {
+                let mut args = get_args(None);
+                let mut iteration = get_iteration(None, None);
+
+                args.iterations_tests_status = TestsStatus::Passed;
+                assert!(args.iteration_matches(&iteration));
+                iteration.tests_status = TestRunStatus::Failed;
+                assert!(!args.iteration_matches(&iteration));
+
+                args.iterations_tests_status = TestsStatus::Failed;
+                assert!(args.iteration_matches(&iteration));
+                iteration.tests_status = TestRunStatus::Errored;
+                assert!(args.iteration_matches(&iteration));
+                iteration.tests_status = TestRunStatus::Queued;
+                assert!(!args.iteration_matches(&iteration));
+
+                args.iterations_tests_status = TestsStatus::Any;
+                assert!(args.iteration_matches(&iteration));
+            }
+        }
+    }
+
+    mod solution_id {
+        use assert_matches::assert_matches;
+
+        use super::*;
+
+        #[test]
+        fn test_from_str() {
+            assert_eq!(
+                SolutionId::Uuid("00c717b68e1b4213b316df82636f5e0f".into()),
+                "00c717b68e1b4213b316df82636f5e0f".parse().unwrap()
+            );
+            assert_matches!(
+                "https://exercism.org/tracks/rust/exercises/poker".parse::<SolutionId>(),
+                Ok(SolutionId::Url(solution_ref)) if solution_ref.to_string() == "rust/poker"
+            );
+            assert_matches!(
+                "https://exercism.org/tracks/rust/exercises/poker/iterations?idx=2".parse::<SolutionId>(),
+                Ok(SolutionId::Url(solution_ref)) if solution_ref.to_string() == "rust/poker"
+            );
+
+            assert!("".parse::<SolutionId>().is_err());
+            assert!("not a uuid!".parse::<SolutionId>().is_err());
+            assert!("https://exercism.org/tracks/rust"
+                .parse::<SolutionId>()
+                .is_err());
+            assert!("https://exercism.org/profiles/clechasseur"
+                .parse::<SolutionId>()
+                .is_err());
+        }
+    }
+
+    mod account {
+        use clap::Parser;
+
+        use super::*;
+
+        #[derive(Debug, Parser)]
+        struct TestCli {
+            #[command(flatten)]
+            args: BackupArgs,
+        }
+
+        #[test]
+        fn test_from_str() {
+            assert_eq!(
+                Account { name: "alice".into(), token: AccountToken::Token("some_token".into()) },
+                "alice=some_token".parse().unwrap()
+            );
+            assert_eq!("bob", " bob = other_token ".parse::<Account>().unwrap().name);
+            assert_eq!(
+                Account {
+                    name: "carol".into(),
+                    token: AccountToken::Command("pass show exercism/carol".into())
+                },
+                "carol=@ pass show exercism/carol".parse().unwrap()
+            );
+
+            assert!("alice".parse::<Account>().is_err());
+            assert!("=some_token".parse::<Account>().is_err());
+            assert!("alice=".parse::<Account>().is_err());
+            assert!("alice=@".parse::<Account>().is_err());
+            assert!("../alice=some_token".parse::<Account>().is_err());
+        }
+
+        #[test]
+        fn test_debug_hides_token() {
+            let account: Account = "alice=some_token".parse().unwrap();
+            assert!(!format!("{account:?}").contains("some_token"));
+        }
+
+        #[test]
+        fn test_parse_error_hides_token() {
+            for account in ["al ice=SUPERSECRET", "SUPERSECRET", "../alice=SUPERSECRET"] {
+                let err =
+                    TestCli::try_parse_from(["auxiliaire", "backup-dir", "--account", account])
+                        .unwrap_err()
+                        .to_string();
+                assert!(err.contains("invalid value for '--account <NAME=TOKEN>'"), "{err}");
+                assert!(!err.contains("SUPERSECRET"), "{err}");
+            }
+        }
+    }
+
+    mod http_header {
+        use super::*;
+
+        #[test]
+        fn test_from_str() {
+            let header: HttpHeader = "X-Foo: bar".parse().unwrap();
+            assert_eq!("x-foo", header.name.as_str());
+            assert_eq!("bar", header.value.to_str().unwrap());
+            assert_eq!(
+                "baz: qux",
+                "X-Foo:baz: qux"
+                    .parse::<HttpHeader>()
+                    .unwrap()
+                    .value
+                    .to_str()
+                    .unwrap()
+            );
+
+            assert!("X-Foo".parse::<HttpHeader>().is_err());
+            assert!(": bar".parse::<HttpHeader>().is_err());
+            assert!("X Foo: bar".parse::<HttpHeader>().is_err());
+            assert!("X-Foo: bar\nbaz".parse::<HttpHeader>().is_err());
+        }
+
+        #[test]
+        fn test_debug_hides_value() {
+            let header: HttpHeader = "X-Api-Key: some_secret".parse().unwrap();
+            assert!(!format!("{header:?}").contains("some_secret"));
+        }
+    }
+
+    mod iteration_range {
+        use super::*;
+
+        mod from_str {
+            use super::*;
+
+            #[test]
+            fn test_valid() {
+                let range = |start, end| IterationRange { start, end };
+
+                assert_eq!(range(Some(3), Some(7)), "3..=7".parse().unwrap());
+                assert_eq!(range(Some(3), Some(7)), "3..8".parse().unwrap());
+                assert_eq!(range(Some(3), None), "3..".parse().unwrap());
+                assert_eq!(range(None, Some(7)), "..=7".parse().unwrap());
+                assert_eq!(range(None, None), "..".parse().unwrap());
+                assert_eq!(range(Some(5), Some(5)), "5".parse().unwrap());
+            }
+
+            #[test]
+            fn test_invalid() {
+                assert!("".parse::<IterationRange>().is_err());
+                assert!("a..=7".parse::<IterationRange>().is_err());
+                assert!("3..=".parse::<IterationRange>().is_err());
+                assert!("7..=3".parse::<IterationRange>().is_err());
             }
         }
+
+        #[test]
+        fn test_contains() {
+            let range: IterationRange = "3..=7".parse().unwrap();
+            assert!(!range.contains(2));
+            assert!(range.contains(3));
+            assert!(range.contains(7));
+            assert!(!range.contains(8));
+
+            let range: IterationRange = "..".parse().unwrap();
+            assert!(range.contains(1));
+        }
+    }
+
+    mod name_mapping {
+        use super::*;
+
+        fn get_mapping() -> NameMapping {
+            "
+                # Merge shell tracks
+                bash -> shell
+                jq -> jq-lang
+
+                rust/poker -> rust/texas-hold-em
+                jq/hello-world -> misc/hello-jq
+            "
+            .parse()
+            .unwrap()
+        }
+
+        #[test]
+        fn test_local_names() {
+            let mapping = get_mapping();
+            let local = |track, exercise| mapping.local_names(track, exercise).to_string();
+
+            assert_eq!("shell/two-fer", local("bash", "two-fer"));
+            assert_eq!("shell/two-fer", local("shell", "two-fer"));
+            assert_eq!("jq-lang/clock", local("jq", "clock"));
+            assert_eq!("rust/texas-hold-em", local("rust", "poker"));
+            assert_eq!("rust/clock", local("rust", "clock"));
+            assert_eq!("misc/hello-jq", local("jq", "hello-world"));
+        }
+
+        #[test]
+        fn test_remote_names() {
+            let mapping = get_mapping();
+            let remote = |track, exercise| {
+                let mut remote_names = mapping
+                    .remote_names(track, exercise)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                remote_names.sort();
+                remote_names
+            };
+
+            assert_eq!(vec!["bash/two-fer", "shell/two-fer"], remote("shell", "two-fer"));
+            assert_eq!(vec!["jq-lang/clock", "jq/clock"], remote("jq-lang", "clock"));
+            assert!(remote("jq", "clock").is_empty());
+            assert!(remote("bash", "two-fer").is_empty());
+            assert_eq!(vec!["jq-lang/hello-world"], remote("jq-lang", "hello-world"));
+            assert_eq!(vec!["jq/hello-world", "misc/hello-jq"], remote("misc", "hello-jq"));
+            assert_eq!(vec!["rust/poker", "rust/texas-hold-em"], remote("rust", "texas-hold-em"));
+            assert!(remote("rust", "poker").is_empty());
+            assert_eq!(vec!["rust/clock"], remote("rust", "clock"));
+        }
+
+        #[test]
+        fn test_invalid() {
+            assert!("bash".parse::<NameMapping>().is_err());
+            assert!("bash ->".parse::<NameMapping>().is_err());
+            assert!("bash -> .hidden".parse::<NameMapping>().is_err());
+            assert!("rust/poker -> texas-hold-em"
+                .parse::<NameMapping>()
+                .is_err());
+
+            let err = "# comment\nrust/poker/extra -> rust/poker"
+                .parse::<NameMapping>()
+                .unwrap_err();
+            assert!(err.to_string().contains("line 2"), "{err}");
+        }
     }
 
     mod solution_status {
@@ -0,0 +1,316 @@
+//! Builder used to create a [`BackupCommand`] with custom API clients or credentials.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use anyhow::Context;
+use mini_exercism::http::header::HeaderMap;
+use mini_exercism::{api, http};
+
+use crate::cache::cache_scope;
+use crate::command::backup::args::BackupArgs;
+use crate::command::backup::progress::ProgressReporter;
+use crate::command::backup::BackupCommand;
+use crate::credentials::{credentials_provider, CredentialsProvider};
+use crate::limiter::{KeyedLimiter, RateLimiter};
+use crate::{Result, USER_AGENT};
+
+/// Builder for a [`BackupCommand`].
+///
+/// By default, the command creates its own Exercism API clients, using credentials resolved from
+/// the [`token`](BackupArgs::token) and [`token_command`](BackupArgs::token_command) arguments.
+/// The builder makes it possible to supply a custom [`CredentialsProvider`] instead, or even
+/// to inject already-configured API clients (for example to use custom middlewares or
+/// a recorded transport in tests).
+///
+/// ```no_run
+/// # use auxiliaire::command::backup::args::BackupArgs;
+/// use auxiliaire::command::backup::BackupCommand;
+/// use mini_exercism::api;
+///
+/// # async fn perform_backup(args: BackupArgs, v1_client: api::v1::Client, v2_client: api::v2::Client) -> auxiliaire::Result<()> {
+/// let backup_command = BackupCommand::builder(args)
+///     .v1_client(v1_client)
+///     .v2_client(v2_client)
+///     .build()?;
+/// BackupCommand::execute(backup_command).await
+/// # }
+/// ```
+pub struct BackupCommandBuilder {
+    args: BackupArgs,
+    credentials_provider: Option<Box<dyn CredentialsProvider>>,
+    api_base_url: Option<String>,
+    v1_client: Option<api::v1::Client>,
+    v2_client: Option<api::v2::Client>,
+    limiters: Option<(KeyedLimiter, RateLimiter)>,
+    progress: Option<ProgressReporter>,
+}
+
+impl BackupCommandBuilder {
+    pub(super) fn new(args: BackupArgs) -> Self {
+        Self {
+            args,
+            credentials_provider: None,
+            api_base_url: None,
+            v1_client: None,
+            v2_client: None,
+            limiters: None,
+            progress: None,
+        }
+    }
+
+    /// Sets the [`CredentialsProvider`] used to create API clients.
+    ///
+    /// Not used if both API clients are [injected](Self::v1_client).
+    pub fn credentials_provider<P>(mut self, credentials_provider: P) -> Self
+    where
+        P: CredentialsProvider + 'static,
+    {
+        self.credentials_provider = Some(Box::new(credentials_provider));
+        self
+    }
+
+    /// Sets the base URL of the Exercism API used to create API clients.
+    ///
+    /// Not used if both API clients are [injected](Self::v1_client). The
+    /// [`v1_api_base_url`](BackupArgs::v1_api_base_url) and
+    /// [`v2_api_base_url`](BackupArgs::v2_api_base_url) arguments take precedence, if specified.
+    pub fn api_base_url<U: Into<String>>(mut self, api_base_url: U) -> Self {
+        self.api_base_url = Some(api_base_url.into());
+        self
+    }
+
+    /// Sets the client used to access the Exercism API v1 (used to download solution files).
+    pub fn v1_client(mut self, v1_client: api::v1::Client) -> Self {
+        self.v1_client = Some(v1_client);
+        self
+    }
+
+    /// Sets the client used to access the Exercism API v2.
+    pub fn v2_client(mut self, v2_client: api::v2::Client) -> Self {
+        self.v2_client = Some(v2_client);
+        self
+    }
+
+    /// Sets the limiters used to limit concurrent requests and the rate of requests, so that
+    /// they can be shared between multiple commands.
+    ///
+    /// If unset, limiters are created using the limits in the command's [`BackupArgs`].
+    pub(crate) fn limiters(mut self, limiter: KeyedLimiter, rate_limiter: RateLimiter) -> Self {
+        self.limiters = Some((limiter, rate_limiter));
+        self
+    }
+
+    /// Sets the reporter used to report progress, so that it can be shared between multiple
+    /// commands.
+    ///
+    /// If unset, a reporter is created using the command's [`BackupArgs`].
+    pub(crate) fn progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Builds the [`BackupCommand`].
+    ///
+    /// See [`BackupCommand`] for details on why it is returned wrapped in an [`Arc`].
+    pub fn build(self) -> Result<Arc<BackupCommand>> {
+        let (v1_client, v2_client, cache_scope, api_token) = match (self.v1_client, self.v2_client)
+        {
+            (Some(v1_client), Some(v2_client)) => {
+                // We do not know which token the injected clients use, so only rely on the one
+                // passed as argument (if any) to scope cached responses and redact logs.
+                let cache_scope = response_cache_scope(
+                    self.args.token.as_deref().unwrap_or_default(),
+                    &self.args,
+                    self.api_base_url.as_deref(),
+                );
+                (v1_client, v2_client, cache_scope, None)
+            },
+            (v1_client, v2_client) => {
+                let credentials_provider = self.credentials_provider.unwrap_or_else(|| {
+                    credentials_provider(
+                        self.args.token.as_deref(),
+                        self.args.token_command.as_deref(),
+                    )
+                });
+
+                let (built_v1_client, built_v2_client, cache_scope, api_token) = build_clients(
+                    credentials_provider.as_ref(),
+                    &self.args,
+                    self.api_base_url.as_deref(),
+                )?;
+                (
+                    v1_client.unwrap_or(built_v1_client),
+                    v2_client.unwrap_or(built_v2_client),
+                    cache_scope,
+                    Some(api_token),
+                )
+            },
+        };
+
+        BackupCommand::with_clients(
+            self.args,
+            v1_client,
+            v2_client,
+            &cache_scope,
+            api_token.as_deref(),
+            self.limiters,
+            self.progress,
+        )
+    }
+}
+
+impl Debug for BackupCommandBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackupCommandBuilder")
+            .field("args", &self.args)
+            .field("api_base_url", &self.api_base_url)
+            .field("v1_client", &self.v1_client)
+            .field("v2_client", &self.v2_client)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates API clients sharing the same HTTP client, using credentials from the given provider.
+///
+/// The HTTP client identifies itself as `auxiliaire/<version>` and sends the additional
+/// [`headers`](BackupArgs::headers) with every request. Each client uses the base URL specified
+/// in `args` for its API version, if any, falling back to `api_base_url`.
+///
+/// Also returns the scope to use for cached responses (see [`response_cache_scope`]) and the API
+/// token obtained from the provider, so that it can be redacted from logs.
+pub(super) fn build_clients(
+    credentials_provider: &dyn CredentialsProvider,
+    args: &BackupArgs,
+    api_base_url: Option<&str>,
+) -> Result<(api::v1::Client, api::v2::Client, String, String)> {
+    let default_headers = args
+        .headers
+        .iter()
+        .map(|header| (header.name.clone(), header.value.clone()))
+        .collect::<HeaderMap>();
+    let http_client = http::Client::builder()
+        .user_agent(USER_AGENT)
+        .default_headers(default_headers)
+        .cookie_store(true)
+        .build()
+        .with_context(|| "failed to create HTTP client")?;
+    let credentials = credentials_provider.credentials()?;
+    let cache_scope = response_cache_scope(credentials.api_token(), args, api_base_url);
+
+    let v1_client = build_client!(
+        api::v1::Client,
+        http_client,
+        credentials,
+        args.v1_api_base_url.as_deref().or(api_base_url)
+    );
+    let v2_client = build_client!(
+        api::v2::Client,
+        http_client,
+        credentials,
+        args.v2_api_base_url.as_deref().or(api_base_url)
+    );
+
+    Ok((v1_client, v2_client, cache_scope, credentials.api_token().to_string()))
+}
+
+/// Returns the scope of responses cached by a [`BackupCommand`] using the given API token,
+/// so that responses fetched for different accounts or from different API endpoints are
+/// never mixed up (see [`cache_scope`]).
+fn response_cache_scope(api_token: &str, args: &BackupArgs, api_base_url: Option<&str>) -> String {
+    let v1_api_base_url = args
+        .v1_api_base_url
+        .as_deref()
+        .or(api_base_url)
+        .unwrap_or(api::v1::DEFAULT_V1_API_BASE_URL);
+    let v2_api_base_url = args
+        .v2_api_base_url
+        .as_deref()
+        .or(api_base_url)
+        .unwrap_or(api::v2::DEFAULT_V2_API_BASE_URL);
+
+    cache_scope(api_token, &[v1_api_base_url, v2_api_base_url])
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use mini_exercism::core::Credentials;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: BackupArgs,
+    }
+
+    fn get_args() -> BackupArgs {
+        TestCli::parse_from(["auxiliaire", "backup-dir", "--token", "some_token"]).args
+    }
+
+    mod backup_command_builder {
+        use super::*;
+
+        #[test]
+        fn test_injected_clients() {
+            let v1_client = api::v1::Client::builder()
+                .api_base_url("http://localhost:1234/v1")
+                .build()
+                .unwrap();
+            let v2_client = api::v2::Client::builder()
+                .api_base_url("http://localhost:1234/v2")
+                .build()
+                .unwrap();
+
+            let result = BackupCommand::builder(get_args())
+                .credentials_provider(|| -> Result<Credentials> {
+                    panic!("credentials should not be needed when clients are injected")
+                })
+                .v1_client(v1_client)
+                .v2_client(v2_client)
+                .build();
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        fn test_response_cache_scope() {
+            let args = get_args();
+            let scope = response_cache_scope("some_token", &args, None);
+
+            assert_eq!(scope, response_cache_scope("some_token", &args, None));
+            assert_ne!(scope, response_cache_scope("other_token", &args, None));
+            assert_ne!(
+                scope,
+                response_cache_scope("some_token", &args, Some("http://localhost:1234"))
+            );
+        }
+
+        #[test]
+        fn test_credentials_provider() {
+            let result = BackupCommand::builder(get_args())
+                .credentials_provider(|| Err(anyhow::anyhow!("no credentials")))
+                .build();
+            assert!(result.is_err_and(|err| err.to_string() == "no credentials"));
+        }
+
+        #[test]
+        fn test_redacts_resolved_token() {
+            let args = TestCli::parse_from([
+                "auxiliaire",
+                "backup-dir",
+                "--token-command",
+                "echo command_token",
+                "--debug-http",
+                "bodies",
+            ])
+            .args;
+
+            let command = BackupCommand::builder(args)
+                .api_base_url("http://localhost:1234")
+                .build()
+                .unwrap();
+            assert_eq!("token=[REDACTED]", command.redactor.redact("token=command_token"));
+        }
+    }
+}
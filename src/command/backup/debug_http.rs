@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter};
+use std::iter;
+
+use mini_exercism::http;
+
+use crate::command::backup::args::{AccountToken, BackupArgs};
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Removes secrets from HTTP debug logs (see [`BackupArgs::debug_http`]).
+///
+/// Secrets are the API token used by the command (wherever it came from), API tokens and
+/// additional header values passed via arguments. Request headers (including the `Authorization`
+/// header) are never logged to begin with.
+#[derive(Default)]
+pub struct Redactor {
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    /// Creates a redactor for the given API token and the secrets found in the given [`BackupArgs`].
+    ///
+    /// The API token should be the one resolved from the command's [`CredentialsProvider`], since
+    /// it can come from elsewhere than the arguments (like the Exercism CLI configuration).
+    ///
+    /// [`CredentialsProvider`]: crate::credentials::CredentialsProvider
+    pub fn new(args: &BackupArgs, api_token: Option<&str>) -> Self {
+        let secrets = api_token
+            .map(Into::into)
+            .into_iter()
+            .chain(args.token.iter().cloned())
+            .chain(
+                args.account
+                    .iter()
+                    .filter_map(|account| match &account.token {
+                        AccountToken::Token(token) => Some(token.clone()),
+                        AccountToken::Command(_) => None,
+                    }),
+            )
+            .chain(
+                args.headers
+                    .iter()
+                    .filter_map(|header| header.value.to_str().ok().map(Into::into)),
+            )
+            .filter(|secret| !secret.is_empty())
+            .collect();
+
+        Self { secrets }
+    }
+
+    /// Returns the given text with all secrets replaced.
+    pub fn redact(&self, text: &str) -> String {
+        self.secrets
+            .iter()
+            .fold(text.to_string(), |text, secret| text.replace(secret.as_str(), REDACTED))
+    }
+}
+
+impl Debug for Redactor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Obviously, the secrets themselves should not end up in logs.
+        f.debug_struct("Redactor").finish_non_exhaustive()
+    }
+}
+
+/// Describes the outcome of a failed HTTP request, using the response's status code if available.
+pub fn error_outcome<E: Error + 'static>(err: &E) -> String {
+    let status = iter::successors(Some(err as &(dyn Error + 'static)), |&err| err.source())
+        .find_map(|err| err.downcast_ref::<http::Error>()?.status());
+
+    match status {
+        Some(status) => status.to_string(),
+        None => format!("failed ({err})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: BackupArgs,
+    }
+
+    #[test]
+    fn test_redact() {
+        let args = TestCli::parse_from([
+            "auxiliaire",
+            "backup-dir",
+            "--token",
+            "some_token",
+            "--header",
+            "X-Api-Key: some_secret",
+        ])
+        .args;
+        let redactor = Redactor::new(&args, Some("cli_token"));
+
+        assert_eq!(
+            "token=[REDACTED], key=[REDACTED], cli=[REDACTED], track=rust",
+            redactor.redact("token=some_token, key=some_secret, cli=cli_token, track=rust")
+        );
+        assert!(!format!("{redactor:?}").contains("some_token"));
+        assert_eq!("some_token", Redactor::default().redact("some_token"));
+    }
+
+    #[test]
+    fn test_error_outcome() {
+        let err = std::io::Error::other("connection reset");
+        assert_eq!("failed (connection reset)", error_outcome(&err));
+    }
+}
@@ -1,12 +1,39 @@
+use std::fmt::{Display, Formatter};
+
+use mini_exercism::api::v2::solution::Solution;
+
 macro_rules! build_client {
-    ($client_ty:ty, $http_client:ident, $credentials:ident, $api_base_url:ident) => {{
+    ($client_ty:ty, $http_client:ident, $credentials:ident, $api_base_url:expr) => {{
         let mut builder = <$client_ty>::builder();
         builder
             .http_client($http_client.clone())
             .credentials($credentials.clone());
         if let Some(api_base_url) = $api_base_url {
-            builder.api_base_url(api_base_url);
+            builder.api_base_url(api_base_url.trim_end_matches('/'));
         }
         builder.build()?
     }};
 }
+
+/// Describes a solution in error messages, including its URL so that it can be opened directly.
+pub fn solution_desc(solution: &Solution) -> SolutionDesc<'_> {
+    SolutionDesc(solution)
+}
+
+#[derive(Debug)]
+pub struct SolutionDesc<'a>(&'a Solution);
+
+impl Display for SolutionDesc<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let solution = self.0;
+        write!(f, "{}/{}", solution.track.name, solution.exercise.name)?;
+
+        match [&solution.private_url, &solution.public_url]
+            .into_iter()
+            .find(|url| !url.is_empty())
+        {
+            Some(url) => write!(f, " ({url})"),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use mini_exercism::api::v2::exercises;
+use tokio::sync::OnceCell;
+use tracing::trace;
+
+use crate::cache::{Expiry, ResponseCache};
+use crate::command::backup::args::ExerciseDifficulty;
+use crate::Result;
+
+/// Difficulties of a track's exercises, by exercise name.
+type TrackDifficulties = HashMap<String, ExerciseDifficulty>;
+
+/// Difficulties of exercises, fetched from Exercism.org one track at a time as needed.
+///
+/// The list of solutions returned by the Exercism API does not include the difficulty of
+/// their exercises, so we need to fetch the track's exercises to filter solutions by difficulty.
+#[derive(Debug, Default)]
+pub struct ExerciseDifficulties {
+    tracks: Mutex<HashMap<String, Arc<OnceCell<TrackDifficulties>>>>,
+}
+
+impl ExerciseDifficulties {
+    /// Determines if the given exercise has one of the given difficulties.
+    ///
+    /// If `difficulties` is empty, all exercises match and nothing is fetched. Otherwise, the
+    /// track's exercises are fetched by calling `fetch_exercises` the first time the track is
+    /// seen (unless they are found in the `response_cache`). Concurrent calls for the same track
+    /// wait for that fetch instead of performing their own.
+    pub async fn matches<F, Fut>(
+        &self,
+        response_cache: &ResponseCache,
+        difficulties: &[ExerciseDifficulty],
+        track: &str,
+        exercise: &str,
+        fetch_exercises: F,
+    ) -> Result<bool>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<exercises::Response>>,
+    {
+        if difficulties.is_empty() {
+            return Ok(true);
+        }
+
+        let track_cell = Arc::clone(self.tracks.lock().unwrap().entry(track.into()).or_default());
+        let track_difficulties = track_cell
+            .get_or_try_init(|| async {
+                let track_difficulties: TrackDifficulties = response_cache
+                    .get_or_fetch(
+                        &format!("v2/tracks/{track}/difficulties"),
+                        Expiry::Ttl,
+                        || async {
+                            Ok(fetch_exercises()
+                                .await?
+                                .exercises
+                                .into_iter()
+                                .filter_map(|exercise| {
+                                    ExerciseDifficulty::try_from(exercise.difficulty)
+                                        .ok()
+                                        .map(|difficulty| (exercise.name, difficulty))
+                                })
+                                .collect())
+                        },
+                    )
+                    .await?;
+                trace!(track, ?track_difficulties);
+                Ok::<_, crate::Error>(track_difficulties)
+            })
+            .await?;
+
+        Ok(track_difficulties
+            .get(exercise)
+            .is_some_and(|difficulty| difficulties.contains(difficulty)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use mini_exercism::api;
+
+    use super::*;
+    use crate::command::args::ResponseCacheArgs;
+
+    fn exercise_json(exercise: &str, difficulty: &str) -> serde_json::Value {
+        json!({
+            "slug": exercise,
+            "type": "practice",
+            "title": exercise,
+            "icon_url": "",
+            "difficulty": difficulty,
+            "blurb": "",
+            "is_external": false,
+            "is_unlocked": true,
+            "is_recommended": false,
+            "links": { "self": "" },
+        })
+    }
+
+    #[test(tokio::test)]
+    async fn test_matches() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method(Method::GET))
+            .and(path("/tracks/rust/exercises"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "exercises": [
+                    exercise_json("clock", "easy"),
+                    exercise_json("poker", "hard"),
+                ],
+                "solutions": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let v2_client = api::v2::Client::builder()
+            .api_base_url(&mock_server.uri())
+            .build()
+            .unwrap();
+        let response_cache =
+            ResponseCache::new(&ResponseCacheArgs { cache_dir: None, cache_ttl: 3600 }, "");
+        let difficulties = ExerciseDifficulties::default();
+
+        let (v2_client, response_cache, difficulties) =
+            (&v2_client, &response_cache, &difficulties);
+        let matches = move |filter: &'static [ExerciseDifficulty], exercise: &'static str| {
+            difficulties.matches(response_cache, filter, "rust", exercise, || async {
+                Ok(v2_client.get_exercises("rust", None).await?)
+            })
+        };
+        assert!(matches(&[], "robot").await.unwrap());
+        assert!(matches(&[ExerciseDifficulty::Hard], "poker").await.unwrap());
+        assert!(!matches(&[ExerciseDifficulty::Hard], "clock").await.unwrap());
+        assert!(matches(&[ExerciseDifficulty::Easy, ExerciseDifficulty::Medium], "clock")
+            .await
+            .unwrap());
+        assert!(!matches(&[ExerciseDifficulty::Easy], "robot").await.unwrap());
+    }
+
+    #[test(tokio::test)]
+    async fn test_matches_fetches_once_per_track() {
+        let response_cache =
+            ResponseCache::new(&ResponseCacheArgs { cache_dir: None, cache_ttl: 3600 }, "");
+        let difficulties = ExerciseDifficulties::default();
+        let fetches = AtomicUsize::new(0);
+
+        let matches = |exercise: &'static str| {
+            difficulties.matches(
+                &response_cache,
+                &[ExerciseDifficulty::Hard],
+                "rust",
+                exercise,
+                || {
+                    let fetches = &fetches;
+                    async move {
+                        fetches.fetch_add(1, Ordering::Relaxed);
+                        tokio::task::yield_now().await;
+                        Ok(serde_json::from_value(json!({
+                            "exercises": [exercise_json("poker", "hard")],
+                            "solutions": [],
+                        }))?)
+                    }
+                },
+            )
+        };
+        let (poker, clock) = tokio::join!(matches("poker"), matches("clock"));
+        assert!(poker.unwrap());
+        assert!(!clock.unwrap());
+        assert_eq!(1, fetches.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::debug;
+
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::Result;
+
+pub const HISTORY_FILE_NAME: &str = ".auxiliaire/history.json";
+pub const HISTORY_TEMP_FILE_NAME: &str = ".auxiliaire/history.json.tmp";
+
+/// Global index of the last time each solution of a backup directory was backed up.
+///
+/// Unlike backup state files, which are only written when a solution changes, this index is
+/// updated for every solution checked during a backup, so it can be used to find out whether
+/// backups are still running.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupHistory {
+    /// Time of the last backup of each solution, in seconds since the Unix epoch,
+    /// keyed by `<track>/<exercise>`.
+    pub solutions: BTreeMap<String, u64>,
+}
+
+impl BackupHistory {
+    /// Loads the history of the given backup directory; if it does not exist, an empty history is returned.
+    pub async fn load(backup_path: &Path) -> Result<Self> {
+        let history_file_path = backup_path.join(HISTORY_FILE_NAME);
+        let history = match fs::read_to_string(&history_file_path).await {
+            Ok(history) => history,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("failed to read backup history {}", history_file_path.display())
+                })
+            },
+        };
+
+        serde_json::from_str(&history).with_context(|| {
+            format!("failed to parse backup history {}", history_file_path.display())
+        })
+    }
+
+    /// Returns the time of the last backup of the solution to the given exercise, in seconds
+    /// since the Unix epoch.
+    pub fn last_backed_up(&self, track: &str, exercise: &str) -> Option<u64> {
+        self.solutions.get(&history_key(track, exercise)).copied()
+    }
+
+    /// Records that the given solutions, identified by track and exercise, were backed up at `time`.
+    pub fn record<'a, I>(&mut self, solutions: I, time: SystemTime)
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let time = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        for (track, exercise) in solutions {
+            self.solutions.insert(history_key(track, exercise), time);
+        }
+    }
+
+    /// Records that the given solutions were just backed up in the history of the given backup directory.
+    pub async fn update<'a, I>(backup_path: &Path, solutions: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut history = Self::load(backup_path).await?;
+        history.record(solutions, SystemTime::now());
+        history.save(backup_path).await?;
+
+        debug!("Backup history updated ({} solution(s))", history.solutions.len());
+        Ok(())
+    }
+
+    pub async fn save(&self, backup_path: &Path) -> Result<()> {
+        let history = serde_json::to_string_pretty(self)
+            .with_context(|| "failed to persist backup history to JSON")?;
+
+        let history_file_path = backup_path.join(HISTORY_FILE_NAME);
+        let temp_file_path = backup_path.join(HISTORY_TEMP_FILE_NAME);
+        fs::create_dir_all(backup_path.join(AUXILIAIRE_STATE_DIR_NAME))
+            .await
+            .with_context(|| {
+                format!("failed to create state directory in {}", backup_path.display())
+            })?;
+        fs::write(&temp_file_path, history).await.with_context(|| {
+            format!("failed to save backup history to {}", temp_file_path.display())
+        })?;
+        fs::rename(&temp_file_path, &history_file_path)
+            .await
+            .with_context(|| {
+                format!("failed to save backup history to {}", history_file_path.display())
+            })
+    }
+}
+
+fn history_key(track: &str, exercise: &str) -> String {
+    format!("{track}/{exercise}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    mod backup_history {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_update() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let backup_path = temp_dir.path();
+            assert_eq!(BackupHistory::default(), BackupHistory::load(backup_path).await.unwrap());
+
+            BackupHistory::update(backup_path, [("rust", "poker"), ("rust", "clock")])
+                .await
+                .unwrap();
+            let mut history = BackupHistory::load(backup_path).await.unwrap();
+            assert!(history.last_backed_up("rust", "poker").is_some());
+            assert!(history.last_backed_up("rust", "clock").is_some());
+            assert!(history.last_backed_up("clojure", "poker").is_none());
+
+            history.record([("rust", "poker")], UNIX_EPOCH + Duration::from_secs(42));
+            assert_eq!(Some(42), history.last_backed_up("rust", "poker"));
+            assert_ne!(Some(42), history.last_backed_up("rust", "clock"));
+        }
+    }
+}
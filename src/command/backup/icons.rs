@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use mini_exercism::api::v2::solution::Solution;
+use mini_exercism::http;
+use tokio::fs;
+
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::Result;
+
+/// Name of the directory, in the backup directory's `.auxiliaire` directory, where icons are stored.
+pub const ICONS_DIR_NAME: &str = "icons";
+
+/// Icon of a track or exercise, referenced in a solution's metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icon {
+    /// Key used to limit concurrent downloads (the track's name).
+    pub track: String,
+
+    pub url: String,
+
+    /// Path of the icon, relative to the backup directory.
+    pub path: PathBuf,
+}
+
+impl Icon {
+    /// Returns the icons of the given solution's track and exercise.
+    ///
+    /// Track icons are stored in `.auxiliaire/icons/tracks/<track>.<ext>`, while exercise
+    /// icons are stored in `.auxiliaire/icons/exercises/<track>/<exercise>.<ext>`.
+    pub fn for_solution(solution: &Solution) -> Vec<Self> {
+        let icons_path: PathBuf = [AUXILIAIRE_STATE_DIR_NAME, ICONS_DIR_NAME].iter().collect();
+        let track = &solution.track;
+        let exercise = &solution.exercise;
+
+        [
+            (&track.icon_url, icons_path.join("tracks"), &track.name),
+            (&exercise.icon_url, icons_path.join("exercises").join(&track.name), &exercise.name),
+        ]
+        .into_iter()
+        .filter(|(url, _, _)| !url.is_empty())
+        .map(|(url, dir_path, name)| Self {
+            track: track.name.clone(),
+            url: url.clone(),
+            path: dir_path.join(format!("{name}.{}", icon_extension(url))),
+        })
+        .collect()
+    }
+
+    /// Downloads the icon in the given backup directory, returning its size in bytes.
+    pub async fn download(&self, http_client: &http::Client, output_path: &Path) -> Result<usize> {
+        let destination_path = output_path.join(&self.path);
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("failed to create directory {}", parent.display()))?;
+        }
+
+        let content = http_client
+            .get(&self.url)
+            .send()
+            .await
+            .and_then(http::Response::error_for_status)
+            .with_context(|| format!("failed to download icon {}", self.url))?
+            .bytes()
+            .await
+            .with_context(|| format!("failed to download icon {}", self.url))?;
+        fs::write(&destination_path, &content)
+            .await
+            .with_context(|| format!("failed to save icon to {}", destination_path.display()))?;
+
+        Ok(content.len())
+    }
+}
+
+fn icon_extension(url: &str) -> &str {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .and_then(|file_name| file_name.rsplit_once('.'))
+        .map(|(_, extension)| extension)
+        .filter(|extension| !extension.is_empty())
+        .unwrap_or("svg")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::test_fixtures;
+
+    fn solution(track_icon_url: &str, exercise_icon_url: &str) -> Solution {
+        test_fixtures::solution(
+            "rust",
+            "poker",
+            json!({
+                "exercise": { "slug": "poker", "title": "Poker", "icon_url": exercise_icon_url },
+                "track": { "slug": "rust", "title": "Rust", "icon_url": track_icon_url },
+            }),
+        )
+    }
+
+    mod icon {
+        use super::*;
+
+        #[test]
+        fn test_for_solution() {
+            let icons = Icon::for_solution(&solution(
+                "https://assets.exercism.org/tracks/rust.svg",
+                "https://assets.exercism.org/exercises/poker.png?v=2",
+            ));
+
+            assert_eq!(
+                vec![
+                    Path::new(".auxiliaire/icons/tracks/rust.svg"),
+                    Path::new(".auxiliaire/icons/exercises/rust/poker.png"),
+                ],
+                icons
+                    .iter()
+                    .map(|icon| icon.path.as_path())
+                    .collect::<Vec<_>>()
+            );
+            assert!(icons.iter().all(|icon| icon.track == "rust"));
+
+            let icons = Icon::for_solution(&solution("", "https://assets.exercism.org/poker"));
+            assert_eq!(1, icons.len());
+            assert_eq!(Path::new(".auxiliaire/icons/exercises/rust/poker.svg"), icons[0].path);
+        }
+
+        #[tokio::test]
+        async fn test_download() {
+            let mock_server = MockServer::start().await;
+            Mock::given(method(Method::GET))
+                .and(path("/tracks/rust.svg"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<svg/>"))
+                .mount(&mock_server)
+                .await;
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            let icons = Icon::for_solution(&solution(
+                &format!("{}/tracks/rust.svg", mock_server.uri()),
+                &format!("{}/exercises/missing.svg", mock_server.uri()),
+            ));
+            let http_client = http::Client::new();
+
+            assert_eq!(
+                6,
+                icons[0]
+                    .download(&http_client, temp_dir.path())
+                    .await
+                    .unwrap()
+            );
+            assert_eq!(
+                "<svg/>",
+                fs::read_to_string(temp_dir.path().join(&icons[0].path))
+                    .await
+                    .unwrap()
+            );
+            assert!(icons[1]
+                .download(&http_client, temp_dir.path())
+                .await
+                .is_err());
+        }
+    }
+}
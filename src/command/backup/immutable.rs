@@ -0,0 +1,136 @@
+use std::fs::Permissions;
+use std::path::Path;
+
+use anyhow::Context;
+use tokio::fs;
+
+use crate::Result;
+
+/// Determines if the given solution backup directory has been marked immutable
+/// (see [`set_immutable`]).
+pub async fn is_immutable(solution_path: &Path) -> bool {
+    fs::metadata(solution_path)
+        .await
+        .is_ok_and(|metadata| metadata.permissions().readonly())
+}
+
+/// Marks the given solution backup directory, and everything it contains, as immutable
+/// (read-only) or writable again.
+pub async fn set_immutable(solution_path: &Path, immutable: bool) -> Result<()> {
+    let mut to_visit = vec![solution_path.to_path_buf()];
+
+    while let Some(dir_path) = to_visit.pop() {
+        let mut entries = fs::read_dir(&dir_path).await.with_context(|| {
+            format!("failed to list content of directory {}", dir_path.display())
+        })?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                to_visit.push(entry.path());
+            } else if file_type.is_file() {
+                set_permissions(&entry.path(), immutable).await?;
+            }
+        }
+
+        set_permissions(&dir_path, immutable).await?;
+    }
+
+    Ok(())
+}
+
+/// Gives write access back to the given directory and all of its subdirectories, so that
+/// their content can be removed.
+///
+/// Unlike [`set_immutable`], the permissions of files are left as-is, since they could be
+/// hard links shared with another directory (like another snapshot).
+pub async fn set_dirs_writable(dir_path: &Path) -> Result<()> {
+    let mut to_visit = vec![dir_path.to_path_buf()];
+
+    while let Some(dir_path) = to_visit.pop() {
+        set_permissions(&dir_path, false).await?;
+
+        let mut entries = fs::read_dir(&dir_path).await.with_context(|| {
+            format!("failed to list content of directory {}", dir_path.display())
+        })?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                to_visit.push(entry.path());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn set_permissions(path: &Path, readonly: bool) -> Result<()> {
+    let metadata = fs::metadata(path)
+        .await
+        .with_context(|| format!("failed to read metadata of {}", path.display()))?;
+
+    let permissions = permissions(metadata.permissions(), readonly);
+    fs::set_permissions(path, permissions)
+        .await
+        .with_context(|| format!("failed to change permissions of {}", path.display()))
+}
+
+#[cfg(unix)]
+fn permissions(permissions: Permissions, readonly: bool) -> Permissions {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Only give write access back to the owner, unlike `Permissions::set_readonly(false)`.
+    let mode = if readonly { permissions.mode() & !0o222 } else { permissions.mode() | 0o200 };
+    Permissions::from_mode(mode)
+}
+
+#[cfg(not(unix))]
+fn permissions(mut permissions: Permissions, readonly: bool) -> Permissions {
+    permissions.set_readonly(readonly);
+    permissions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_immutable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let solution_path = temp_dir.path().join("rust/poker");
+        fs::create_dir_all(solution_path.join("src")).await.unwrap();
+        fs::write(solution_path.join("src/lib.rs"), "foo")
+            .await
+            .unwrap();
+        assert!(!is_immutable(&solution_path).await);
+
+        set_immutable(&solution_path, true).await.unwrap();
+        assert!(is_immutable(&solution_path).await);
+        assert!(is_immutable(&solution_path.join("src")).await);
+        assert!(is_immutable(&solution_path.join("src/lib.rs")).await);
+
+        set_immutable(&solution_path, false).await.unwrap();
+        assert!(!is_immutable(&solution_path).await);
+        assert!(!is_immutable(&solution_path.join("src/lib.rs")).await);
+        fs::write(solution_path.join("src/lib.rs"), "bar")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_set_dirs_writable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let solution_path = temp_dir.path().join("rust/poker");
+        fs::create_dir_all(solution_path.join("src")).await.unwrap();
+        fs::write(solution_path.join("src/lib.rs"), "foo")
+            .await
+            .unwrap();
+        set_immutable(&solution_path, true).await.unwrap();
+
+        set_dirs_writable(&solution_path).await.unwrap();
+        assert!(!is_immutable(&solution_path).await);
+        assert!(!is_immutable(&solution_path.join("src")).await);
+        assert!(is_immutable(&solution_path.join("src/lib.rs")).await);
+
+        set_immutable(&solution_path, false).await.unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::fs;
+use tokio::task::AbortHandle;
+use tracing::{debug, warn};
+
+/// Time given to in-progress downloads to complete once the backup has been interrupted.
+pub const GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Waits until the user interrupts the program (via Ctrl-C or, on Unix, `SIGTERM`).
+pub async fn interrupted() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => (),
+                    _ = terminate.recv() => (),
+                }
+            },
+            Err(err) => {
+                debug!("Failed to listen for SIGTERM: {err}");
+                let _ = tokio::signal::ctrl_c().await;
+            },
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Files and directories that are being written and that would be left incomplete
+/// if the backup was aborted.
+#[derive(Debug, Default)]
+pub struct PendingPaths(Mutex<HashSet<PathBuf>>);
+
+impl PendingPaths {
+    pub fn add(&self, path: &Path) {
+        self.0.lock().unwrap().insert(path.to_path_buf());
+    }
+
+    pub fn complete(&self, path: &Path) {
+        self.0.lock().unwrap().remove(path);
+    }
+
+    /// Removes all paths that are still pending from disk.
+    pub async fn clean_up(&self) {
+        let paths = self.0.lock().unwrap().drain().collect::<Vec<_>>();
+
+        for path in paths {
+            let result = match fs::symlink_metadata(&path).await {
+                Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(&path).await,
+                Ok(_) => fs::remove_file(&path).await,
+                Err(_) => continue,
+            };
+
+            match result {
+                Ok(()) => debug!("Removed incomplete {}", path.display()),
+                Err(err) => warn!("Failed to remove incomplete {}: {err}", path.display()),
+            }
+        }
+    }
+}
+
+/// Aborts a task when dropped.
+#[derive(Debug)]
+pub struct AbortOnDrop(pub AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod pending_paths {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_clean_up() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let complete_file = temp_dir.path().join("complete.txt");
+            let pending_file = temp_dir.path().join("pending.txt");
+            let pending_dir = temp_dir.path().join("pending");
+            fs::write(&complete_file, "foo").await.unwrap();
+            fs::write(&pending_file, "bar").await.unwrap();
+            fs::create_dir_all(pending_dir.join("src")).await.unwrap();
+
+            let pending_paths = PendingPaths::default();
+            pending_paths.add(&complete_file);
+            pending_paths.add(&pending_file);
+            pending_paths.add(&pending_dir);
+            pending_paths.add(&temp_dir.path().join("missing.txt"));
+            pending_paths.complete(&complete_file);
+            pending_paths.clean_up().await;
+
+            assert!(complete_file.exists());
+            assert!(!pending_file.exists());
+            assert!(!pending_dir.exists());
+        }
+    }
+}
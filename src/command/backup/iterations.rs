@@ -1,13 +1,45 @@
 use std::env;
+use std::path::Path;
 
 use mini_exercism::api::v2::iteration::Iteration;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::Result;
 
 pub fn get_iterations_dir_name() -> String {
     env::var(ITERATIONS_DIR_ENV_VAR_NAME).unwrap_or_else(|_| DEFAULT_ITERATIONS_DIR_NAME.into())
 }
 
+/// Returns the indexes of the iterations backed up in the given iterations directory, sorted.
+pub async fn list_iterations_in(iterations_path: &Path) -> Result<Vec<i32>> {
+    let mut iterations_dir_content = fs::read_dir(iterations_path).await?;
+
+    let mut iterations = Vec::new();
+    while let Some(entry) = iterations_dir_content.next_entry().await? {
+        let iteration = entry
+            .file_type()
+            .await
+            .ok()
+            .and_then(|file_type| {
+                file_type
+                    .is_dir()
+                    .then(|| entry.file_name().into_string().ok())
+            })
+            .flatten()
+            .and_then(|file_name| file_name.parse::<i32>().ok());
+        if let Some(iteration) = iteration {
+            iterations.push(iteration);
+        }
+    }
+
+    iterations.sort_unstable();
+    Ok(iterations)
+}
+
 pub const ITERATIONS_DIR_ENV_VAR_NAME: &str = "AUXILIAIRE_ITERATIONS_DIR";
 pub const DEFAULT_ITERATIONS_DIR_NAME: &str = "_iterations";
+pub const ITERATION_METADATA_FILE_NAME: &str = ".auxiliaire/iteration.json";
 
 #[derive(Debug, Default, Clone)]
 pub struct SyncOps {
@@ -20,3 +52,243 @@ impl SyncOps {
         self.existing_iterations_to_clean_up.is_empty() && self.iterations_to_backup.is_empty()
     }
 }
+
+/// Plans the operations needed to sync backed up iterations of a solution with those on Exercism.org.
+///
+/// `matching_iterations` are the solution's iterations that match our filters and
+/// `existing_iterations` are the indexes of those already backed up; both must be sorted
+/// by index. Matching iterations that are not backed up yet need to be backed up; existing
+/// iterations that no longer match need to be cleaned up, but only if `clean_up_old` is `true`.
+pub fn plan_iteration_sync<M, E>(
+    matching_iterations: M,
+    existing_iterations: E,
+    clean_up_old: bool,
+) -> SyncOps
+where
+    M: IntoIterator<Item = Iteration>,
+    E: IntoIterator<Item = i32>,
+{
+    let mut existing_it = existing_iterations.into_iter().peekable();
+
+    let mut ops = SyncOps::default();
+    for matching in matching_iterations {
+        while let Some(existing) = existing_it.next_if(|&ne| ne < matching.index) {
+            ops.existing_iterations_to_clean_up.push(existing);
+        }
+        if existing_it.next_if_eq(&matching.index).is_none() {
+            ops.iterations_to_backup.push(matching);
+        }
+    }
+    ops.existing_iterations_to_clean_up.extend(existing_it);
+
+    // Existing iterations are needed even if we don't want to clean them up, because
+    // we need them to compute which iterations are new. However, if we don't want to
+    // clean them up, remove them here.
+    if !clean_up_old {
+        ops.existing_iterations_to_clean_up.clear();
+    }
+
+    ops
+}
+
+/// Metadata stored alongside a backed up iteration's files, so that it can be interpreted
+/// without having to query the Exercism API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IterationMetadata {
+    pub index: i32,
+    pub uuid: String,
+    pub created_at: String,
+    pub status: String,
+    pub tests_status: String,
+    pub is_published: bool,
+}
+
+impl From<&Iteration> for IterationMetadata {
+    fn from(value: &Iteration) -> Self {
+        Self {
+            index: value.index,
+            uuid: value.uuid.clone(),
+            created_at: value.created_at.clone(),
+            status: value.status.to_string(),
+            tests_status: value.tests_status.to_string(),
+            is_published: value.is_published,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod plan_iteration_sync {
+        use std::collections::BTreeSet;
+
+        use proptest::collection::btree_set;
+        use proptest::prelude::*;
+
+        use super::*;
+
+        fn get_iteration(index: i32) -> Iteration {
+            let json = format!(
+                r#"{{
+                    "uuid": "iteration-{index}",
+                    "submission_uuid": "submission-{index}",
+                    "idx": {index},
+                    "status": "no_automated_feedback",
+                    "num_essential_automated_comments": 0,
+                    "num_actionable_automated_comments": 0,
+                    "num_non_actionable_automated_comments": 0,
+                    "num_celebratory_automated_comments": 0,
+                    "submission_method": "cli",
+                    "created_at": "2023-05-07T05:35:43Z",
+                    "tests_status": "passed",
+                    "is_published": false,
+                    "is_latest": false,
+                    "links": {{
+                        "self": "https://exercism.org/tracks/rust/exercises/poker/iterations?idx={index}",
+                        "automated_feedback": "",
+                        "delete": "",
+                        "solution": "https://exercism.org/tracks/rust/exercises/poker",
+                        "test_run": "",
+                        "files": ""
+                    }}
+                }}"#
+            );
+            serde_json::from_str(&json).unwrap()
+        }
+
+        fn plan(matching: &[i32], existing: &[i32], clean_up_old: bool) -> (Vec<i32>, Vec<i32>) {
+            let ops = plan_iteration_sync(
+                matching.iter().copied().map(get_iteration),
+                existing.iter().copied(),
+                clean_up_old,
+            );
+            (
+                ops.iterations_to_backup
+                    .iter()
+                    .map(|iteration| iteration.index)
+                    .collect(),
+                ops.existing_iterations_to_clean_up,
+            )
+        }
+
+        #[test]
+        fn test_all() {
+            assert_eq!((vec![2, 4], vec![1, 5]), plan(&[2, 3, 4], &[1, 3, 5], true));
+            assert_eq!((vec![2, 4], vec![]), plan(&[2, 3, 4], &[1, 3, 5], false));
+            assert_eq!((vec![1, 2], vec![]), plan(&[1, 2], &[], true));
+            assert_eq!((vec![], vec![1, 2]), plan(&[], &[1, 2], true));
+            assert!(plan_iteration_sync(Vec::<Iteration>::new(), Vec::new(), true).is_empty());
+        }
+
+        proptest! {
+            #[test]
+            fn test_matches_set_difference(
+                matching in btree_set(1..50i32, 0..20),
+                existing in btree_set(1..50i32, 0..20),
+                clean_up_old: bool,
+            ) {
+                let (to_backup, to_clean_up) = plan(
+                    &matching.iter().copied().collect::<Vec<_>>(),
+                    &existing.iter().copied().collect::<Vec<_>>(),
+                    clean_up_old,
+                );
+
+                let expected_to_backup: Vec<_> = matching.difference(&existing).copied().collect();
+                prop_assert_eq!(expected_to_backup, to_backup);
+
+                let expected_to_clean_up: Vec<_> = if clean_up_old {
+                    existing.difference(&matching).copied().collect()
+                } else {
+                    vec![]
+                };
+                prop_assert_eq!(expected_to_clean_up, to_clean_up);
+            }
+
+            #[test]
+            fn test_sync_is_idempotent(
+                matching in btree_set(1..50i32, 0..20),
+                existing in btree_set(1..50i32, 0..20),
+            ) {
+                // Once the planned operations are applied, there should be nothing left to do.
+                let (to_backup, to_clean_up) = plan(
+                    &matching.iter().copied().collect::<Vec<_>>(),
+                    &existing.iter().copied().collect::<Vec<_>>(),
+                    true,
+                );
+                let synced: BTreeSet<_> = existing
+                    .iter()
+                    .copied()
+                    .filter(|index| !to_clean_up.contains(index))
+                    .chain(to_backup)
+                    .collect();
+
+                let (to_backup, to_clean_up) = plan(
+                    &matching.iter().copied().collect::<Vec<_>>(),
+                    &synced.into_iter().collect::<Vec<_>>(),
+                    true,
+                );
+                prop_assert!(to_backup.is_empty());
+                prop_assert!(to_clean_up.is_empty());
+            }
+        }
+    }
+
+    mod iteration_metadata {
+        use super::*;
+
+        #[test]
+        fn test_from_iteration() {
+            let json = r#"{
+                "uuid": "e44cbc866b1d42e5b276fd2afabb8fe0",
+                "submission_uuid": "f19960cbe3b344a58f7728db53ce47f9",
+                "idx": 13,
+                "status": "no_automated_feedback",
+                "num_essential_automated_comments": 0,
+                "num_actionable_automated_comments": 0,
+                "num_non_actionable_automated_comments": 0,
+                "num_celebratory_automated_comments": 0,
+                "submission_method": "cli",
+                "created_at": "2023-05-07T05:35:43Z",
+                "tests_status": "passed",
+                "is_published": true,
+                "is_latest": true,
+                "links": {
+                    "self": "https://exercism.org/tracks/rust/exercises/poker/iterations?idx=13",
+                    "automated_feedback": "https://exercism.org/api/v2/solutions/00c717b68e1b4213b316df82636f5e0f/iterations/e44cbc866b1d42e5b276fd2afabb8fe0/automated_feedback",
+                    "delete": "https://exercism.org/api/v2/solutions/00c717b68e1b4213b316df82636f5e0f/iterations/e44cbc866b1d42e5b276fd2afabb8fe0",
+                    "solution": "https://exercism.org/tracks/rust/exercises/poker",
+                    "test_run": "https://exercism.org/api/v2/solutions/00c717b68e1b4213b316df82636f5e0f/submissions/f19960cbe3b344a58f7728db53ce47f9/test_run",
+                    "files": "https://exercism.org/api/v2/solutions/00c717b68e1b4213b316df82636f5e0f/submissions/f19960cbe3b344a58f7728db53ce47f9/files"
+                }
+            }"#;
+            let iteration: Iteration = serde_json::from_str(json).unwrap();
+
+            let expected = IterationMetadata {
+                index: 13,
+                uuid: "e44cbc866b1d42e5b276fd2afabb8fe0".into(),
+                created_at: "2023-05-07T05:35:43Z".into(),
+                status: "no_automated_feedback".into(),
+                tests_status: "passed".into(),
+                is_published: true,
+            };
+            assert_eq!(expected, IterationMetadata::from(&iteration));
+        }
+    }
+    mod list_iterations_in {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_all() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            for dir in ["3", "1", "10", "foo"] {
+                fs::create_dir(temp_dir.path().join(dir)).await.unwrap();
+            }
+            fs::write(temp_dir.path().join("2"), "not an iteration")
+                .await
+                .unwrap();
+
+            assert_eq!(vec![1, 3, 10], list_iterations_in(temp_dir.path()).await.unwrap());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use fs4::{FileExt, TryLockError};
+use tokio::fs;
+use tokio::task::spawn_blocking;
+use tracing::debug;
+
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::Result;
+
+/// Advisory lock on a solution's backup, held while the backup is being modified.
+///
+/// Lock files are stored in the track directory (in `.auxiliaire/<exercise>.lock`) so that they
+/// survive the solution directory being cleaned up. The lock is released when dropped.
+#[derive(Debug)]
+pub struct SolutionLock {
+    _file: File,
+}
+
+impl SolutionLock {
+    /// Acquires the lock for the given exercise in the given track directory, waiting for
+    /// any other process holding it to release it first.
+    pub async fn acquire(track_output_path: &Path, exercise: &str) -> Result<Self> {
+        let lock_file_path = lock_file_path(track_output_path, exercise);
+        if let Some(parent) = lock_file_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        spawn_blocking(move || {
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&lock_file_path)
+                .with_context(|| {
+                    format!("failed to open lock file {}", lock_file_path.display())
+                })?;
+
+            match FileExt::try_lock(&file) {
+                Ok(()) => (),
+                Err(TryLockError::WouldBlock) => {
+                    debug!(
+                        "Waiting for another process to release lock {}",
+                        lock_file_path.display()
+                    );
+                    FileExt::lock(&file)
+                        .with_context(|| format!("failed to lock {}", lock_file_path.display()))?;
+                },
+                Err(TryLockError::Error(err)) => {
+                    return Err(err)
+                        .with_context(|| format!("failed to lock {}", lock_file_path.display()));
+                },
+            }
+
+            Ok(Self { _file: file })
+        })
+        .await?
+    }
+}
+
+fn lock_file_path(track_output_path: &Path, exercise: &str) -> PathBuf {
+    let mut lock_file_path = track_output_path.join(AUXILIAIRE_STATE_DIR_NAME);
+    lock_file_path.push(format!("{exercise}.lock"));
+    lock_file_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod solution_lock {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_acquire() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let lock_file_path = lock_file_path(temp_dir.path(), "poker");
+
+            let lock = SolutionLock::acquire(temp_dir.path(), "poker")
+                .await
+                .unwrap();
+            let other = File::open(&lock_file_path).unwrap();
+            assert!(matches!(FileExt::try_lock(&other), Err(TryLockError::WouldBlock)));
+
+            drop(lock);
+            assert!(FileExt::try_lock(&other).is_ok());
+        }
+    }
+}
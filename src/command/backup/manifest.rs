@@ -0,0 +1,489 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{anyhow, bail, Context};
+use ring::digest;
+use ring::digest::{Digest, SHA256};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::process::Command;
+use tracing::{debug, trace};
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::command::backup::args::ChecksumAlgorithm;
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::walk::files;
+use crate::Result;
+
+pub const MANIFEST_FILE_NAME: &str = ".auxiliaire/manifest.json";
+pub const MANIFEST_TEMP_FILE_NAME: &str = ".auxiliaire/manifest.json.tmp";
+
+/// Extension appended to a manifest's file name to get the name of its detached GPG signature.
+pub const SIGNATURE_EXTENSION: &str = "asc";
+
+/// List of all files stored in a backup directory, along with their hashes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Algorithm used to compute the hashes of files.
+    #[serde(default)]
+    pub checksum: ChecksumAlgorithm,
+
+    /// Backed up files, keyed by their path relative to the backup directory (using `/` as separator).
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+
+    /// Hash of the file's content, computed using the manifest's [checksum algorithm](Manifest::checksum).
+    #[serde(alias = "sha256")]
+    pub hash: String,
+
+    /// Last modification time of the file when it was hashed, in nanoseconds since the Unix epoch.
+    pub modified: u64,
+}
+
+impl Manifest {
+    /// Loads the manifest of the given backup directory, if it exists.
+    pub async fn load(backup_path: &Path) -> Result<Option<Self>> {
+        Self::load_from(&backup_path.join(MANIFEST_FILE_NAME)).await
+    }
+
+    /// Loads a manifest from the given file, if it exists.
+    pub async fn load_from(manifest_file_path: &Path) -> Result<Option<Self>> {
+        let manifest = match fs::read_to_string(manifest_file_path).await {
+            Ok(manifest) => manifest,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("failed to read manifest {}", manifest_file_path.display())
+                })
+            },
+        };
+
+        serde_json::from_str(&manifest)
+            .map(Some)
+            .with_context(|| format!("failed to parse manifest {}", manifest_file_path.display()))
+    }
+
+    /// Builds the manifest of the files currently stored in the given backup directory.
+    ///
+    /// Files whose size and modification time match their entry in the `previous` manifest
+    /// are not hashed again, unless the `previous` manifest used another checksum algorithm.
+    pub async fn build(
+        backup_path: &Path,
+        previous: Option<&Self>,
+        checksum: ChecksumAlgorithm,
+    ) -> Result<Self> {
+        let mut manifest = Self { checksum, ..Self::default() };
+        let previous = previous.filter(|previous| previous.checksum == checksum);
+
+        for (key, file_path) in backup_files(backup_path).await? {
+            let metadata = fs::metadata(&file_path)
+                .await
+                .with_context(|| format!("failed to read metadata of {}", file_path.display()))?;
+            let (size, modified) = (metadata.len(), modified_nanos(&metadata));
+
+            let entry = match previous.and_then(|previous| previous.files.get(&key)) {
+                Some(entry) if entry.size == size && entry.modified == modified => entry.clone(),
+                _ => {
+                    trace!(file = key, "Hashing file");
+                    ManifestEntry { size, hash: hash_file(&file_path, checksum).await?, modified }
+                },
+            };
+            manifest.files.insert(key, entry);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Updates the manifest of the given backup directory so that it lists the files
+    /// currently stored in it, hashing files with the given checksum algorithm.
+    pub async fn update(backup_path: &Path, checksum: ChecksumAlgorithm) -> Result<Self> {
+        let previous = Self::load(backup_path).await?;
+        let manifest = Self::build(backup_path, previous.as_ref(), checksum).await?;
+        manifest.save(backup_path).await?;
+
+        debug!("Manifest updated ({} file(s))", manifest.files.len());
+        Ok(manifest)
+    }
+
+    pub async fn save(&self, backup_path: &Path) -> Result<()> {
+        let manifest = serde_json::to_string_pretty(self)
+            .with_context(|| "failed to persist manifest to JSON")?;
+
+        // Write to a temporary file first so that an interrupted write doesn't corrupt the manifest.
+        let manifest_file_path = backup_path.join(MANIFEST_FILE_NAME);
+        let temp_file_path = backup_path.join(MANIFEST_TEMP_FILE_NAME);
+        fs::create_dir_all(backup_path.join(AUXILIAIRE_STATE_DIR_NAME))
+            .await
+            .with_context(|| {
+                format!("failed to create state directory in {}", backup_path.display())
+            })?;
+        fs::write(&temp_file_path, manifest)
+            .await
+            .with_context(|| format!("failed to save manifest to {}", temp_file_path.display()))?;
+        fs::rename(&temp_file_path, &manifest_file_path)
+            .await
+            .with_context(|| format!("failed to save manifest to {}", manifest_file_path.display()))
+    }
+}
+
+/// Returns the path of the detached signature of the given manifest file.
+pub fn signature_path(manifest_file_path: &Path) -> PathBuf {
+    let mut signature_path = manifest_file_path.as_os_str().to_owned();
+    signature_path.push(".");
+    signature_path.push(SIGNATURE_EXTENSION);
+    signature_path.into()
+}
+
+/// Signs the manifest of the given backup directory with GPG, using the given key.
+///
+/// The signature is written in ASCII-armored form next to the manifest (see [`signature_path`]).
+pub async fn sign_manifest(backup_path: &Path, key_id: &str) -> Result<()> {
+    let manifest_file_path = backup_path.join(MANIFEST_FILE_NAME);
+    let mut command = Command::new("gpg");
+    command
+        .args(["--batch", "--yes", "--armor", "--local-user", key_id, "--output"])
+        .arg(signature_path(&manifest_file_path))
+        .arg("--detach-sign")
+        .arg(&manifest_file_path);
+
+    run_gpg(&mut command)
+        .await
+        .with_context(|| format!("failed to sign manifest {}", manifest_file_path.display()))?;
+    debug!("Manifest signed with key {key_id}");
+    Ok(())
+}
+
+/// Key that made a valid signature, as reported by GPG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signer {
+    /// Fingerprint of the key that made the signature (which can be a subkey).
+    pub fingerprint: String,
+
+    /// Fingerprint of the primary key of the key that made the signature.
+    pub primary_fingerprint: String,
+}
+
+impl Signer {
+    /// Parses the signer from the `VALIDSIG` line of GPG's machine-readable status output
+    /// (see `--status-fd`), if any.
+    fn from_gpg_status(status: &str) -> Option<Self> {
+        status.lines().find_map(|line| {
+            let mut fields = line.strip_prefix("[GNUPG:] VALIDSIG ")?.split_whitespace();
+            let fingerprint = fields.next()?.to_owned();
+            let primary_fingerprint = fields
+                .nth(8)
+                .map_or_else(|| fingerprint.clone(), Into::into);
+            Some(Self { fingerprint, primary_fingerprint })
+        })
+    }
+
+    /// Determines if this is the key with the given fingerprint (or one of its subkeys).
+    ///
+    /// Fingerprints are compared ignoring case and spaces, so that they can be copied
+    /// from the output of `gpg --fingerprint`.
+    pub fn is(&self, fingerprint: &str) -> bool {
+        let fingerprint = fingerprint
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        !fingerprint.is_empty()
+            && (self.fingerprint.eq_ignore_ascii_case(&fingerprint)
+                || self.primary_fingerprint.eq_ignore_ascii_case(&fingerprint))
+    }
+}
+
+/// Checks the detached GPG signature of the given manifest file, returning the key that made it.
+///
+/// GPG accepts a signature made by any key in the user's keyring, so callers must make sure
+/// the returned [`Signer`] is the expected one.
+pub async fn verify_manifest_signature(
+    manifest_file_path: &Path,
+    signature_path: &Path,
+) -> Result<Signer> {
+    let mut command = Command::new("gpg");
+    command
+        .args(["--batch", "--status-fd", "1", "--verify"])
+        .arg(signature_path)
+        .arg(manifest_file_path);
+
+    let status = run_gpg(&mut command).await.with_context(|| {
+        format!("invalid signature for manifest {}", manifest_file_path.display())
+    })?;
+    Signer::from_gpg_status(&status).ok_or_else(|| {
+        anyhow!(
+            "invalid signature for manifest {}: gpg did not report a valid signature",
+            manifest_file_path.display()
+        )
+    })
+}
+
+/// Runs the given GPG command, returning its standard output.
+async fn run_gpg(command: &mut Command) -> Result<String> {
+    trace!(?command);
+
+    let output = command
+        .output()
+        .await
+        .with_context(|| "failed to run gpg")?;
+    if !output.status.success() {
+        bail!(
+            "gpg exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns the hash of the given file's content computed using the given checksum algorithm,
+/// as a hexadecimal string.
+pub async fn hash_file(file_path: &Path, checksum: ChecksumAlgorithm) -> Result<String> {
+    let content = fs::read(file_path)
+        .await
+        .with_context(|| format!("failed to read {}", file_path.display()))?;
+
+    let mut hasher = FileHasher::new(checksum);
+    hasher.update(&content);
+    Ok(hasher.finish())
+}
+
+/// Incremental hasher computing the hash of a file's content using a [`ChecksumAlgorithm`].
+pub enum FileHasher {
+    Sha256(Box<digest::Context>),
+    Blake3(Box<blake3::Hasher>),
+    Xxh3(Box<Xxh3>),
+}
+
+impl FileHasher {
+    pub fn new(checksum: ChecksumAlgorithm) -> Self {
+        match checksum {
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Box::new(digest::Context::new(&SHA256))),
+            ChecksumAlgorithm::Blake3 => Self::Blake3(Box::default()),
+            ChecksumAlgorithm::Xxh3 => Self::Xxh3(Box::default()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(context) => context.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            },
+            Self::Xxh3(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Returns the hash of all data passed to [`update`](Self::update), as a hexadecimal string.
+    pub fn finish(self) -> String {
+        match self {
+            Self::Sha256(context) => hex_digest(&context.finish()),
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Self::Xxh3(hasher) => format!("{:032x}", hasher.digest128()),
+        }
+    }
+}
+
+/// Returns the given digest as a hexadecimal string.
+pub fn hex_digest(digest: &Digest) -> String {
+    digest
+        .as_ref()
+        .iter()
+        .fold(String::with_capacity(64), |mut hash, b| {
+            let _ = write!(hash, "{b:02x}");
+            hash
+        })
+}
+
+/// Returns all files stored in the given backup directory (except those in `.auxiliaire` directories),
+/// along with their key in a [`Manifest`].
+pub async fn backup_files(backup_path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    Ok(files(backup_path, is_state_dir)
+        .await?
+        .into_iter()
+        .map(|file_path| (manifest_key(backup_path, &file_path), file_path))
+        .collect())
+}
+
+/// Returns the key used for the given file in a [`Manifest`].
+fn manifest_key(backup_path: &Path, file_path: &Path) -> String {
+    file_path
+        .strip_prefix(backup_path)
+        .unwrap_or(file_path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn modified_nanos(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+fn is_state_dir(dir_path: &Path) -> bool {
+    dir_path
+        .file_name()
+        .is_some_and(|name| name == AUXILIAIRE_STATE_DIR_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOO_SHA256: &str = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+    const FOO_BLAKE3: &str = "04e0bb39f30b1a3feb89f536c93be15055482df748674b00d26e5a75777702e9";
+
+    mod file_hasher {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            let hash = |checksum| {
+                let mut hasher = FileHasher::new(checksum);
+                hasher.update(b"fo");
+                hasher.update(b"o");
+                hasher.finish()
+            };
+
+            assert_eq!(FOO_SHA256, hash(ChecksumAlgorithm::Sha256));
+            assert_eq!(FOO_BLAKE3, hash(ChecksumAlgorithm::Blake3));
+            assert_eq!(32, hash(ChecksumAlgorithm::Xxh3).len());
+        }
+    }
+
+    mod manifest {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_update() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let backup_path = temp_dir.path();
+            fs::create_dir_all(backup_path.join("rust/poker/.exercism"))
+                .await
+                .unwrap();
+            fs::create_dir_all(backup_path.join("rust/poker/.auxiliaire"))
+                .await
+                .unwrap();
+            fs::write(backup_path.join("rust/poker/lib.rs"), "foo")
+                .await
+                .unwrap();
+            fs::write(backup_path.join("rust/poker/.exercism/config.json"), "{}")
+                .await
+                .unwrap();
+            fs::write(backup_path.join("rust/poker/.auxiliaire/backup_state.json"), "{}")
+                .await
+                .unwrap();
+            assert_eq!(None, Manifest::load(backup_path).await.unwrap());
+
+            let manifest = Manifest::update(backup_path, ChecksumAlgorithm::Sha256)
+                .await
+                .unwrap();
+            assert_eq!(
+                vec!["rust/poker/.exercism/config.json", "rust/poker/lib.rs"],
+                manifest.files.keys().collect::<Vec<_>>()
+            );
+            let entry = &manifest.files["rust/poker/lib.rs"];
+            assert_eq!(3, entry.size);
+            assert_eq!(FOO_SHA256, entry.hash);
+            assert_eq!(Some(&manifest), Manifest::load(backup_path).await.unwrap().as_ref());
+        }
+
+        #[tokio::test]
+        async fn test_build_incremental() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let backup_path = temp_dir.path();
+            fs::write(backup_path.join("unchanged.rs"), "foo")
+                .await
+                .unwrap();
+            fs::write(backup_path.join("changed.rs"), "foo")
+                .await
+                .unwrap();
+
+            let mut previous = Manifest::build(backup_path, None, ChecksumAlgorithm::Sha256)
+                .await
+                .unwrap();
+            for entry in previous.files.values_mut() {
+                entry.hash = "reused".into();
+            }
+            previous.files.get_mut("changed.rs").unwrap().size = 42;
+
+            let manifest = Manifest::build(backup_path, Some(&previous), ChecksumAlgorithm::Sha256)
+                .await
+                .unwrap();
+            assert_eq!("reused", manifest.files["unchanged.rs"].hash);
+            assert_eq!(FOO_SHA256, manifest.files["changed.rs"].hash);
+
+            let manifest = Manifest::build(backup_path, Some(&previous), ChecksumAlgorithm::Blake3)
+                .await
+                .unwrap();
+            assert_eq!(ChecksumAlgorithm::Blake3, manifest.checksum);
+            assert_eq!(FOO_BLAKE3, manifest.files["unchanged.rs"].hash);
+        }
+
+        #[test]
+        fn test_signature_path() {
+            assert_eq!(
+                Path::new("backup/.auxiliaire/manifest.json.asc"),
+                signature_path(&Path::new("backup").join(MANIFEST_FILE_NAME))
+            );
+        }
+
+        #[test]
+        fn test_load_legacy() {
+            let manifest: Manifest = serde_json::from_str(
+                r#"{ "files": { "lib.rs": { "size": 3, "sha256": "abc", "modified": 0 } } }"#,
+            )
+            .unwrap();
+            assert_eq!(ChecksumAlgorithm::Sha256, manifest.checksum);
+            assert_eq!("abc", manifest.files["lib.rs"].hash);
+        }
+    }
+
+    mod signer {
+        use super::*;
+
+        const SUBKEY: &str = "0123456789ABCDEF0123456789ABCDEF01234567";
+        const PRIMARY_KEY: &str = "89ABCDEF0123456789ABCDEF0123456789ABCDEF";
+
+        #[test]
+        fn test_from_gpg_status() {
+            let status = format!(
+                "[GNUPG:] NEWSIG\n[GNUPG:] GOODSIG 0123456789ABCDEF Test <test@example.com>\n[GNUPG:] VALIDSIG {SUBKEY} 2024-01-01 1704067200 0 4 0 22 10 00 {PRIMARY_KEY}\n[GNUPG:] TRUST_UNDEFINED 0 pgp\n"
+            );
+            assert_eq!(
+                Some(Signer {
+                    fingerprint: SUBKEY.into(),
+                    primary_fingerprint: PRIMARY_KEY.into()
+                }),
+                Signer::from_gpg_status(&status)
+            );
+
+            assert_eq!(None, Signer::from_gpg_status("[GNUPG:] BADSIG 0123456789ABCDEF Test\n"));
+        }
+
+        #[test]
+        fn test_is() {
+            let signer =
+                Signer { fingerprint: SUBKEY.into(), primary_fingerprint: PRIMARY_KEY.into() };
+
+            assert!(signer.is(PRIMARY_KEY));
+            assert!(signer.is(&SUBKEY.to_lowercase()));
+            assert!(signer.is("89AB CDEF 0123 4567 89AB  CDEF 0123 4567 89AB CDEF"));
+            assert!(!signer.is("FEDCBA9876543210FEDCBA9876543210FEDCBA98"));
+            assert!(!signer.is(&PRIMARY_KEY[24..]));
+            assert!(!signer.is(""));
+        }
+    }
+}
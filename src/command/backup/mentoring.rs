@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use anyhow::Context;
+use mini_exercism::api::v2::solution::{MentoringStatus, Solution};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::Result;
+
+/// Mentoring request that is pending or in progress for a solution.
+///
+/// The Exercism API does not expose the text of the request, so only the solution's
+/// information is recorded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct MentoringRequest {
+    pub track: String,
+    pub exercise: String,
+    pub uuid: String,
+
+    /// Mentoring status of the solution (`requested` or `in_progress`).
+    pub mentoring_status: String,
+
+    pub private_url: String,
+    pub updated_at: String,
+    pub last_iterated_at: Option<String>,
+}
+
+impl MentoringRequest {
+    /// Returns the mentoring request of the given solution, if it has one that is still open.
+    pub fn for_solution(solution: &Solution) -> Option<Self> {
+        match solution.mentoring_status {
+            MentoringStatus::Requested | MentoringStatus::InProgress => Some(Self {
+                track: solution.track.name.clone(),
+                exercise: solution.exercise.name.clone(),
+                uuid: solution.uuid.clone(),
+                mentoring_status: solution.mentoring_status.to_string(),
+                private_url: solution.private_url.clone(),
+                updated_at: solution.updated_at.clone(),
+                last_iterated_at: solution.last_iterated_at.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Saves the given mentoring requests to a JSON file, sorted by track and exercise.
+pub async fn save_mentoring_requests(
+    mut requests: Vec<MentoringRequest>,
+    requests_file_path: &Path,
+) -> Result<()> {
+    requests.sort_unstable();
+    let requests = serde_json::to_string_pretty(&requests)
+        .with_context(|| "failed to persist mentoring requests to JSON")?;
+
+    fs::write(requests_file_path, requests)
+        .await
+        .with_context(|| {
+            format!("failed to save mentoring requests to {}", requests_file_path.display())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_fixtures;
+
+    fn solution(exercise: &str, mentoring_status: &str) -> Solution {
+        test_fixtures::solution(
+            "rust",
+            exercise,
+            json!({
+                "uuid": format!("{exercise}-uuid"),
+                "status": "iterated",
+                "mentoring_status": mentoring_status,
+                "published_iteration_head_tests_status": "not_queued",
+            }),
+        )
+    }
+
+    mod mentoring_request {
+        use super::*;
+
+        #[test]
+        fn test_for_solution() {
+            let request =
+                MentoringRequest::for_solution(&solution("poker", "in_progress")).unwrap();
+            assert_eq!("rust", request.track);
+            assert_eq!("poker", request.exercise);
+            assert_eq!("in_progress", request.mentoring_status);
+            assert_eq!(Some("2023-05-07T05:35:43Z"), request.last_iterated_at.as_deref());
+
+            assert!(MentoringRequest::for_solution(&solution("clock", "requested")).is_some());
+            assert!(MentoringRequest::for_solution(&solution("clock", "none")).is_none());
+            assert!(MentoringRequest::for_solution(&solution("clock", "finished")).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_mentoring_requests() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let requests_file_path = temp_dir.path().join("mentoring.json");
+
+        let requests = ["poker", "clock"]
+            .into_iter()
+            .filter_map(|exercise| MentoringRequest::for_solution(&solution(exercise, "requested")))
+            .collect::<Vec<_>>();
+        save_mentoring_requests(requests, &requests_file_path)
+            .await
+            .unwrap();
+
+        let saved: Vec<MentoringRequest> =
+            serde_json::from_str(&fs::read_to_string(&requests_file_path).await.unwrap()).unwrap();
+        assert_eq!(
+            vec!["clock", "poker"],
+            saved
+                .iter()
+                .map(|request| request.exercise.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write as _};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::Serialize;
+use tokio::fs;
+
+use crate::command::size::human_size;
+use crate::Result;
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    solutions_backed_up: AtomicU64,
+    bytes_downloaded: AtomicU64,
+    files_downloaded: AtomicU64,
+    errors: AtomicU64,
+
+    /// Number of bytes downloaded for each solution, keyed by solution UUID.
+    solution_bytes: Mutex<HashMap<String, u64>>,
+    solution_timings: Mutex<Vec<SolutionTiming>>,
+}
+
+/// Time spent backing up a solution, along with the number of bytes downloaded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SolutionTiming {
+    pub track: String,
+    pub exercise: String,
+    pub elapsed_ms: u64,
+    pub bytes: u64,
+}
+
+impl SolutionTiming {
+    /// Returns the number of bytes downloaded per second for this solution.
+    pub fn throughput(&self) -> u64 {
+        match self.elapsed_ms {
+            0 => self.bytes,
+            elapsed_ms => self.bytes * 1000 / elapsed_ms,
+        }
+    }
+}
+
+impl Display for SolutionTiming {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}: {:.1}s, {} ({}/s)",
+            self.track,
+            self.exercise,
+            Duration::from_millis(self.elapsed_ms).as_secs_f64(),
+            human_size(self.bytes),
+            human_size(self.throughput())
+        )
+    }
+}
+
+impl Metrics {
+    pub fn solution_backed_up(&self) {
+        self.solutions_backed_up.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn bytes_downloaded(&self, bytes: usize) {
+        self.bytes_downloaded
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn total_bytes_downloaded(&self) -> u64 {
+        self.bytes_downloaded.load(Ordering::Relaxed)
+    }
+
+    pub fn file_downloaded(&self) {
+        self.files_downloaded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn total_files_downloaded(&self) -> u64 {
+        self.files_downloaded.load(Ordering::Relaxed)
+    }
+
+    pub fn error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records bytes downloaded for the solution with the given UUID (see [`solution_finished`](Self::solution_finished)).
+    pub fn solution_bytes_downloaded(&self, solution_uuid: &str, bytes: usize) {
+        *self
+            .solution_bytes
+            .lock()
+            .unwrap()
+            .entry(solution_uuid.into())
+            .or_default() += bytes as u64;
+    }
+
+    /// Records the time spent backing up a solution, returning its [`SolutionTiming`].
+    pub fn solution_finished(
+        &self,
+        track: &str,
+        exercise: &str,
+        solution_uuid: &str,
+        elapsed: Duration,
+    ) -> SolutionTiming {
+        let bytes = self
+            .solution_bytes
+            .lock()
+            .unwrap()
+            .remove(solution_uuid)
+            .unwrap_or_default();
+        let timing = SolutionTiming {
+            track: track.into(),
+            exercise: exercise.into(),
+            elapsed_ms: elapsed.as_millis() as u64,
+            bytes,
+        };
+
+        self.solution_timings.lock().unwrap().push(timing.clone());
+        timing
+    }
+
+    /// Returns the `n` solutions that took the most time to back up, slowest first.
+    ///
+    /// Solutions for which nothing was downloaded are ignored.
+    pub fn slowest_solutions(&self, n: usize) -> Vec<SolutionTiming> {
+        let mut timings = self
+            .solution_timings
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|timing| timing.bytes > 0)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        timings.sort_by_key(|timing| Reverse(timing.elapsed_ms));
+        timings.truncate(n);
+        timings
+    }
+
+    pub fn to_prometheus(&self, duration: Duration, success: bool) -> String {
+        let metrics: [(&str, &str, &str, String); 6] = [
+            (
+                "solutions_backed_up_total",
+                "counter",
+                "Number of solutions backed up during the last run",
+                self.solutions_backed_up.load(Ordering::Relaxed).to_string(),
+            ),
+            (
+                "bytes_downloaded_total",
+                "counter",
+                "Number of bytes downloaded during the last run",
+                self.total_bytes_downloaded().to_string(),
+            ),
+            (
+                "files_downloaded_total",
+                "counter",
+                "Number of files downloaded during the last run",
+                self.total_files_downloaded().to_string(),
+            ),
+            (
+                "errors_total",
+                "counter",
+                "Number of solutions that could not be backed up during the last run",
+                self.errors.load(Ordering::Relaxed).to_string(),
+            ),
+            (
+                "duration_seconds",
+                "gauge",
+                "Duration of the last run, in seconds",
+                duration.as_secs_f64().to_string(),
+            ),
+            (
+                "success",
+                "gauge",
+                "Whether the last run completed successfully (1) or not (0)",
+                u8::from(success).to_string(),
+            ),
+        ];
+
+        metrics
+            .iter()
+            .fold(String::new(), |mut output, (name, metric_type, help, value)| {
+                let name = format!("{METRICS_PREFIX}{name}");
+                let _ = writeln!(output, "# HELP {name} {help}");
+                let _ = writeln!(output, "# TYPE {name} {metric_type}");
+                let _ = writeln!(output, "{name} {value}");
+                output
+            })
+    }
+
+    pub async fn save(
+        &self,
+        metrics_file_path: &Path,
+        duration: Duration,
+        success: bool,
+    ) -> Result<()> {
+        // node_exporter's textfile collector might read the file while we write it,
+        // so we write to a temporary file first and then rename it.
+        let mut temp_file_path = metrics_file_path.as_os_str().to_owned();
+        temp_file_path.push(".tmp");
+
+        fs::write(&temp_file_path, self.to_prometheus(duration, success))
+            .await
+            .with_context(|| {
+                format!("failed to write metrics to {}", Path::new(&temp_file_path).display())
+            })?;
+        fs::rename(&temp_file_path, metrics_file_path)
+            .await
+            .with_context(|| format!("failed to save metrics to {}", metrics_file_path.display()))
+    }
+}
+
+const METRICS_PREFIX: &str = "auxiliaire_backup_";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod to_prometheus {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            let metrics = Metrics::default();
+            metrics.solution_backed_up();
+            metrics.solution_backed_up();
+            metrics.bytes_downloaded(1024);
+            metrics.bytes_downloaded(42);
+            metrics.file_downloaded();
+            metrics.error();
+
+            let output = metrics.to_prometheus(Duration::from_millis(1500), false);
+            let lines = output.lines().collect::<Vec<_>>();
+
+            assert!(lines.contains(&"# TYPE auxiliaire_backup_solutions_backed_up_total counter"));
+            assert!(lines.contains(&"auxiliaire_backup_solutions_backed_up_total 2"));
+            assert!(lines.contains(&"auxiliaire_backup_bytes_downloaded_total 1066"));
+            assert!(lines.contains(&"auxiliaire_backup_files_downloaded_total 1"));
+            assert!(lines.contains(&"auxiliaire_backup_errors_total 1"));
+            assert!(lines.contains(&"auxiliaire_backup_duration_seconds 1.5"));
+            assert!(lines.contains(&"auxiliaire_backup_success 0"));
+        }
+    }
+
+    mod slowest_solutions {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            let metrics = Metrics::default();
+            metrics.solution_bytes_downloaded("poker-uuid", 2048);
+            metrics.solution_bytes_downloaded("clock-uuid", 100);
+            metrics.solution_bytes_downloaded("poker-uuid", 2048);
+
+            let timing =
+                metrics.solution_finished("rust", "poker", "poker-uuid", Duration::from_secs(2));
+            assert_eq!(4096, timing.bytes);
+            assert_eq!(2048, timing.throughput());
+            assert_eq!("rust/poker: 2.0s, 4.0 KiB (2.0 KiB/s)", timing.to_string());
+            metrics.solution_finished("rust", "clock", "clock-uuid", Duration::from_secs(3));
+            metrics.solution_finished("rust", "robot", "robot-uuid", Duration::from_secs(4));
+
+            let slowest = metrics.slowest_solutions(5);
+            assert_eq!(
+                vec!["clock", "poker"],
+                slowest
+                    .iter()
+                    .map(|timing| timing.exercise.as_str())
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(1, metrics.slowest_solutions(1).len());
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use tokio::fs;
+
+use crate::command::backup::state::BACKUP_STATE_FILE_NAME;
+use crate::Result;
+
+/// Copies the backup of a solution stored in `solution_path` to `mirror_solution_path`,
+/// unless the mirror already contains the same backup.
+///
+/// The two backups are considered identical if their backup state files have the same content.
+/// Otherwise, the solution is first copied to a temporary directory, then moved in place of the
+/// existing backup in the mirror (if any), so that the mirror never contains a partial backup.
+/// If `replace` is `false` and the mirror contains a different backup, an error is returned.
+///
+/// Returns `true` if the solution was copied.
+pub async fn mirror_solution(
+    solution_path: &Path,
+    mirror_solution_path: &Path,
+    replace: bool,
+) -> Result<bool> {
+    let Ok(state) = fs::read(state_file_path(solution_path)).await else {
+        // Solution was never backed up, nothing to mirror.
+        return Ok(false);
+    };
+    let mirror_exists = fs::try_exists(mirror_solution_path).await?;
+    if mirror_exists {
+        if fs::read(state_file_path(mirror_solution_path))
+            .await
+            .is_ok_and(|mirror_state| mirror_state == state)
+        {
+            return Ok(false);
+        }
+        if !replace {
+            bail!("mirror {} contains a different backup", mirror_solution_path.display());
+        }
+    }
+
+    let temp_name = format!(
+        ".{}.tmp",
+        mirror_solution_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+    );
+    let temp_path = mirror_solution_path.with_file_name(temp_name);
+    if fs::try_exists(&temp_path).await? {
+        fs::remove_dir_all(&temp_path).await?;
+    }
+
+    copy_dir(solution_path, &temp_path).await?;
+    if mirror_exists {
+        fs::remove_dir_all(mirror_solution_path)
+            .await
+            .with_context(|| format!("failed to remove {}", mirror_solution_path.display()))?;
+    }
+    fs::rename(&temp_path, mirror_solution_path)
+        .await
+        .with_context(|| format!("failed to move {}", mirror_solution_path.display()))?;
+
+    Ok(true)
+}
+
+fn state_file_path(solution_path: &Path) -> PathBuf {
+    let mut state_file_path = solution_path.to_path_buf();
+    state_file_path.extend(BACKUP_STATE_FILE_NAME.split('/'));
+    state_file_path
+}
+
+/// Recreates the content of `source_path` in `destination_path`, copying files.
+async fn copy_dir(source_path: &Path, destination_path: &Path) -> Result<()> {
+    let mut to_visit = vec![(source_path.to_path_buf(), destination_path.to_path_buf())];
+
+    while let Some((source_dir, destination_dir)) = to_visit.pop() {
+        fs::create_dir_all(&destination_dir)
+            .await
+            .with_context(|| format!("failed to create {}", destination_dir.display()))?;
+        let mut entries = fs::read_dir(&source_dir).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            let destination = destination_dir.join(entry.file_name());
+
+            if file_type.is_dir() {
+                to_visit.push((entry.path(), destination));
+            } else if file_type.is_file() {
+                fs::copy(entry.path(), &destination)
+                    .await
+                    .with_context(|| format!("failed to copy {}", destination.display()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+        fs::write(path, content).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mirror_solution() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let solution_path = temp_dir.path().join("backup/rust/poker");
+        let mirror_path = temp_dir.path().join("mirror/rust/poker");
+
+        assert!(!mirror_solution(&solution_path, &mirror_path, true)
+            .await
+            .unwrap());
+
+        write(&solution_path.join("src/lib.rs"), "foo").await;
+        write(&state_file_path(&solution_path), "{}").await;
+        assert!(mirror_solution(&solution_path, &mirror_path, true)
+            .await
+            .unwrap());
+        assert_eq!(
+            "foo",
+            fs::read_to_string(mirror_path.join("src/lib.rs"))
+                .await
+                .unwrap()
+        );
+        assert!(!mirror_solution(&solution_path, &mirror_path, true)
+            .await
+            .unwrap());
+
+        fs::remove_file(solution_path.join("src/lib.rs"))
+            .await
+            .unwrap();
+        write(&solution_path.join("lib.rs"), "bar").await;
+        write(&state_file_path(&solution_path), r#"{"uuid":""}"#).await;
+        assert!(mirror_solution(&solution_path, &mirror_path, false)
+            .await
+            .is_err());
+        assert!(mirror_solution(&solution_path, &mirror_path, true)
+            .await
+            .unwrap());
+        assert!(!mirror_path.join("src/lib.rs").exists());
+        assert_eq!(
+            "bar",
+            fs::read_to_string(mirror_path.join("lib.rs"))
+                .await
+                .unwrap()
+        );
+        assert!(!temp_dir.path().join("mirror/rust/.poker.tmp").exists());
+    }
+}
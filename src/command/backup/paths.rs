@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::command::backup::manifest::hex_digest;
+use crate::Result;
+
+pub const SHORTENED_PATHS_FILE_NAME: &str = ".auxiliaire/shortened_paths.json";
+
+/// Maximum length of a path on this platform.
+///
+/// On Windows, this is the legacy `MAX_PATH` limit, since long path support is opt-in.
+#[cfg(windows)]
+pub const MAX_PATH_LEN: usize = 260;
+#[cfg(not(windows))]
+pub const MAX_PATH_LEN: usize = 4096;
+
+/// Maximum length of a single path component (e.g. a file name), for most file systems.
+pub const MAX_NAME_LEN: usize = 255;
+
+/// Number of hexadecimal characters of a file's hash appended to its name when it is shortened.
+const HASH_SUFFIX_LEN: usize = 8;
+
+/// Makes sure the given path fits the platform's limits.
+pub fn check_path_length(path: &Path) -> Result<()> {
+    if let Some(name) = path.iter().find(|component| component.len() > MAX_NAME_LEN) {
+        bail!(
+            "path {} is too long: component {} exceeds {MAX_NAME_LEN} characters (see --long-paths)",
+            path.display(),
+            name.to_string_lossy(),
+        );
+    }
+
+    let path_len = path.as_os_str().len();
+    if path_len > MAX_PATH_LEN {
+        bail!(
+            "path {} is too long: {path_len} characters exceeds the limit of {MAX_PATH_LEN} (see --long-paths)",
+            path.display(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns a shortened version of `file` (a path relative to `output_path` using `/` as separator)
+/// so that it fits the platform's limits when stored in `output_path`, or `None` if it already fits.
+///
+/// Only the file name is shortened: it is truncated and suffixed with a hash of the original
+/// file path (keeping its extension), so the result is deterministic and unlikely to collide
+/// with other files. If the path still does not fit, an error is returned.
+pub fn shorten_file_path(output_path: &Path, file: &str) -> Result<Option<String>> {
+    let mut file_path = output_path.to_path_buf();
+    file_path.extend(file.split('/'));
+    if check_path_length(&file_path).is_ok() {
+        return Ok(None);
+    }
+
+    let (dir, file_name) = file.rsplit_once('/').unwrap_or(("", file));
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (file_name, String::new()),
+    };
+    let suffix = format!("~{}{extension}", &file_hash(file)[..HASH_SUFFIX_LEN]);
+
+    let max_name_len = MAX_NAME_LEN
+        .min((MAX_PATH_LEN + file_name.len()).saturating_sub(file_path.as_os_str().len()));
+    let Some(max_stem_len) = max_name_len.checked_sub(suffix.len()) else {
+        bail!("path {} is too long and cannot be shortened", file_path.display());
+    };
+    let stem_len = stem
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .take_while(|&len| len <= max_stem_len)
+        .last()
+        .unwrap_or(0);
+
+    let shortened = match dir {
+        "" => format!("{}{suffix}", &stem[..stem_len]),
+        dir => format!("{dir}/{}{suffix}", &stem[..stem_len]),
+    };
+    let mut shortened_path = output_path.to_path_buf();
+    shortened_path.extend(shortened.split('/'));
+    check_path_length(&shortened_path)?;
+
+    Ok(Some(shortened))
+}
+
+/// Mapping of the files of a solution that were stored under a shortened path
+/// (see [`shorten_file_path`]), stored in the solution's backup directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShortenedPaths {
+    /// Shortened path of each file, keyed by the file's original path.
+    pub files: BTreeMap<String, String>,
+}
+
+impl ShortenedPaths {
+    /// Loads the mapping stored in the given solution backup directory; if it does not exist,
+    /// an empty mapping is returned.
+    pub async fn load(solution_output_path: &Path) -> Result<Self> {
+        let mapping_file_path = solution_output_path.join(SHORTENED_PATHS_FILE_NAME);
+        let mapping = match fs::read_to_string(&mapping_file_path).await {
+            Ok(mapping) => mapping,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("failed to read shortened paths {}", mapping_file_path.display())
+                })
+            },
+        };
+
+        serde_json::from_str(&mapping).with_context(|| {
+            format!("failed to parse shortened paths {}", mapping_file_path.display())
+        })
+    }
+
+    /// Returns the path where the given file is stored in the solution's backup directory.
+    pub fn local_path<'a>(&'a self, file: &'a str) -> &'a str {
+        self.files.get(file).map_or(file, String::as_str)
+    }
+
+    /// Saves the mapping to the given solution backup directory. If the mapping is empty,
+    /// any existing mapping file is removed instead.
+    pub async fn save(&self, solution_output_path: &Path) -> Result<()> {
+        let mapping_file_path = solution_output_path.join(SHORTENED_PATHS_FILE_NAME);
+        if self.files.is_empty() {
+            return match fs::remove_file(&mapping_file_path).await {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                    Err(err).with_context(|| {
+                        format!("failed to remove shortened paths {}", mapping_file_path.display())
+                    })
+                },
+                _ => Ok(()),
+            };
+        }
+
+        let mapping = serde_json::to_string_pretty(self)
+            .with_context(|| "failed to persist shortened paths to JSON")?;
+        if let Some(parent) = mapping_file_path.parent() {
+            fs::create_dir_all(parent).await.with_context(|| {
+                format!("failed to create state directory {}", parent.display())
+            })?;
+        }
+        fs::write(&mapping_file_path, mapping)
+            .await
+            .with_context(|| {
+                format!("failed to save shortened paths to {}", mapping_file_path.display())
+            })
+    }
+}
+
+fn file_hash(file: &str) -> String {
+    hex_digest(&digest(&SHA256, file.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    mod check_path_length {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            assert!(check_path_length(Path::new("backup/rust/poker/src/lib.rs")).is_ok());
+            assert!(
+                check_path_length(&Path::new("backup").join("a".repeat(MAX_NAME_LEN + 1))).is_err()
+            );
+
+            let too_long = (0..=MAX_PATH_LEN / 10)
+                .map(|_| "directory")
+                .collect::<PathBuf>();
+            assert!(check_path_length(&too_long).is_err());
+        }
+    }
+
+    mod shorten_file_path {
+        use super::*;
+
+        #[test]
+        fn test_short_enough() {
+            assert_eq!(None, shorten_file_path(Path::new("backup"), "src/lib.rs").unwrap());
+        }
+
+        #[test]
+        fn test_long_name() {
+            let file = format!("src/{}.rs", "a".repeat(300));
+
+            let shortened = shorten_file_path(Path::new("backup"), &file)
+                .unwrap()
+                .unwrap();
+            assert!(shortened.starts_with("src/aaaa"));
+            assert!(shortened.ends_with(".rs"));
+            assert!(shortened.contains('~'));
+            assert_eq!(MAX_NAME_LEN, shortened.len() - "src/".len());
+            assert_eq!(Some(shortened), shorten_file_path(Path::new("backup"), &file).unwrap());
+        }
+
+        #[test]
+        fn test_long_dir() {
+            let file = format!("{}/lib.rs", "a".repeat(300));
+
+            assert!(shorten_file_path(Path::new("backup"), &file).is_err());
+        }
+    }
+
+    mod shortened_paths {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_save() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution_path = temp_dir.path();
+            assert_eq!(
+                ShortenedPaths::default(),
+                ShortenedPaths::load(solution_path).await.unwrap()
+            );
+
+            let mut shortened_paths = ShortenedPaths::default();
+            shortened_paths
+                .files
+                .insert("src/long.rs".into(), "src/lo~12345678.rs".into());
+            shortened_paths.save(solution_path).await.unwrap();
+
+            let loaded = ShortenedPaths::load(solution_path).await.unwrap();
+            assert_eq!(shortened_paths, loaded);
+            assert_eq!("src/lo~12345678.rs", loaded.local_path("src/long.rs"));
+            assert_eq!("src/lib.rs", loaded.local_path("src/lib.rs"));
+
+            ShortenedPaths::default().save(solution_path).await.unwrap();
+            assert!(!solution_path.join(SHORTENED_PATHS_FILE_NAME).exists());
+        }
+    }
+}
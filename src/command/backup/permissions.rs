@@ -0,0 +1,127 @@
+#[cfg(unix)]
+use std::fs::Permissions;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use anyhow::anyhow;
+#[cfg(unix)]
+use anyhow::Context;
+#[cfg(unix)]
+use tokio::fs;
+
+use crate::Result;
+
+/// Modes to apply to files and directories created during a backup
+/// (see [`BackupArgs::file_mode`](crate::command::backup::args::BackupArgs::file_mode)
+/// and [`BackupArgs::dir_mode`](crate::command::backup::args::BackupArgs::dir_mode)).
+///
+/// Modes are applied after files and directories are created, so they are not affected by the umask.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Modes {
+    pub file: Option<u32>,
+    pub dir: Option<u32>,
+}
+
+impl Modes {
+    /// Determines if any mode is set.
+    pub fn is_set(&self) -> bool {
+        self.file.is_some() || self.dir.is_some()
+    }
+
+    /// Applies modes to the given directory. If `recursive` is `true`, modes are also applied to
+    /// everything it contains; otherwise, they are only applied to the directory and the files
+    /// directly in it.
+    #[cfg(unix)]
+    pub async fn apply(&self, dir_path: &Path, recursive: bool) -> Result<()> {
+        if !self.is_set() {
+            return Ok(());
+        }
+
+        let mut to_visit = vec![dir_path.to_path_buf()];
+        while let Some(dir_path) = to_visit.pop() {
+            set_mode(&dir_path, self.dir).await?;
+
+            let mut entries = fs::read_dir(&dir_path).await.with_context(|| {
+                format!("failed to list content of directory {}", dir_path.display())
+            })?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_type = entry.file_type().await?;
+                if file_type.is_dir() && recursive {
+                    to_visit.push(entry.path());
+                } else if file_type.is_file() {
+                    set_mode(&entry.path(), self.file).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub async fn apply(&self, _dir_path: &Path, _recursive: bool) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+async fn set_mode(path: &Path, mode: Option<u32>) -> Result<()> {
+    if let Some(mode) = mode {
+        fs::set_permissions(path, Permissions::from_mode(mode))
+            .await
+            .with_context(|| format!("failed to change permissions of {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Parses a Unix mode written in octal (e.g. `644`, `0644` or `0o644`).
+pub fn parse_mode(s: &str) -> Result<u32> {
+    let digits = s.strip_prefix("0o").unwrap_or(s);
+    match u32::from_str_radix(digits, 8) {
+        Ok(mode) if !digits.is_empty() && mode <= 0o7777 => Ok(mode),
+        _ => Err(anyhow!("invalid mode '{s}': expected an octal number like 644")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(0o644, parse_mode("644").unwrap());
+        assert_eq!(0o755, parse_mode("0755").unwrap());
+        assert_eq!(0o2775, parse_mode("0o2775").unwrap());
+        assert!(parse_mode("").is_err());
+        assert!(parse_mode("0o").is_err());
+        assert!(parse_mode("648").is_err());
+        assert!(parse_mode("17777").is_err());
+        assert!(parse_mode("rw-r--r--").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_apply() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let solution_path = temp_dir.path().join("rust/poker");
+        fs::create_dir_all(solution_path.join("src")).await.unwrap();
+        fs::write(solution_path.join("src/lib.rs"), "foo")
+            .await
+            .unwrap();
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+
+        let modes = Modes { file: Some(0o604), dir: Some(0o711) };
+        modes
+            .apply(&temp_dir.path().join("rust"), false)
+            .await
+            .unwrap();
+        assert_eq!(0o711, mode(&temp_dir.path().join("rust")));
+        assert_ne!(0o711, mode(&solution_path));
+
+        modes.apply(&solution_path, true).await.unwrap();
+        assert_eq!(0o711, mode(&solution_path));
+        assert_eq!(0o711, mode(&solution_path.join("src")));
+        assert_eq!(0o604, mode(&solution_path.join("src/lib.rs")));
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+
+use anstyle::{AnsiColor, Color, Style};
+
+use crate::style::NAME;
+
+const ADDED: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+const REPLACED: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+const REMOVED: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+/// Operations that would be performed for a solution during a dry run.
+#[derive(Debug, Default, Clone)]
+pub struct PlannedSolution {
+    pub exists: bool,
+    pub files: Vec<String>,
+    pub iterations_to_backup: Vec<i32>,
+    pub iterations_to_clean_up: Vec<i32>,
+}
+
+/// Plan of a dry run, collected while processing solutions so that it can be rendered as a tree.
+#[derive(Debug, Default)]
+pub struct Plan {
+    solutions: Mutex<BTreeMap<String, BTreeMap<String, PlannedSolution>>>,
+}
+
+impl Plan {
+    pub fn add(&self, track: &str, exercise: &str, solution: PlannedSolution) {
+        self.solutions
+            .lock()
+            .unwrap()
+            .entry(track.into())
+            .or_default()
+            .insert(exercise.into(), solution);
+    }
+
+    pub fn render(&self, iterations_dir_name: &str) -> String {
+        let solutions = self.solutions.lock().unwrap();
+
+        let mut output = String::new();
+        for (track, exercises) in solutions.iter() {
+            let _ = writeln!(output, "{NAME}{track}{NAME:#}");
+
+            for (exercise_pos, (exercise, solution)) in exercises.iter().enumerate() {
+                let last_exercise = exercise_pos == exercises.len() - 1;
+                let state = if solution.exists { "update" } else { "new" };
+                let _ =
+                    writeln!(output, "{}{NAME}{exercise}{NAME:#} ({state})", branch(last_exercise));
+
+                let (file_marker, file_style) =
+                    if solution.exists { ('~', REPLACED) } else { ('+', ADDED) };
+                let entries = solution
+                    .files
+                    .iter()
+                    .map(|file| (file_marker, file_style, file.clone()))
+                    .chain(solution.iterations_to_backup.iter().map(|iteration| {
+                        ('+', ADDED, format!("{iterations_dir_name}/{iteration}"))
+                    }))
+                    .chain(solution.iterations_to_clean_up.iter().map(|iteration| {
+                        ('-', REMOVED, format!("{iterations_dir_name}/{iteration}"))
+                    }))
+                    .collect::<Vec<_>>();
+
+                let indent = if last_exercise { "    " } else { "│   " };
+                for (entry_pos, (marker, style, entry)) in entries.iter().enumerate() {
+                    let _ = writeln!(
+                        output,
+                        "{indent}{}{style}{marker} {entry}{style:#}",
+                        branch(entry_pos == entries.len() - 1)
+                    );
+                }
+            }
+        }
+
+        output
+    }
+}
+
+fn branch(last: bool) -> &'static str {
+    if last {
+        "└── "
+    } else {
+        "├── "
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anstream::adapter::strip_str;
+
+    use super::*;
+
+    mod plan {
+        use super::*;
+
+        #[test]
+        fn test_render() {
+            let plan = Plan::default();
+            plan.add(
+                "rust",
+                "poker",
+                PlannedSolution {
+                    exists: true,
+                    files: vec!["src/lib.rs".into()],
+                    iterations_to_backup: vec![3],
+                    iterations_to_clean_up: vec![1],
+                },
+            );
+            plan.add(
+                "rust",
+                "clock",
+                PlannedSolution { files: vec!["src/lib.rs".into()], ..PlannedSolution::default() },
+            );
+
+            let expected = "\
+rust
+├── clock (new)
+│   └── + src/lib.rs
+└── poker (update)
+    ├── ~ src/lib.rs
+    ├── + _iterations/3
+    └── - _iterations/1
+";
+            assert_eq!(expected, strip_str(&plan.render("_iterations")).to_string());
+        }
+    }
+}
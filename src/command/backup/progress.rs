@@ -0,0 +1,187 @@
+use std::fmt::{Debug, Formatter};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tracing::debug;
+
+use crate::command::backup::args::ProgressFormat;
+use crate::command::backup::metrics::SolutionTiming;
+use crate::Result;
+
+/// Progress event emitted while backing up solutions (see [`BackupArgs::progress`]).
+///
+/// [`BackupArgs::progress`]: crate::command::backup::args::BackupArgs::progress
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent<'a> {
+    /// Backup of a solution has started.
+    SolutionStarted { track: &'a str, exercise: &'a str },
+
+    /// A file of a solution (or of one of its iterations) has been downloaded.
+    FileDownloaded {
+        track: &'a str,
+        exercise: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        iteration: Option<i32>,
+        file: &'a str,
+        bytes: u64,
+    },
+
+    /// Backup of a solution has finished.
+    SolutionFinished {
+        track: &'a str,
+        exercise: &'a str,
+        success: bool,
+        elapsed_ms: u64,
+        bytes: u64,
+    },
+
+    /// A backed up solution no longer exists on Exercism.org.
+    RemotelyDeleted { track: &'a str, exercise: &'a str },
+
+    /// The backup has finished.
+    Finished {
+        success: bool,
+        files_downloaded: u64,
+        bytes_downloaded: u64,
+        slowest_solutions: &'a [SolutionTiming],
+    },
+}
+
+/// Writes [`ProgressEvent`]s to a dedicated stream, if progress reporting is enabled.
+///
+/// Cloning a reporter (or calling [`for_account`](Self::for_account)) returns a reporter writing
+/// to the same stream, so that multiple commands can report progress without overwriting each
+/// other's events.
+#[derive(Default, Clone)]
+pub struct ProgressReporter {
+    output: Option<Arc<Mutex<Box<dyn Write + Send>>>>,
+    account: Option<String>,
+}
+
+impl ProgressReporter {
+    pub fn new(format: Option<ProgressFormat>, fd: Option<u32>) -> Result<Self> {
+        let output: Box<dyn Write + Send> = match (format, fd) {
+            (None, _) => return Ok(Self::default()),
+            (Some(ProgressFormat::Json), None) => Box::new(std::io::stderr()),
+            (Some(ProgressFormat::Json), Some(fd)) => Box::new(open_fd(fd)?),
+        };
+
+        Ok(Self { output: Some(Arc::new(Mutex::new(output))), account: None })
+    }
+
+    /// Returns a reporter writing to the same stream, whose events include the given account name
+    /// (see [`BackupArgs::account`]).
+    ///
+    /// [`BackupArgs::account`]: crate::command::backup::args::BackupArgs::account
+    pub fn for_account(&self, account: &str) -> Self {
+        Self { output: self.output.clone(), account: Some(account.into()) }
+    }
+
+    pub fn report(&self, event: ProgressEvent<'_>) {
+        if let Some(output) = &self.output {
+            let event = AccountEvent { account: self.account.as_deref(), event };
+            let mut output = output.lock().unwrap();
+
+            // Failing to report progress should not affect the backup itself.
+            let result = serde_json::to_writer(&mut *output, &event)
+                .map_err(std::io::Error::from)
+                .and_then(|_| writeln!(output))
+                .and_then(|_| output.flush());
+            if let Err(err) = result {
+                debug!("Failed to report progress: {err}");
+            }
+        }
+    }
+}
+
+/// [`ProgressEvent`] along with the account it relates to, if any.
+#[derive(Debug, Serialize)]
+struct AccountEvent<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<&'a str>,
+    #[serde(flatten)]
+    event: ProgressEvent<'a>,
+}
+
+impl Debug for ProgressReporter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("enabled", &self.output.is_some())
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+#[cfg(unix)]
+fn open_fd(fd: u32) -> Result<std::fs::File> {
+    use anyhow::Context;
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(format!("/dev/fd/{fd}"))
+        .with_context(|| format!("failed to open file descriptor {fd} to report progress"))
+}
+
+#[cfg(not(unix))]
+fn open_fd(_fd: u32) -> Result<std::fs::File> {
+    Err(anyhow::anyhow!("--progress-fd is only supported on Unix"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod progress_event {
+        use super::*;
+
+        #[test]
+        fn test_serialize() {
+            let event = ProgressEvent::FileDownloaded {
+                track: "rust",
+                exercise: "poker",
+                iteration: None,
+                file: "src/lib.rs",
+                bytes: 42,
+            };
+            assert_eq!(
+                r#"{"event":"file_downloaded","track":"rust","exercise":"poker","file":"src/lib.rs","bytes":42}"#,
+                serde_json::to_string(&event).unwrap()
+            );
+
+            let event = ProgressEvent::SolutionFinished {
+                track: "rust",
+                exercise: "poker",
+                success: true,
+                elapsed_ms: 1500,
+                bytes: 42,
+            };
+            assert_eq!(
+                r#"{"event":"solution_finished","track":"rust","exercise":"poker","success":true,"elapsed_ms":1500,"bytes":42}"#,
+                serde_json::to_string(&event).unwrap()
+            );
+        }
+    }
+
+    mod account_event {
+        use super::*;
+
+        #[test]
+        fn test_serialize() {
+            let event = ProgressEvent::SolutionStarted { track: "rust", exercise: "poker" };
+            assert_eq!(
+                r#"{"account":"alice","event":"solution_started","track":"rust","exercise":"poker"}"#,
+                serde_json::to_string(&AccountEvent {
+                    account: Some("alice"),
+                    event: event.clone()
+                })
+                .unwrap()
+            );
+            assert_eq!(
+                r#"{"event":"solution_started","track":"rust","exercise":"poker"}"#,
+                serde_json::to_string(&AccountEvent { account: None, event }).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::{BufRead, IsTerminal, Write};
+
+use anyhow::Context;
+use tokio::task::spawn_blocking;
+
+use crate::Result;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OverwriteAnswer {
+    Overwrite,
+    Skip,
+    OverwriteAll,
+}
+
+impl OverwriteAnswer {
+    fn parse(answer: &str) -> Option<Self> {
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => Some(Self::Overwrite),
+            "n" | "no" | "" => Some(Self::Skip),
+            "a" | "all" => Some(Self::OverwriteAll),
+            _ => None,
+        }
+    }
+}
+
+/// Difference between a solution's local files and its files on Exercism, shown to the user
+/// before asking whether to overwrite the solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// File exists on Exercism, but not locally.
+    Added(String),
+
+    /// File exists both locally and on Exercism, but with a different content.
+    Modified(String),
+
+    /// File exists locally, but not on Exercism anymore.
+    Removed(String),
+}
+
+impl FileChange {
+    fn file(&self) -> &str {
+        match self {
+            Self::Added(file) | Self::Modified(file) | Self::Removed(file) => file,
+        }
+    }
+}
+
+impl Display for FileChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let change = match self {
+            Self::Added(_) => "added",
+            Self::Modified(_) => "modified",
+            Self::Removed(_) => "removed",
+        };
+        write!(f, "{change}: {}", self.file())
+    }
+}
+
+/// Returns a summary of the given file changes, one per line, sorted by file.
+pub fn file_changes_summary(mut changes: Vec<FileChange>) -> String {
+    if changes.is_empty() {
+        return "  (no file changes)\n".into();
+    }
+
+    changes.sort_by(|a, b| a.file().cmp(b.file()));
+    changes
+        .iter()
+        .map(|change| format!("  {change}\n"))
+        .collect()
+}
+
+pub fn can_prompt() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Asks the user whether to overwrite a solution, repeating the question until a valid answer is given.
+pub async fn ask_overwrite(question: String) -> Result<OverwriteAnswer> {
+    spawn_blocking(move || {
+        let mut stdin = io::stdin().lock();
+        loop {
+            eprint!("{question} [y]es, [N]o, [a]ll: ");
+            io::stderr().flush()?;
+
+            let mut answer = String::new();
+            if stdin.read_line(&mut answer)? == 0 {
+                // stdin has been closed; assume the safest answer.
+                return Ok(OverwriteAnswer::Skip);
+            }
+            if let Some(answer) = OverwriteAnswer::parse(&answer) {
+                return Ok(answer);
+            }
+        }
+    })
+    .await
+    .with_context(|| "failed to prompt for overwrite")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod overwrite_answer {
+        use super::*;
+
+        #[test]
+        fn test_parse() {
+            assert_eq!(Some(OverwriteAnswer::Overwrite), OverwriteAnswer::parse("y\n"));
+            assert_eq!(Some(OverwriteAnswer::Overwrite), OverwriteAnswer::parse("Yes"));
+            assert_eq!(Some(OverwriteAnswer::Skip), OverwriteAnswer::parse("n"));
+            assert_eq!(Some(OverwriteAnswer::Skip), OverwriteAnswer::parse("\n"));
+            assert_eq!(Some(OverwriteAnswer::OverwriteAll), OverwriteAnswer::parse(" a "));
+            assert_eq!(None, OverwriteAnswer::parse("maybe"));
+        }
+    }
+
+    mod file_changes_summary {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            let summary = file_changes_summary(vec![
+                FileChange::Removed("src/old.rs".into()),
+                FileChange::Added("src/new.rs".into()),
+                FileChange::Modified("src/lib.rs".into()),
+            ]);
+            assert_eq!(
+                "  modified: src/lib.rs\n  added: src/new.rs\n  removed: src/old.rs\n",
+                summary
+            );
+
+            assert_eq!("  (no file changes)\n", file_changes_summary(vec![]));
+        }
+    }
+}
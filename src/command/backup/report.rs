@@ -0,0 +1,202 @@
+use std::path::Path;
+
+use anyhow::Context;
+use mini_exercism::api::v2::solution::Solution;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::error::MultiError;
+use crate::{Error, Result};
+
+/// Machine-readable report of the errors that occurred during a backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    /// Chain of errors that caused the backup to fail, from outermost to innermost.
+    pub errors: Vec<String>,
+
+    /// Tasks that failed (usually one per solution).
+    pub failures: Vec<Failure>,
+
+    /// Solutions that could not be backed up (see [`BackupArgs::retry_failed`](crate::command::backup::args::BackupArgs::retry_failed)).
+    #[serde(default)]
+    pub failed_solutions: Vec<FailedSolution>,
+}
+
+/// Solution that could not be backed up.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FailedSolution {
+    pub track: String,
+    pub exercise: String,
+    pub uuid: String,
+}
+
+impl From<&Solution> for FailedSolution {
+    fn from(solution: &Solution) -> Self {
+        Self {
+            track: solution.track.name.clone(),
+            exercise: solution.exercise.name.clone(),
+            uuid: solution.uuid.clone(),
+        }
+    }
+}
+
+/// Failure of a single task, with the failures of its own sub-tasks (if any).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Failure {
+    /// Label of the task that failed (e.g. the solution or file it was processing).
+    pub task: Option<String>,
+
+    /// Chain of errors that caused the task to fail, from outermost to innermost.
+    pub errors: Vec<String>,
+
+    /// Sub-tasks that failed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<Failure>,
+}
+
+impl ErrorReport {
+    pub fn new(error: &Error, mut failed_solutions: Vec<FailedSolution>) -> Self {
+        let Failure { errors, failures, .. } = Failure::new(None, error);
+        failed_solutions.sort_unstable();
+
+        Self { errors, failures, failed_solutions }
+    }
+
+    /// Loads a report saved by a previous backup, if it exists.
+    pub fn load(report_file_path: &Path) -> Result<Option<Self>> {
+        let report = match std::fs::read_to_string(report_file_path) {
+            Ok(report) => report,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("failed to read error report {}", report_file_path.display())
+                })
+            },
+        };
+
+        serde_json::from_str(&report)
+            .map(Some)
+            .with_context(|| format!("failed to parse error report {}", report_file_path.display()))
+    }
+
+    pub async fn save(&self, report_file_path: &Path) -> Result<()> {
+        let report = serde_json::to_string_pretty(self)
+            .with_context(|| "failed to persist error report to JSON")?;
+
+        fs::write(report_file_path, report).await.with_context(|| {
+            format!("failed to save error report to {}", report_file_path.display())
+        })
+    }
+
+    pub async fn remove(report_file_path: &Path) -> Result<()> {
+        match fs::remove_file(report_file_path).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err).with_context(|| {
+                format!("failed to remove stale error report {}", report_file_path.display())
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Failure {
+    fn new(task: Option<String>, error: &Error) -> Self {
+        let mut errors = Vec::new();
+        let mut failures = Vec::new();
+
+        // The chain stops at the first `MultiError`, since its own errors are reported
+        // as sub-task failures instead.
+        for cause in error.chain() {
+            if let Some(multi_error) = cause.downcast_ref::<MultiError>() {
+                failures = multi_error
+                    .labels()
+                    .iter()
+                    .zip(multi_error.errors())
+                    .map(|(label, error)| Self::new(label.clone(), error))
+                    .collect();
+                break;
+            }
+
+            errors.push(cause.to_string());
+        }
+
+        Self { task, errors, failures }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod error_report {
+        use anyhow::anyhow;
+
+        use super::*;
+
+        #[test]
+        fn test_new() {
+            let file_errors = vec![(
+                Some("src/lib.rs".to_string()),
+                anyhow!("boom").context("failed to save file"),
+            )];
+            let file_error = MultiError::check_labeled(file_errors, || {
+                "errors detected while backing up solution"
+            })
+            .unwrap_err();
+            let solution_errors = vec![
+                (Some("rust/poker".to_string()), file_error),
+                (Some("rust/clock".to_string()), anyhow!("oops")),
+            ];
+            let error =
+                MultiError::check_labeled(solution_errors, || "errors detected").unwrap_err();
+
+            let expected = ErrorReport {
+                errors: vec!["errors detected".into()],
+                failures: vec![
+                    Failure {
+                        task: Some("rust/poker".into()),
+                        errors: vec!["errors detected while backing up solution".into()],
+                        failures: vec![Failure {
+                            task: Some("src/lib.rs".into()),
+                            errors: vec!["failed to save file".into(), "boom".into()],
+                            failures: vec![],
+                        }],
+                    },
+                    Failure {
+                        task: Some("rust/clock".into()),
+                        errors: vec!["oops".into()],
+                        failures: vec![],
+                    },
+                ],
+                failed_solutions: vec![],
+            };
+            assert_eq!(expected, ErrorReport::new(&error, vec![]));
+        }
+
+        #[test]
+        fn test_save_and_load() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let report_file_path = temp_dir.path().join("errors.json");
+            assert!(ErrorReport::load(&report_file_path).unwrap().is_none());
+
+            let failed_solution = |exercise: &str| FailedSolution {
+                track: "rust".into(),
+                exercise: exercise.into(),
+                uuid: format!("{exercise}-uuid"),
+            };
+            let report = ErrorReport::new(
+                &anyhow!("errors detected"),
+                vec![failed_solution("poker"), failed_solution("clock")],
+            );
+            assert_eq!(
+                vec![failed_solution("clock"), failed_solution("poker")],
+                report.failed_solutions
+            );
+
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(report.save(&report_file_path))
+                .unwrap();
+            assert_eq!(Some(report), ErrorReport::load(&report_file_path).unwrap());
+        }
+    }
+}
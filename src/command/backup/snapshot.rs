@@ -0,0 +1,308 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use regex::Regex;
+use tokio::fs;
+use tracing::{debug, info};
+
+use crate::command::backup::immutable::set_dirs_writable;
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::Result;
+
+/// Name of the directory, in the `.auxiliaire` directory of a backup directory, where previous
+/// versions of solutions are kept when using [`OverwritePolicy::Backup`].
+///
+/// [`OverwritePolicy::Backup`]: crate::command::backup::args::OverwritePolicy::Backup
+pub const SOLUTION_SNAPSHOTS_DIR_NAME: &str = "snapshots";
+
+/// Returns the path of the directory where snapshots of the previous versions of the solution
+/// stored in `track/exercise` are kept, in the given backup directory.
+///
+/// Snapshots are stored in a hidden directory so that they are not mistaken for solutions
+/// by the commands that list the content of a backup directory.
+pub fn solution_snapshots_path(output_path: &Path, track: &str, exercise: &str) -> PathBuf {
+    [
+        output_path,
+        Path::new(AUXILIAIRE_STATE_DIR_NAME),
+        Path::new(SOLUTION_SNAPSHOTS_DIR_NAME),
+        Path::new(track),
+        Path::new(exercise),
+    ]
+    .iter()
+    .collect()
+}
+
+/// Returns the path of the directory where solutions should be stored when backing up
+/// in snapshot mode in the given root directory.
+///
+/// A new snapshot directory named after the current time is returned, except in dry-run mode,
+/// in which case the latest existing snapshot is used so that the backup plan reflects what
+/// would actually be downloaded (or the root directory itself, if there are no snapshots yet).
+pub fn snapshot_output_path(root_path: &Path, dry_run: bool) -> Result<PathBuf> {
+    if dry_run {
+        return Ok(list_snapshots(root_path)?
+            .pop()
+            .unwrap_or_else(|| root_path.to_path_buf()));
+    }
+
+    Ok(root_path.join(snapshot_name(SystemTime::now())))
+}
+
+/// Creates the snapshot directory at `snapshot_path`, in the given root directory.
+///
+/// Files of the latest existing snapshot are hard-linked in the new snapshot, so that solutions
+/// that did not change since then are not downloaded again and do not use more disk space.
+pub async fn create_snapshot(root_path: &Path, snapshot_path: &Path) -> Result<()> {
+    let previous = list_snapshots(root_path)?.pop();
+
+    fs::create_dir(snapshot_path)
+        .await
+        .with_context(|| format!("failed to create snapshot {}", snapshot_path.display()))?;
+
+    if let Some(previous) = previous {
+        debug!("Linking files of snapshot {} in new snapshot", previous.display());
+        hard_link_dir(&previous, snapshot_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to link files of snapshot {} in {}",
+                    previous.display(),
+                    snapshot_path.display()
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Removes the oldest snapshots in the given root directory, keeping only the `keep` latest ones.
+pub async fn prune_snapshots(root_path: &Path, keep: usize) -> Result<()> {
+    let snapshots = list_snapshots(root_path)?;
+
+    for snapshot in &snapshots[..snapshots.len().saturating_sub(keep)] {
+        // Immutable solutions' directories are read-only, which would prevent removing their files.
+        set_dirs_writable(snapshot).await?;
+        fs::remove_dir_all(snapshot)
+            .await
+            .with_context(|| format!("failed to remove old snapshot {}", snapshot.display()))?;
+        info!("Old snapshot {} removed", snapshot.display());
+    }
+
+    Ok(())
+}
+
+/// Returns the snapshots stored in the given root directory, from oldest to newest.
+pub fn list_snapshots(root_path: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(root_path) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("failed to list snapshots in {}", root_path.display()))
+        },
+    };
+
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if is_snapshot_name(&entry.file_name().to_string_lossy()) && entry.file_type()?.is_dir() {
+            snapshots.push(entry.path());
+        }
+    }
+
+    // Snapshot names are timestamps, so sorting them by name sorts them chronologically.
+    snapshots.sort_unstable();
+    Ok(snapshots)
+}
+
+/// Recreates the content of `source_path` in `destination_path`, hard-linking files.
+///
+/// Lock files are not linked, since a lock held on a solution in one snapshot should not
+/// affect other snapshots. The permissions of directories are copied, so that solutions
+/// that were made [immutable](crate::command::backup::args::BackupArgs::immutable) stay so.
+async fn hard_link_dir(source_path: &Path, destination_path: &Path) -> Result<()> {
+    let mut to_visit = vec![(source_path.to_path_buf(), destination_path.to_path_buf())];
+    let mut visited = Vec::new();
+
+    while let Some((source_dir, destination_dir)) = to_visit.pop() {
+        let mut entries = fs::read_dir(&source_dir).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            let destination = destination_dir.join(entry.file_name());
+
+            if file_type.is_dir() {
+                fs::create_dir_all(&destination).await?;
+                to_visit.push((entry.path(), destination));
+            } else if file_type.is_file() && !is_lock_file(&entry.path()) {
+                fs::hard_link(entry.path(), &destination)
+                    .await
+                    .with_context(|| format!("failed to link {}", destination.display()))?;
+            }
+        }
+
+        visited.push((source_dir, destination_dir));
+    }
+
+    // Directories are visited before their subdirectories, so copying permissions in reverse
+    // order makes sure a read-only directory is never left without its content.
+    for (source_dir, destination_dir) in visited.into_iter().rev() {
+        let permissions = fs::metadata(&source_dir).await?.permissions();
+        fs::set_permissions(&destination_dir, permissions)
+            .await
+            .with_context(|| {
+                format!("failed to change permissions of {}", destination_dir.display())
+            })?;
+    }
+
+    Ok(())
+}
+
+fn is_lock_file(file_path: &Path) -> bool {
+    file_path.extension().is_some_and(|ext| ext == "lock")
+        && file_path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|name| name == AUXILIAIRE_STATE_DIR_NAME)
+}
+
+fn is_snapshot_name(name: &str) -> bool {
+    static SNAPSHOT_NAME_REGEX: OnceLock<Regex> = OnceLock::new();
+
+    SNAPSHOT_NAME_REGEX
+        .get_or_init(|| Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{6}Z$").unwrap())
+        .is_match(name)
+}
+
+/// Returns the name of the snapshot taken at the given time, in the form `YYYY-MM-DDTHHMMSSZ` (UTC).
+fn snapshot_name(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Converts days since the Unix epoch to a civil date (see http://howardhinnant.github.io/date_algorithms.html).
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}{:02}{:02}Z",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::command::backup::immutable::{is_immutable, set_immutable};
+
+    #[test]
+    fn test_snapshot_name() {
+        assert_eq!("1970-01-01T000000Z", snapshot_name(UNIX_EPOCH));
+        assert_eq!(
+            "2023-05-07T053543Z",
+            snapshot_name(UNIX_EPOCH + Duration::from_secs(1_683_437_743))
+        );
+        assert_eq!(
+            "2024-02-29T235959Z",
+            snapshot_name(UNIX_EPOCH + Duration::from_secs(1_709_251_199))
+        );
+        assert!(is_snapshot_name(&snapshot_name(SystemTime::now())));
+        assert!(!is_snapshot_name("rust"));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_prune() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root_path = temp_dir.path();
+        let first = root_path.join("2024-01-01T000000Z");
+        let second = root_path.join("2024-01-02T000000Z");
+        let third = root_path.join("2024-01-03T000000Z");
+
+        create_snapshot(root_path, &first).await.unwrap();
+        fs::create_dir_all(first.join("rust/poker/.auxiliaire"))
+            .await
+            .unwrap();
+        fs::write(first.join("rust/poker/lib.rs"), "foo")
+            .await
+            .unwrap();
+        fs::create_dir_all(first.join("rust/.auxiliaire"))
+            .await
+            .unwrap();
+        fs::write(first.join("rust/.auxiliaire/poker.lock"), "")
+            .await
+            .unwrap();
+
+        create_snapshot(root_path, &second).await.unwrap();
+        assert_eq!(
+            "foo",
+            fs::read_to_string(second.join("rust/poker/lib.rs"))
+                .await
+                .unwrap()
+        );
+        assert!(second.join("rust/poker/.auxiliaire").is_dir());
+        assert!(!second.join("rust/.auxiliaire/poker.lock").exists());
+
+        create_snapshot(root_path, &third).await.unwrap();
+        fs::create_dir(root_path.join("not-a-snapshot"))
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![first.clone(), second.clone(), third.clone()],
+            list_snapshots(root_path).unwrap()
+        );
+
+        prune_snapshots(root_path, 2).await.unwrap();
+        assert_eq!(vec![second, third.clone()], list_snapshots(root_path).unwrap());
+        assert_eq!(
+            "foo",
+            fs::read_to_string(third.join("rust/poker/lib.rs"))
+                .await
+                .unwrap()
+        );
+        assert!(root_path.join("not-a-snapshot").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_immutable_solutions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root_path = temp_dir.path();
+        let first = root_path.join("2024-01-01T000000Z");
+        let second = root_path.join("2024-01-02T000000Z");
+
+        create_snapshot(root_path, &first).await.unwrap();
+        fs::create_dir_all(first.join("rust/poker")).await.unwrap();
+        fs::write(first.join("rust/poker/lib.rs"), "foo")
+            .await
+            .unwrap();
+        set_immutable(&first.join("rust/poker"), true)
+            .await
+            .unwrap();
+
+        create_snapshot(root_path, &second).await.unwrap();
+        assert!(is_immutable(&second.join("rust/poker")).await);
+        assert!(!is_immutable(&second.join("rust")).await);
+
+        prune_snapshots(root_path, 1).await.unwrap();
+        assert_eq!(vec![second.clone()], list_snapshots(root_path).unwrap());
+        assert!(is_immutable(&second.join("rust/poker/lib.rs")).await);
+
+        set_immutable(&second.join("rust/poker"), false)
+            .await
+            .unwrap();
+    }
+}
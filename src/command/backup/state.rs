@@ -1,10 +1,12 @@
 use std::path::Path;
 
 use anyhow::anyhow;
-use mini_exercism::api::v2::solution::Solution;
+use mini_exercism::api::v2::solution;
+use mini_exercism::api::v2::solution::{MentoringStatus, Solution};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 
+use crate::command::backup::args::SolutionStatus;
 use crate::Result;
 
 pub const AUXILIAIRE_STATE_DIR_NAME: &str = ".auxiliaire";
@@ -15,6 +17,18 @@ pub const BACKUP_STATE_TEMP_FILE_NAME: &str = ".auxiliaire/backup_state.json.tmp
 pub struct BackupState {
     pub uuid: String,
     pub last_iteration_marker: LastIterationMarker,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<solution::Status>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mentoring_status: Option<MentoringStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<Vec<i32>>,
 }
 
 impl BackupState {
@@ -32,22 +46,48 @@ impl BackupState {
                 .last_iterated_at
                 .map(Into::into)
                 .unwrap_or_else(|| solution.num_iterations.into()),
+            private_url: Some(solution.private_url),
+            public_url: Some(solution.public_url),
+            // Unknown status cannot be serialized, so there's no point in storing it.
+            status: Some(solution.status).filter(|status| *status != solution::Status::Unknown),
+            mentoring_status: Some(solution.mentoring_status)
+                .filter(|status| *status != MentoringStatus::Unknown),
+            ..Self::default()
         }
     }
 
+    /// Removes information identifying the solution's author, like the solution's public URL
+    /// (see [`BackupArgs::anonymize`](crate::command::backup::args::BackupArgs::anonymize)).
+    ///
+    /// The solution's UUID is kept, since it is needed to check if the solution needs to be updated.
+    pub fn anonymize(&mut self) {
+        self.public_url = None;
+    }
+
     pub async fn for_backup(solution: &Solution, solution_output_path: &Path) -> Self {
+        Self::load(solution_output_path)
+            .await
+            .unwrap_or_else(|| Self::for_solution_uuid(&solution.uuid))
+    }
+
+    pub async fn load(solution_output_path: &Path) -> Option<Self> {
         let mut state_file_path = solution_output_path.to_path_buf();
         state_file_path.push(BACKUP_STATE_FILE_NAME);
 
-        fs::read_to_string(state_file_path)
-            .await
-            .map_err(|_| ())
-            .and_then(|state_str| {
-                serde_json::from_str::<PersistedBackupState>(&state_str)
-                    .map(PersistedBackupState::revise)
-                    .map_err(|_| ())
-            })
-            .unwrap_or_else(|_| Self::for_solution_uuid(&solution.uuid))
+        let state_str = fs::read_to_string(state_file_path).await.ok()?;
+        serde_json::from_str::<PersistedBackupState>(&state_str)
+            .map(PersistedBackupState::revise)
+            .ok()
+    }
+
+    /// Returns the solution's last seen status if the solution's current status is lower,
+    /// which usually indicates that we're not looking at the same solution.
+    pub fn status_regression(&self, solution: &Solution) -> Option<solution::Status> {
+        let last_status = self.status?;
+        match (SolutionStatus::try_from(last_status), SolutionStatus::try_from(solution.status)) {
+            (Ok(last), Ok(current)) if current < last => Some(last_status),
+            _ => None,
+        }
     }
 
     pub fn needs_update(&self, solution: &Solution) -> Result<bool> {
@@ -120,6 +160,7 @@ impl From<V1BackupState> for BackupState {
         Self {
             uuid: value.uuid,
             last_iteration_marker: value.iterations.last().copied().unwrap_or(0).into(),
+            ..BackupState::default()
         }
     }
 }
@@ -212,11 +253,26 @@ mod tests {
                     assert_matches!(&state.last_iteration_marker, LastIterationMarker::LastIteratedAt(state_lia) => {
                         assert_eq!(solution.last_iterated_at.as_ref(), Some(state_lia));
                     });
+                    assert_eq!(Some(&solution.private_url), state.private_url.as_ref());
+                    assert_eq!(Some(&solution.public_url), state.public_url.as_ref());
+                    assert_eq!(Some(solution.status), state.status);
+                    assert_eq!(Some(solution.mentoring_status), state.mentoring_status);
                     assert_matches!(state.needs_update(&solution), Ok(false));
 
                     solution.last_iterated_at = Some("2024-05-07T05:35:43Z".into());
                     assert_matches!(state.needs_update(&solution), Ok(true));
                 }
+
+                #[test]
+                fn test_anonymize() {
+                    let solution = get_solution();
+                    let mut state = BackupState::for_solution(get_solution());
+                    state.anonymize();
+
+                    assert_eq!(None, state.public_url);
+                    assert_eq!(Some(&solution.private_url), state.private_url.as_ref());
+                    assert_matches!(state.needs_update(&solution), Ok(false));
+                }
             }
 
             mod without_last_iterated_at {
@@ -282,6 +338,59 @@ mod tests {
             }
         }
 
+        mod status_regression {
+            use super::*;
+
+            #[test]
+            fn test_all() {
+                let mut solution = get_solution();
+                let state = BackupState::for_solution(solution.clone());
+                assert_eq!(None, state.status_regression(&solution));
+
+                solution.status = solution::Status::Iterated;
+                assert_eq!(Some(solution::Status::Published), state.status_regression(&solution));
+
+                solution.status = solution::Status::Unknown;
+                assert_eq!(None, state.status_regression(&solution));
+
+                let state = BackupState::for_solution_uuid(&solution.uuid);
+                solution.status = solution::Status::Started;
+                assert_eq!(None, state.status_regression(&solution));
+            }
+        }
+
+        mod cache {
+            use super::*;
+
+            #[test]
+            fn test_round_trip() {
+                let state = BackupState {
+                    files: Some(vec!["src/lib.rs".into(), "Cargo.toml".into()]),
+                    iterations: Some(vec![1, 3]),
+                    ..BackupState::for_solution(get_solution())
+                };
+
+                let json = serde_json::to_string(&state).unwrap();
+                let state: BackupState = serde_json::from_str(&json).unwrap();
+                assert_eq!(Some(vec!["src/lib.rs".into(), "Cargo.toml".into()]), state.files);
+                assert_eq!(Some(vec![1, 3]), state.iterations);
+            }
+
+            #[test]
+            fn test_missing() {
+                let json = r#"{
+                    "uuid": "00c717b68e1b4213b316df82636f5e0f",
+                    "last_iteration_marker": {
+                        "last_iterated_at": "2023-05-07T05:35:43Z"
+                    }
+                }"#;
+
+                let state: BackupState = serde_json::from_str(json).unwrap();
+                assert!(state.files.is_none());
+                assert!(state.iterations.is_none());
+            }
+        }
+
         mod for_backup {
             use std::path::PathBuf;
 
@@ -343,6 +452,19 @@ mod tests {
             with_backup_state_tests!(with_backup_state);
             with_backup_state_tests!(with_v1_backup_state);
 
+            #[tokio::test]
+            async fn test_load() {
+                let solution = get_solution();
+
+                let state = BackupState::load(&test_manifest_path("with_backup_state")).await;
+                assert_matches!(state, Some(state) => {
+                    assert_eq!(solution.uuid, state.uuid);
+                });
+
+                let state = BackupState::load(&test_manifest_path("without_backup_state")).await;
+                assert!(state.is_none());
+            }
+
             #[tokio::test]
             async fn test_without_backup_state() {
                 let solution = get_solution();
@@ -0,0 +1,86 @@
+use mini_exercism::api::v1;
+use serde::{Deserialize, Serialize};
+
+pub const EXERCISM_METADATA_FILE_NAME: &str = ".exercism/metadata.json";
+
+/// Solution metadata, as stored by the Exercism CLI in an exercise's directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExercismMetadata {
+    pub track: String,
+    pub exercise: String,
+    pub id: String,
+    pub url: String,
+    pub handle: String,
+    pub is_requester: bool,
+    pub auto_approve: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitted_at: Option<String>,
+}
+
+impl ExercismMetadata {
+    /// Removes information identifying the solution's author, like their handle
+    /// (see [`BackupArgs::anonymize`](crate::command::backup::args::BackupArgs::anonymize)).
+    pub fn anonymize(&mut self) {
+        self.handle.clear();
+    }
+}
+
+impl From<v1::solution::Solution> for ExercismMetadata {
+    fn from(value: v1::solution::Solution) -> Self {
+        Self {
+            track: value.exercise.track.name,
+            exercise: value.exercise.name,
+            id: value.uuid,
+            url: value.url,
+            handle: value.user.handle,
+            is_requester: value.user.is_requester,
+            auto_approve: false,
+            submitted_at: value.submission.map(|submission| submission.submitted_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_v1_solution() {
+        let json = r#"{
+            "id": "00c717b68e1b4213b316df82636f5e0f",
+            "url": "https://exercism.org/tracks/rust/exercises/poker",
+            "user": {
+                "handle": "clechasseur",
+                "is_requester": true
+            },
+            "exercise": {
+                "id": "poker",
+                "instructions_url": "https://exercism.org/tracks/rust/exercises/poker",
+                "track": {
+                    "id": "rust",
+                    "language": "Rust"
+                }
+            },
+            "file_download_base_url": "https://exercism.org/api/v1/solutions/00c717b68e1b4213b316df82636f5e0f/files/",
+            "files": [".exercism/config.json", "src/lib.rs", "Cargo.toml"],
+            "submission": {
+                "submitted_at": "2023-05-07T05:35:43.184Z"
+            }
+        }"#;
+        let solution: v1::solution::Solution = serde_json::from_str(json).unwrap();
+
+        let mut metadata = ExercismMetadata::from(solution);
+        assert_eq!("rust", metadata.track);
+        assert_eq!("poker", metadata.exercise);
+        assert_eq!("00c717b68e1b4213b316df82636f5e0f", metadata.id);
+        assert_eq!("https://exercism.org/tracks/rust/exercises/poker", metadata.url);
+        assert_eq!("clechasseur", metadata.handle);
+        assert!(metadata.is_requester);
+        assert!(!metadata.auto_approve);
+        assert_eq!(Some("2023-05-07T05:35:43.184Z"), metadata.submitted_at.as_deref());
+
+        metadata.anonymize();
+        assert_eq!("", metadata.handle);
+        assert_eq!("https://exercism.org/tracks/rust/exercises/poker", metadata.url);
+    }
+}
@@ -0,0 +1,35 @@
+//! Definition of the [`Cache`](crate::command::Command::Cache) command.
+
+pub mod args;
+
+use tracing::{info, instrument};
+
+use crate::cache::ResponseCache;
+use crate::command::cache::args::{CacheAction, CacheArgs};
+use crate::Result;
+
+/// Command wrapper used for the [`Cache`](crate::command::Command::Cache) command.
+#[derive(Debug)]
+pub struct CacheCommand {
+    args: CacheArgs,
+}
+
+impl CacheCommand {
+    /// Creates a new [`CacheCommand`] using the provided [`args`](CacheArgs).
+    pub fn new(args: CacheArgs) -> Self {
+        Self { args }
+    }
+
+    /// Perform the requested [cache operation](CacheArgs::action).
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        match &self.args.action {
+            CacheAction::Clear { cache_dir } => {
+                ResponseCache::clear(cache_dir).await?;
+                info!("Cached responses removed from {}", cache_dir.display());
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+//! Arguments that can be passed to the [`Cache`](crate::command::Command::Cache) command.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+/// Command-line arguments accepted by the [`Cache`](crate::command::Command::Cache) command.
+#[derive(Debug, Clone, Args)]
+pub struct CacheArgs {
+    /// Cache operation to perform
+    #[command(subcommand)]
+    pub action: CacheAction,
+}
+
+/// Possible operations on the cache of Exercism API responses (see [`CacheArgs::action`]).
+#[derive(Debug, Clone, Subcommand)]
+pub enum CacheAction {
+    /// Remove all cached responses
+    Clear {
+        /// Directory where Exercism API responses are cached
+        #[arg(long, env = "AUXILIAIRE_CACHE_DIR")]
+        cache_dir: PathBuf,
+    },
+}
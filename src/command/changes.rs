@@ -0,0 +1,360 @@
+//! Definition of the [`Changes`](crate::command::Command::Changes) command.
+
+pub mod args;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+use anyhow::Context;
+use mini_exercism::api;
+use mini_exercism::api::v2::solution;
+use mini_exercism::api::v2::solution::{MentoringStatus, Solution};
+use mini_exercism::api::v2::solutions;
+use serde::Serialize;
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::state::{BackupState, LastIterationMarker};
+use crate::command::changes::args::{ChangesArgs, ChangesFormat};
+use crate::command::get_credentials;
+use crate::style::NAME;
+use crate::Result;
+
+/// Command wrapper used for the [`Changes`](crate::command::Command::Changes) command.
+#[derive(Debug)]
+pub struct ChangesCommand {
+    args: ChangesArgs,
+    v2_client: api::v2::Client,
+}
+
+impl ChangesCommand {
+    /// Creates a new [`ChangesCommand`] using the provided [`args`](ChangesArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: ChangesArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let credentials = get_credentials(args.token.as_deref(), args.token_command.as_deref())?;
+
+        let mut builder = api::v2::Client::builder();
+        builder.credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v2_client = builder.build()?;
+
+        Ok(Self { args, v2_client })
+    }
+
+    /// Report what changed on Exercism.org since solutions were last backed up.
+    ///
+    /// Solutions on Exercism.org are compared with the backup state stored in the backup directory;
+    /// nothing is downloaded.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        let changes = self.get_changes().await?;
+
+        if changes.is_empty() {
+            info!("No changes since last backup");
+            return Ok(());
+        }
+
+        write_changes(&mut anstream::stdout(), self.args.format, &changes)
+    }
+
+    async fn get_changes(&self) -> Result<BTreeMap<(String, String), Vec<Change>>> {
+        let mut changes = BTreeMap::new();
+
+        for solution in self.get_remote_solutions().await? {
+            let solution_path = self
+                .args
+                .path
+                .join(&solution.track.name)
+                .join(&solution.exercise.name);
+            let state = BackupState::load(&solution_path).await;
+            trace!(solution.track.name, solution.exercise.name, ?state);
+
+            let solution_changes = Change::between(state.as_ref(), &solution);
+            if !solution_changes.is_empty() {
+                changes.insert((solution.track.name, solution.exercise.name), solution_changes);
+            }
+        }
+
+        Ok(changes)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn get_remote_solutions(&self) -> Result<Vec<Solution>> {
+        let mut solutions = Vec::new();
+
+        let mut page = 1;
+        loop {
+            let response = self
+                .v2_client
+                .get_solutions(None, Some(solutions::Paging::for_page(page)), None)
+                .await
+                .with_context(|| format!("failed to fetch solutions for page {page}"))?;
+
+            solutions.extend(response.results.into_iter().filter(|solution| {
+                self.args
+                    .solution_matches(&solution.track.name, &solution.exercise.name)
+            }));
+
+            if response.meta.current_page >= response.meta.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(solutions)
+    }
+}
+
+/// Change to a solution since it was last backed up, as reported by the
+/// [`Changes`](crate::command::Command::Changes) command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum Change {
+    /// Solution has never been backed up.
+    NewSolution { status: solution::Status },
+
+    /// New iterations have been submitted.
+    NewIterations {
+        num_iterations: i32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_iterated_at: Option<String>,
+    },
+
+    /// Solution's status changed (for example, it was published).
+    StatusChanged { from: solution::Status, to: solution::Status },
+
+    /// Solution's mentoring status changed (for example, mentoring was requested).
+    MentoringStatusChanged { from: MentoringStatus, to: MentoringStatus },
+}
+
+impl Change {
+    /// Returns the changes to the given solution since the given backup state was saved.
+    fn between(state: Option<&BackupState>, solution: &Solution) -> Vec<Self> {
+        let Some(state) = state else {
+            return vec![Self::NewSolution { status: solution.status }];
+        };
+
+        let mut changes = Vec::new();
+
+        let new_iterations = match (&state.last_iteration_marker, &solution.last_iterated_at) {
+            (LastIterationMarker::LastIteratedAt(local), Some(remote)) => local != remote,
+            (LastIterationMarker::NumIterations(local), _) => *local < solution.num_iterations,
+            _ => false,
+        };
+        if new_iterations {
+            changes.push(Self::NewIterations {
+                num_iterations: solution.num_iterations,
+                last_iterated_at: solution.last_iterated_at.clone(),
+            });
+        }
+
+        // States saved by older versions did not record statuses, so we can't tell if they changed.
+        match state.status {
+            Some(status)
+                if solution.status != solution::Status::Unknown && status != solution.status =>
+            {
+                changes.push(Self::StatusChanged { from: status, to: solution.status });
+            },
+            _ => (),
+        }
+        match state.mentoring_status {
+            Some(mentoring_status)
+                if solution.mentoring_status != MentoringStatus::Unknown
+                    && mentoring_status != solution.mentoring_status =>
+            {
+                changes.push(Self::MentoringStatusChanged {
+                    from: mentoring_status,
+                    to: solution.mentoring_status,
+                });
+            },
+            _ => (),
+        }
+
+        changes
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NewSolution { status } => write!(f, "new solution ({status})"),
+            Self::NewIterations { num_iterations, last_iterated_at: Some(last_iterated_at) } => {
+                write!(
+                    f,
+                    "new iteration(s) ({num_iterations} in total, last iterated at {last_iterated_at})"
+                )
+            },
+            Self::NewIterations { num_iterations, last_iterated_at: None } => {
+                write!(f, "new iteration(s) ({num_iterations} in total)")
+            },
+            Self::StatusChanged { to: solution::Status::Published, .. } => write!(f, "published"),
+            Self::StatusChanged { from, to } => write!(f, "status changed from {from} to {to}"),
+            Self::MentoringStatusChanged { from, to } => {
+                write!(f, "mentoring status changed from {from} to {to}")
+            },
+        }
+    }
+}
+
+/// Change to a solution, as output in [JSON Lines](ChangesFormat::Jsonl) format.
+#[derive(Debug, Serialize)]
+struct SolutionChange<'a> {
+    track: &'a str,
+    exercise: &'a str,
+    #[serde(flatten)]
+    change: &'a Change,
+}
+
+fn write_changes<W: Write>(
+    output: &mut W,
+    format: ChangesFormat,
+    changes: &BTreeMap<(String, String), Vec<Change>>,
+) -> Result<()> {
+    for ((track, exercise), solution_changes) in changes {
+        for change in solution_changes {
+            match format {
+                ChangesFormat::Text => {
+                    writeln!(output, "{NAME}{track}/{exercise}{NAME:#}: {change}")?;
+                },
+                ChangesFormat::Jsonl => {
+                    serde_json::to_writer(
+                        &mut *output,
+                        &SolutionChange { track, exercise, change },
+                    )
+                    .with_context(|| {
+                        format!("failed to serialize change to solution to {track}/{exercise}")
+                    })?;
+                    writeln!(output)?;
+                },
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+    use tokio::fs;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::test_fixtures;
+
+    fn solution_json(exercise: &str, status: &str, mentoring_status: &str) -> serde_json::Value {
+        test_fixtures::solution_json(
+            "rust",
+            exercise,
+            json!({
+                "uuid": format!("{exercise}-uuid"),
+                "status": status,
+                "mentoring_status": mentoring_status,
+                "num_iterations": 2,
+                "updated_at": "2023-05-08T05:35:43Z",
+                "last_iterated_at": "2023-05-08T05:35:43Z",
+            }),
+        )
+    }
+
+    async fn save_state(backup_path: &std::path::Path, exercise: &str, state: serde_json::Value) {
+        let state_dir_path = backup_path.join("rust").join(exercise).join(".auxiliaire");
+        fs::create_dir_all(&state_dir_path).await.unwrap();
+        fs::write(state_dir_path.join("backup_state.json"), state.to_string())
+            .await
+            .unwrap();
+    }
+
+    #[test(tokio::test)]
+    async fn test_get_changes() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method(Method::GET))
+            .and(path("/solutions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [
+                    solution_json("clock", "iterated", "none"),
+                    solution_json("poker", "published", "requested"),
+                    solution_json("robot", "iterated", "none"),
+                ],
+                "meta": { "current_page": 1, "total_count": 3, "total_pages": 1 },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let backup_path = temp_dir.path();
+        save_state(
+            backup_path,
+            "poker",
+            json!({
+                "uuid": "poker-uuid",
+                "last_iteration_marker": { "last_iterated_at": "2023-05-07T05:35:43Z" },
+                "status": "completed",
+                "mentoring_status": "none",
+            }),
+        )
+        .await;
+        save_state(
+            backup_path,
+            "robot",
+            json!({
+                "uuid": "robot-uuid",
+                "last_iteration_marker": { "last_iterated_at": "2023-05-08T05:35:43Z" },
+                "status": "iterated",
+                "mentoring_status": "none",
+            }),
+        )
+        .await;
+
+        let args = ChangesArgs {
+            path: backup_path.into(),
+            token: Some("some_token".into()),
+            token_command: None,
+            track: vec![],
+            exercise: vec![],
+            format: ChangesFormat::Jsonl,
+        };
+        let command = ChangesCommand::new(args, Some(&mock_server.uri())).unwrap();
+        let changes = command.get_changes().await.unwrap();
+
+        assert_eq!(
+            vec![("rust".to_string(), "clock".to_string()), ("rust".into(), "poker".into())],
+            changes.keys().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Change::NewSolution { status: solution::Status::Iterated }],
+            changes[&("rust".into(), "clock".into())]
+        );
+        assert_eq!(
+            vec![
+                Change::NewIterations {
+                    num_iterations: 2,
+                    last_iterated_at: Some("2023-05-08T05:35:43Z".into())
+                },
+                Change::StatusChanged {
+                    from: solution::Status::Completed,
+                    to: solution::Status::Published
+                },
+                Change::MentoringStatusChanged {
+                    from: MentoringStatus::None,
+                    to: MentoringStatus::Requested
+                },
+            ],
+            changes[&("rust".into(), "poker".into())]
+        );
+
+        let mut output = Vec::new();
+        write_changes(&mut output, ChangesFormat::Jsonl, &changes).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(4, output.lines().count());
+        assert!(output.starts_with(
+            r#"{"track":"rust","exercise":"clock","change":"new_solution","status":"iterated"}"#
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+//! Arguments that can be passed to the [`Changes`](crate::command::Command::Changes) command.
+
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+/// Command-line arguments accepted by the [`Changes`](crate::command::Command::Changes) command.
+#[derive(Debug, Clone, Args)]
+pub struct ChangesArgs {
+    /// Path where solutions have been backed up
+    #[arg(default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Only report changes to solutions in the given track(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_TRACK", value_delimiter = ',')]
+    pub track: Vec<String>,
+
+    /// Only report changes to solutions for the given exercise(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_EXERCISE", value_delimiter = ',')]
+    pub exercise: Vec<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ChangesFormat::Text, env = "AUXILIAIRE_FORMAT")]
+    pub format: ChangesFormat,
+}
+
+impl ChangesArgs {
+    /// Determines if changes to the solution for the given track and exercise should be reported.
+    pub fn solution_matches(&self, track_name: &str, exercise_name: &str) -> bool {
+        (self.track.is_empty() || self.track.iter().any(|t| t == track_name))
+            && (self.exercise.is_empty() || self.exercise.iter().any(|e| e == exercise_name))
+    }
+}
+
+/// Possible output formats for the change log (see [`ChangesArgs::format`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ChangesFormat {
+    /// Human-readable change log, one change per line
+    Text,
+
+    /// JSON Lines: one JSON object per change
+    Jsonl,
+}
@@ -0,0 +1,401 @@
+//! Definition of the [`Community`](crate::command::Command::Community) command.
+
+pub mod args;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use mini_exercism::api::v1::solution::Solution;
+use mini_exercism::stream::StreamExt;
+use mini_exercism::{api, http};
+use serde::Deserialize;
+use tokio::fs;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::command::community::args::{CommunityArgs, CommunitySolutionId};
+use crate::command::get_credentials;
+use crate::Result;
+
+/// Name of the directory, in a backup directory, where community solutions are stored.
+pub const COMMUNITY_DIR_NAME: &str = "_community";
+
+/// Name of the file storing a community solution's metadata (in its `.auxiliaire` directory).
+pub const COMMUNITY_METADATA_FILE_NAME: &str = "community_solution.json";
+
+/// Command wrapper used for the [`Community`](crate::command::Command::Community) command.
+#[derive(Debug)]
+pub struct CommunityCommand {
+    args: CommunityArgs,
+    http_client: http::Client,
+    v1_client: api::v1::Client,
+    v2_api_base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommunitySolutionsResponse {
+    results: Vec<CommunitySolution>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommunitySolution {
+    uuid: String,
+    author: CommunitySolutionAuthor,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommunitySolutionAuthor {
+    handle: String,
+}
+
+impl CommunityCommand {
+    /// Creates a new [`CommunityCommand`] using the provided [`args`](CommunityArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: CommunityArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let credentials = get_credentials(args.token.as_deref(), args.token_command.as_deref())?;
+        let http_client = http::Client::builder()
+            .build()
+            .with_context(|| "failed to create HTTP client")?;
+
+        let mut builder = api::v1::Client::builder();
+        builder
+            .http_client(http_client.clone())
+            .credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v1_client = builder.build()?;
+        let v2_api_base_url = api_base_url
+            .unwrap_or(api::v2::DEFAULT_V2_API_BASE_URL)
+            .to_string();
+
+        Ok(Self { args, http_client, v1_client, v2_api_base_url })
+    }
+
+    /// Download the published solution.
+    ///
+    /// The solution's files are stored in the backup directory under
+    /// `_community/<track>/<exercise>/<handle>`, along with the solution's metadata.
+    #[instrument(skip_all, fields(solution = %self.args.solution))]
+    pub async fn execute(&self) -> Result<()> {
+        let uuid = self.solution_uuid().await?;
+        let solution = self
+            .v1_client
+            .get_solution(&uuid)
+            .await
+            .with_context(|| format!("failed to get solution {}", self.args.solution))?
+            .solution;
+        trace!(?solution);
+
+        // Since the solution comes from another user, make sure its files cannot be written
+        // outside of its directory before downloading anything.
+        let solution_path = community_solution_path(&self.args.path, &solution)?;
+        if let Some(file) = solution.files.iter().find(|file| !is_safe_path(file)) {
+            return Err(anyhow!("invalid file name {file:?} in solution {}", self.args.solution));
+        }
+        for file in &solution.files {
+            self.download_file(&solution, file, &solution_path).await?;
+        }
+        self.save_metadata(&solution, &solution_path).await?;
+
+        info!(
+            "Solution to {}/{} by {} downloaded to {}",
+            solution.exercise.track.name,
+            solution.exercise.name,
+            solution.user.handle,
+            solution_path.display()
+        );
+        Ok(())
+    }
+
+    async fn solution_uuid(&self) -> Result<String> {
+        let (track, exercise, handle) = match &self.args.solution {
+            CommunitySolutionId::Uuid(uuid) => return Ok(uuid.clone()),
+            CommunitySolutionId::Url { track, exercise, handle } => (track, exercise, handle),
+        };
+
+        let url = format!(
+            "{}/tracks/{track}/exercises/{exercise}/community_solutions",
+            self.v2_api_base_url
+        );
+        let response: CommunitySolutionsResponse = self
+            .http_client
+            .get(url)
+            .query(&[("criteria", handle.as_str())])
+            .send()
+            .await
+            .and_then(http::Response::error_for_status)
+            .with_context(|| format!("failed to look up solution {}", self.args.solution))?
+            .json()
+            .await
+            .with_context(|| format!("failed to parse solutions to {track}/{exercise}"))?;
+
+        response
+            .results
+            .into_iter()
+            .find(|solution| solution.author.handle.eq_ignore_ascii_case(handle))
+            .map(|solution| solution.uuid)
+            .ok_or_else(|| anyhow!("published solution {} not found", self.args.solution))
+    }
+
+    #[instrument(level = "trace", skip(self, solution, solution_path))]
+    async fn download_file(
+        &self,
+        solution: &Solution,
+        file: &str,
+        solution_path: &Path,
+    ) -> Result<()> {
+        let mut destination_path = solution_path.to_path_buf();
+        destination_path.extend(file.split('/'));
+        trace!(destination_path = %destination_path.display());
+
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("failed to create directory {}", parent.display()))?;
+        }
+
+        let mut file_stream = self.v1_client.get_file(&solution.uuid, file).await;
+        let destination_file = fs::File::create(&destination_path)
+            .await
+            .with_context(|| format!("failed to create file {}", destination_path.display()))?;
+        let mut destination_file = BufWriter::new(destination_file);
+        while let Some(bytes) = file_stream.next().await {
+            let bytes = bytes.with_context(|| {
+                format!("failed to download file {file} in solution {}", self.args.solution)
+            })?;
+            destination_file.write_all(&bytes).await?;
+        }
+        destination_file.flush().await?;
+
+        Ok(())
+    }
+
+    async fn save_metadata(&self, solution: &Solution, solution_path: &Path) -> Result<()> {
+        let state_dir_path = solution_path.join(AUXILIAIRE_STATE_DIR_NAME);
+        fs::create_dir_all(&state_dir_path)
+            .await
+            .with_context(|| format!("failed to create directory {}", state_dir_path.display()))?;
+
+        let metadata_file_path = state_dir_path.join(COMMUNITY_METADATA_FILE_NAME);
+        let metadata = serde_json::to_string_pretty(solution)
+            .with_context(|| "failed to persist community solution metadata to JSON")?;
+        fs::write(&metadata_file_path, metadata)
+            .await
+            .with_context(|| {
+                format!("failed to save solution metadata to {}", metadata_file_path.display())
+            })
+    }
+}
+
+/// Returns the path where the given community solution is stored in a backup directory.
+///
+/// Fails if the track, exercise or user handle of the solution cannot be safely used as
+/// a directory name (see [`is_safe_path`]).
+pub fn community_solution_path(backup_path: &Path, solution: &Solution) -> Result<PathBuf> {
+    let segments = [&solution.exercise.track.name, &solution.exercise.name, &solution.user.handle];
+    if let Some(segment) = segments.iter().find(|segment| !is_safe_segment(segment)) {
+        return Err(anyhow!("invalid path component {segment:?} in solution {}", solution.uuid));
+    }
+
+    let mut solution_path = backup_path.join(COMMUNITY_DIR_NAME);
+    solution_path.extend(segments);
+    Ok(solution_path)
+}
+
+/// Checks whether the given `/`-separated relative path only contains
+/// [safe segments](is_safe_segment), so that it cannot point outside of the directory
+/// it is appended to.
+fn is_safe_path(path: &str) -> bool {
+    path.split('/').all(is_safe_segment)
+}
+
+/// Checks whether the given path segment is a plain file or directory name, e.g. not empty,
+/// `.`, `..` or something that could be interpreted as a path on its own (like `C:`).
+fn is_safe_segment(segment: &str) -> bool {
+    !segment.is_empty() && segment != "." && segment != ".." && !segment.contains(['\\', ':', '\0'])
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    const SOLUTION_UUID: &str = "5b2f5e8a3b6f4c1e9a7d0c4e2f1b3a6d";
+
+    fn solution_json(mock_server: &MockServer, handle: &str, files: &[&str]) -> serde_json::Value {
+        json!({
+            "solution": {
+                "id": SOLUTION_UUID,
+                "url": format!("https://exercism.org/tracks/rust/exercises/poker/solutions/{handle}"),
+                "user": { "handle": handle, "is_requester": false },
+                "exercise": {
+                    "id": "poker",
+                    "instructions_url": "https://exercism.org/tracks/rust/exercises/poker",
+                    "track": { "id": "rust", "language": "Rust" },
+                },
+                "file_download_base_url": format!("{}/solutions/{SOLUTION_UUID}/files/", mock_server.uri()),
+                "files": files,
+                "submission": { "submitted_at": "2023-05-07T05:35:43Z" },
+            },
+        })
+    }
+
+    async fn get_mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method(Method::GET))
+            .and(path("/tracks/rust/exercises/poker/community_solutions"))
+            .and(query_param("criteria", "someone"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [
+                    { "uuid": "other-uuid", "author": { "handle": "someone_else" } },
+                    { "uuid": SOLUTION_UUID, "author": { "handle": "someone" } },
+                ],
+                "meta": { "current_page": 1, "total_count": 2, "total_pages": 1 },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method(Method::GET))
+            .and(path(format!("/solutions/{SOLUTION_UUID}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(solution_json(
+                &mock_server,
+                "someone",
+                &["src/lib.rs", "Cargo.toml"],
+            )))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method(Method::GET))
+            .and(path(format!("/solutions/{SOLUTION_UUID}/files/src/lib.rs")))
+            .respond_with(ResponseTemplate::new(200).set_body_string("pub fn poker() {}"))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method(Method::GET))
+            .and(path(format!("/solutions/{SOLUTION_UUID}/files/Cargo.toml")))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[package]"))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    #[test(tokio::test)]
+    async fn test_execute() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mock_server = get_mock_server().await;
+
+        for solution in
+            ["https://exercism.org/tracks/rust/exercises/poker/solutions/someone", SOLUTION_UUID]
+        {
+            let command = CommunityCommand::new(
+                CommunityArgs {
+                    solution: solution.parse().unwrap(),
+                    path: temp_dir.path().to_path_buf(),
+                    token: Some("some_token".into()),
+                    token_command: None,
+                },
+                Some(&mock_server.uri()),
+            )
+            .unwrap();
+            command.execute().await.unwrap();
+
+            let solution_path: PathBuf =
+                [temp_dir.path(), Path::new("_community/rust/poker/someone")]
+                    .iter()
+                    .collect();
+            assert_eq!(
+                "pub fn poker() {}",
+                fs::read_to_string(solution_path.join("src").join("lib.rs"))
+                    .await
+                    .unwrap()
+            );
+            assert!(solution_path.join("Cargo.toml").is_file());
+
+            let metadata: Solution = serde_json::from_str(
+                &fs::read_to_string(
+                    solution_path
+                        .join(AUXILIAIRE_STATE_DIR_NAME)
+                        .join(COMMUNITY_METADATA_FILE_NAME),
+                )
+                .await
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(SOLUTION_UUID, metadata.uuid);
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_not_found() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mock_server = get_mock_server().await;
+
+        let command = CommunityCommand::new(
+            CommunityArgs {
+                solution: "https://exercism.org/tracks/rust/exercises/poker/solutions/nobody"
+                    .parse()
+                    .unwrap(),
+                path: temp_dir.path().to_path_buf(),
+                token: Some("some_token".into()),
+                token_command: None,
+            },
+            Some(&mock_server.uri()),
+        )
+        .unwrap();
+        assert!(command.execute().await.is_err());
+    }
+
+    #[test(tokio::test)]
+    async fn test_unsafe_paths() {
+        for (handle, file) in [
+            ("someone", "../../../../escaped.rs"),
+            ("someone", "src/../../../../../escaped.rs"),
+            ("someone", "/escaped.rs"),
+            ("..", "escaped.rs"),
+        ] {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let backup_path = temp_dir.path().join("backup");
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method(Method::GET))
+                .and(path(format!("/solutions/{SOLUTION_UUID}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(solution_json(
+                    &mock_server,
+                    handle,
+                    &[file],
+                )))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method(Method::GET))
+                .and(path(format!("/solutions/{SOLUTION_UUID}/files/{file}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string("escaped"))
+                .expect(0)
+                .mount(&mock_server)
+                .await;
+
+            let command = CommunityCommand::new(
+                CommunityArgs {
+                    solution: SOLUTION_UUID.parse().unwrap(),
+                    path: backup_path.clone(),
+                    token: Some("some_token".into()),
+                    token_command: None,
+                },
+                Some(&mock_server.uri()),
+            )
+            .unwrap();
+            assert!(command.execute().await.is_err(), "{handle}/{file}");
+            assert!(!backup_path.exists(), "{handle}/{file}");
+            assert!(!temp_dir.path().join("escaped.rs").exists(), "{handle}/{file}");
+        }
+    }
+}
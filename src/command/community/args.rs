@@ -0,0 +1,133 @@
+//! Arguments that can be passed to the [`Community`](crate::command::Command::Community) command.
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use clap::Args;
+
+use crate::Error;
+
+/// Command-line arguments accepted by the [`Community`](crate::command::Command::Community) command.
+#[derive(Debug, Clone, Args)]
+pub struct CommunityArgs {
+    /// Public URL of the solution to download (e.g. https://exercism.org/tracks/rust/exercises/poker/solutions/clechasseur), or its UUID
+    #[arg(value_name = "URL|UUID")]
+    pub solution: CommunitySolutionId,
+
+    /// Path where solutions have been backed up
+    #[arg(short, long, default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+}
+
+/// Identifies a published solution (see [`CommunityArgs::solution`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommunitySolutionId {
+    /// Solution's UUID.
+    Uuid(String),
+
+    /// Solution's public URL, in the form `https://exercism.org/tracks/<track>/exercises/<exercise>/solutions/<handle>`.
+    Url {
+        /// Name of the solution's track.
+        track: String,
+
+        /// Name of the solution's exercise.
+        exercise: String,
+
+        /// Handle of the solution's author.
+        handle: String,
+    },
+}
+
+impl FromStr for CommunitySolutionId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains('/') {
+            return if !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                Ok(Self::Uuid(s.into()))
+            } else {
+                Err(anyhow!("invalid solution UUID '{s}'"))
+            };
+        }
+
+        let parts = s
+            .split_once("/tracks/")
+            .map(|(_, path)| path.split(['/', '?', '#']).collect::<Vec<_>>());
+        match parts.as_deref() {
+            Some([track, "exercises", exercise, "solutions", handle, ..])
+                if !track.is_empty() && !exercise.is_empty() && !handle.is_empty() =>
+            {
+                Ok(Self::Url {
+                    track: (*track).into(),
+                    exercise: (*exercise).into(),
+                    handle: (*handle).into(),
+                })
+            },
+            _ => Err(anyhow!(
+                "invalid solution URL '{s}': expected https://exercism.org/tracks/<track>/exercises/<exercise>/solutions/<handle>"
+            )),
+        }
+    }
+}
+
+impl Display for CommunitySolutionId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Uuid(uuid) => write!(f, "{uuid}"),
+            Self::Url { track, exercise, handle } => write!(f, "{track}/{exercise} by {handle}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod community_solution_id {
+        use assert_matches::assert_matches;
+
+        use super::*;
+
+        #[test]
+        fn test_uuid() {
+            assert_eq!(
+                CommunitySolutionId::Uuid("00c717b68e1b4213b316df82636f5e0f".into()),
+                "00c717b68e1b4213b316df82636f5e0f".parse().unwrap()
+            );
+            assert_matches!("".parse::<CommunitySolutionId>(), Err(_));
+            assert_matches!("not a uuid".parse::<CommunitySolutionId>(), Err(_));
+        }
+
+        #[test]
+        fn test_url() {
+            let solution_id: CommunitySolutionId =
+                "https://exercism.org/tracks/rust/exercises/poker/solutions/clechasseur?foo=bar"
+                    .parse()
+                    .unwrap();
+            assert_eq!(
+                CommunitySolutionId::Url {
+                    track: "rust".into(),
+                    exercise: "poker".into(),
+                    handle: "clechasseur".into(),
+                },
+                solution_id
+            );
+            assert_eq!("rust/poker by clechasseur", solution_id.to_string());
+
+            assert_matches!(
+                "https://exercism.org/tracks/rust/exercises/poker".parse::<CommunitySolutionId>(),
+                Err(_)
+            );
+        }
+    }
+}
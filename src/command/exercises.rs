@@ -0,0 +1,290 @@
+//! Definition of the [`Exercises`](crate::command::Command::Exercises) command.
+
+pub mod args;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+use anstream::println;
+use anyhow::Context;
+use mini_exercism::api;
+use mini_exercism::api::v2::exercises;
+use mini_exercism::api::v2::solution::Solution;
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::args::SolutionStatus;
+use crate::command::backup::state::BackupState;
+use crate::command::exercises::args::ExercisesArgs;
+use crate::command::get_credentials;
+use crate::style::{HEADER, NAME};
+use crate::Result;
+
+/// Command wrapper used for the [`Exercises`](crate::command::Command::Exercises) command.
+#[derive(Debug)]
+pub struct ExercisesCommand {
+    args: ExercisesArgs,
+    v2_client: api::v2::Client,
+}
+
+impl ExercisesCommand {
+    /// Creates a new [`ExercisesCommand`] using the provided [`args`](ExercisesArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: ExercisesArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let credentials = get_credentials(args.token.as_deref(), args.token_command.as_deref())?;
+
+        let mut builder = api::v2::Client::builder();
+        builder.credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v2_client = builder.build()?;
+
+        Ok(Self { args, v2_client })
+    }
+
+    /// List the exercises of the track.
+    ///
+    /// Each exercise is listed with the status of its solution on Exercism.org and, if it was
+    /// solved, whether it's been backed up.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        let statuses = self.get_statuses().await?;
+
+        if statuses.is_empty() {
+            info!("No exercises found in track {}", self.args.track);
+            return Ok(());
+        }
+
+        let mut counts = BTreeMap::<&str, usize>::new();
+        for (exercise, status) in &statuses {
+            println!("{NAME}{exercise}{NAME:#}: {status}");
+            *counts.entry(status.summary()).or_default() += 1;
+        }
+
+        println!("\n{HEADER}Summary{HEADER:#}");
+        for (summary, count) in counts {
+            println!("  {summary}: {count}");
+        }
+
+        Ok(())
+    }
+
+    async fn get_statuses(&self) -> Result<Vec<(String, ExerciseStatus)>> {
+        let track = &self.args.track;
+        let filters = exercises::Filters::builder()
+            .include_solutions(true)
+            .build();
+        let response = self
+            .v2_client
+            .get_exercises(track, Some(filters))
+            .await
+            .with_context(|| format!("failed to fetch exercises for track {track}"))?;
+
+        let mut solutions = response
+            .solutions
+            .into_iter()
+            .map(|solution| (solution.exercise.name.clone(), solution))
+            .collect::<HashMap<_, _>>();
+
+        let mut statuses = Vec::with_capacity(response.exercises.len());
+        for exercise in response.exercises {
+            let status = match solutions.remove(&exercise.name) {
+                None if !exercise.is_unlocked => ExerciseStatus::Locked,
+                None => ExerciseStatus::NotStarted,
+                Some(solution) => self.get_solution_status(&solution).await,
+            };
+            statuses.push((exercise.name, status));
+        }
+
+        Ok(statuses)
+    }
+
+    async fn get_solution_status(&self, solution: &Solution) -> ExerciseStatus {
+        let mut solution_path = self.args.path.clone();
+        solution_path.push(&solution.track.name);
+        solution_path.push(&solution.exercise.name);
+
+        let state = BackupState::load(&solution_path).await;
+        trace!(exercise = solution.exercise.name, ?state);
+
+        let solution_status = solution_status_desc(solution);
+        match state.map(|state| state.needs_update(solution)) {
+            None => ExerciseStatus::MissingLocally(solution_status),
+            Some(Ok(true)) => ExerciseStatus::Stale(solution_status),
+            Some(Ok(false)) => ExerciseStatus::BackedUp(solution_status),
+            Some(Err(err)) => ExerciseStatus::Inconsistent(solution_status, err.to_string()),
+        }
+    }
+}
+
+/// Status of an exercise, as reported by the [`Exercises`](crate::command::Command::Exercises) command.
+///
+/// For exercises that have a solution, the solution's status on Exercism.org is also stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ExerciseStatus {
+    /// Exercise is not unlocked yet.
+    Locked,
+
+    /// Exercise is unlocked, but has not been started.
+    NotStarted,
+
+    /// Exercise has a solution, but it has not been backed up.
+    MissingLocally(&'static str),
+
+    /// Exercise has a solution that was backed up, but has changed on Exercism.org since.
+    Stale(&'static str),
+
+    /// Exercise has a solution that is backed up and up-to-date.
+    BackedUp(&'static str),
+
+    /// Exercise has a solution whose backup does not seem to match.
+    Inconsistent(&'static str, String),
+}
+
+impl ExerciseStatus {
+    fn summary(&self) -> &'static str {
+        match self {
+            Self::Locked => "locked",
+            Self::NotStarted => "not started",
+            Self::MissingLocally(_) => "missing locally",
+            Self::Stale(_) => "stale",
+            Self::BackedUp(_) => "backed up",
+            Self::Inconsistent(..) => "inconsistent",
+        }
+    }
+}
+
+impl Display for ExerciseStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Locked | Self::NotStarted => write!(f, "{}", self.summary()),
+            Self::MissingLocally(solution_status)
+            | Self::Stale(solution_status)
+            | Self::BackedUp(solution_status) => {
+                write!(f, "{solution_status}, {}", self.summary())
+            },
+            Self::Inconsistent(solution_status, reason) => {
+                write!(f, "{solution_status}, {} ({reason})", self.summary())
+            },
+        }
+    }
+}
+
+fn solution_status_desc(solution: &Solution) -> &'static str {
+    match SolutionStatus::try_from(solution.status) {
+        Ok(SolutionStatus::Any) => "started",
+        Ok(SolutionStatus::Submitted) => "submitted",
+        Ok(SolutionStatus::Completed) => "completed",
+        Ok(SolutionStatus::Published) => "published",
+        Err(_) => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::test_fixtures;
+
+    fn exercise_json(exercise: &str, is_unlocked: bool) -> serde_json::Value {
+        json!({
+            "slug": exercise,
+            "type": "practice",
+            "title": exercise,
+            "icon_url": "",
+            "difficulty": "easy",
+            "blurb": "",
+            "is_external": false,
+            "is_unlocked": is_unlocked,
+            "is_recommended": false,
+            "links": { "self": "" },
+        })
+    }
+
+    fn solution_json(exercise: &str, uuid: &str, last_iterated_at: &str) -> serde_json::Value {
+        test_fixtures::solution_json(
+            "rust",
+            exercise,
+            json!({
+                "uuid": uuid,
+                "updated_at": last_iterated_at,
+                "last_iterated_at": last_iterated_at,
+            }),
+        )
+    }
+
+    async fn get_mock_server(poker_last_iterated_at: &str) -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method(Method::GET))
+            .and(path("/tracks/rust/exercises"))
+            .and(query_param("sideload", "solutions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "exercises": [
+                    exercise_json("poker", true),
+                    exercise_json("clock", true),
+                    exercise_json("luhn", true),
+                    exercise_json("forth", false),
+                ],
+                "solutions": [
+                    solution_json("poker", "00c717b68e1b4213b316df82636f5e0f", poker_last_iterated_at),
+                    solution_json("clock", "clock-uuid", "2023-05-07T05:35:43Z"),
+                ],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    fn get_args() -> ExercisesArgs {
+        ExercisesArgs {
+            track: "rust".into(),
+            path: [env!("CARGO_MANIFEST_DIR"), "resources", "tests", "with_backup_state"]
+                .iter()
+                .collect::<PathBuf>(),
+            token: Some("some_token".into()),
+            token_command: None,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_up_to_date() {
+        let mock_server = get_mock_server("2023-05-07T05:35:43Z").await;
+        let command = ExercisesCommand::new(get_args(), Some(&mock_server.uri())).unwrap();
+
+        let statuses = command.get_statuses().await.unwrap();
+        assert_eq!(
+            vec![
+                ("poker".to_string(), ExerciseStatus::BackedUp("published")),
+                ("clock".to_string(), ExerciseStatus::MissingLocally("published")),
+                ("luhn".to_string(), ExerciseStatus::NotStarted),
+                ("forth".to_string(), ExerciseStatus::Locked),
+            ],
+            statuses
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_stale() {
+        let mock_server = get_mock_server("2024-01-02T10:00:00Z").await;
+        let command = ExercisesCommand::new(get_args(), Some(&mock_server.uri())).unwrap();
+
+        let statuses = command.get_statuses().await.unwrap();
+        assert_eq!(
+            Some(&ExerciseStatus::Stale("published")),
+            statuses
+                .iter()
+                .find(|(exercise, _)| exercise == "poker")
+                .map(|(_, status)| status)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Arguments that can be passed to the [`Exercises`](crate::command::Command::Exercises) command.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Command-line arguments accepted by the [`Exercises`](crate::command::Command::Exercises) command.
+#[derive(Debug, Clone, Args)]
+pub struct ExercisesArgs {
+    /// Track whose exercises to list (e.g. rust)
+    pub track: String,
+
+    /// Path where solutions have been backed up
+    #[arg(short, long, default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+}
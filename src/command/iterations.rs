@@ -0,0 +1,364 @@
+//! Definition of the [`Iterations`](crate::command::Command::Iterations) command.
+
+pub mod args;
+
+use std::io::Write;
+
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use mini_exercism::api;
+use serde::Serialize;
+use tokio::fs;
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::iterations::{get_iterations_dir_name, list_iterations_in};
+use crate::command::backup::state::BackupState;
+use crate::command::get_credentials;
+use crate::command::iterations::args::{
+    IterationsAction, IterationsArgs, IterationsDownloadArgs, IterationsFormat, IterationsListArgs,
+};
+use crate::command::solution::solution_uuid;
+use crate::style::NAME;
+use crate::Result;
+
+/// Command wrapper used for the [`Iterations`](crate::command::Command::Iterations) command.
+#[derive(Debug)]
+pub struct IterationsCommand {
+    args: IterationsArgs,
+    v2_client: api::v2::Client,
+    iterations_dir_name: String,
+}
+
+/// Information about an iteration, as listed by the [`List`](IterationsAction::List) operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct IterationInfo {
+    index: i32,
+    created_at: String,
+    tests_status: String,
+    is_published: bool,
+    is_backed_up: bool,
+}
+
+impl IterationsCommand {
+    /// Creates a new [`IterationsCommand`] using the provided [`args`](IterationsArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: IterationsArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let (token, token_command) = match &args.action {
+            IterationsAction::List(list_args) => (&list_args.token, &list_args.token_command),
+            IterationsAction::Download(download_args) => {
+                (&download_args.token, &download_args.token_command)
+            },
+        };
+        let credentials = get_credentials(token.as_deref(), token_command.as_deref())?;
+
+        let mut builder = api::v2::Client::builder();
+        builder.credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v2_client = builder.build()?;
+
+        Ok(Self { args, v2_client, iterations_dir_name: get_iterations_dir_name() })
+    }
+
+    /// Perform the requested [iterations operation](IterationsArgs::action).
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        match &self.args.action {
+            IterationsAction::List(list_args) => {
+                let iterations = self.get_iterations(list_args).await?;
+                if iterations.is_empty() {
+                    info!("No iterations found for solution to {}", list_args.solution);
+                }
+
+                let mut output = anstream::stdout();
+                for iteration in &iterations {
+                    write_iteration(&mut output, list_args.format, iteration)?;
+                }
+
+                Ok(())
+            },
+            IterationsAction::Download(download_args) => {
+                self.download_iteration(download_args).await
+            },
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(solution = %args.solution))]
+    async fn get_iterations(&self, args: &IterationsListArgs) -> Result<Vec<IterationInfo>> {
+        let solution_path = args.solution.path_in(&args.path);
+        let backup_state = BackupState::load(&solution_path).await;
+        trace!(?backup_state);
+
+        let uuid = solution_uuid(&self.v2_client, &args.solution, backup_state.as_ref()).await?;
+        let iterations = self
+            .v2_client
+            .get_solution(&uuid, true)
+            .await
+            .with_context(|| {
+                format!("failed to get list of iterations for solution to {}", args.solution)
+            })?
+            .iterations;
+
+        let iterations_path = solution_path.join(&self.iterations_dir_name);
+        let backed_up_iterations = if fs::metadata(&iterations_path)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            list_iterations_in(&iterations_path)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to list existing backed up iterations for solution to {}",
+                        args.solution
+                    )
+                })?
+        } else {
+            vec![]
+        };
+
+        Ok(iterations
+            .into_iter()
+            .sorted_unstable_by_key(|iteration| iteration.index)
+            .map(|iteration| IterationInfo {
+                index: iteration.index,
+                is_backed_up: backed_up_iterations.contains(&iteration.index),
+                created_at: iteration.created_at,
+                tests_status: iteration.tests_status.to_string(),
+                is_published: iteration.is_published,
+            })
+            .collect())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(solution = %args.solution, iteration.index = args.index))]
+    async fn download_iteration(&self, args: &IterationsDownloadArgs) -> Result<()> {
+        let uuid = solution_uuid(&self.v2_client, &args.solution, None).await?;
+        let iteration = self
+            .v2_client
+            .get_solution(&uuid, true)
+            .await
+            .with_context(|| {
+                format!("failed to get list of iterations for solution to {}", args.solution)
+            })?
+            .iterations
+            .into_iter()
+            .find(|iteration| iteration.index == args.index)
+            .ok_or_else(|| {
+                anyhow!("iteration {} of solution to {} not found", args.index, args.solution)
+            })?;
+        let submission_uuid = iteration.submission_uuid.as_deref().ok_or_else(|| {
+            anyhow!(
+                "iteration {} of solution to {} does not have a submission UUID",
+                args.index,
+                args.solution
+            )
+        })?;
+
+        let files = self
+            .v2_client
+            .get_submission_files(&uuid, submission_uuid)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to fetch files for iteration {} of solution to {}",
+                    args.index, args.solution
+                )
+            })?
+            .files;
+
+        for file in files {
+            let mut destination_path = args.path.clone();
+            destination_path.extend(file.filename.split('/'));
+            trace!(destination_path = %destination_path.display());
+
+            if let Some(parent) = destination_path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("failed to create directory {}", parent.display()))?;
+            }
+            fs::write(&destination_path, file.content)
+                .await
+                .with_context(|| format!("failed to save file {}", destination_path.display()))?;
+        }
+
+        info!(
+            "Iteration {} of solution to {} downloaded to {}",
+            args.index,
+            args.solution,
+            args.path.display()
+        );
+        Ok(())
+    }
+}
+
+fn write_iteration<W: Write>(
+    output: &mut W,
+    format: IterationsFormat,
+    iteration: &IterationInfo,
+) -> Result<()> {
+    match format {
+        IterationsFormat::Text => {
+            let published = if iteration.is_published { ", published" } else { "" };
+            let backed_up = if iteration.is_backed_up { "backed up" } else { "not backed up" };
+            writeln!(
+                output,
+                "{NAME}{}{NAME:#}: {}, tests {}{published} ({backed_up})",
+                iteration.index, iteration.created_at, iteration.tests_status,
+            )?;
+        },
+        IterationsFormat::Jsonl => {
+            serde_json::to_writer(&mut *output, iteration)
+                .with_context(|| format!("failed to serialize iteration {}", iteration.index))?;
+            writeln!(output)?;
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::test_fixtures;
+
+    const POKER_UUID: &str = "00c717b68e1b4213b316df82636f5e0f";
+
+    fn solution_json() -> serde_json::Value {
+        test_fixtures::solution_json(
+            "rust",
+            "poker",
+            json!({ "uuid": POKER_UUID, "num_iterations": 2 }),
+        )
+    }
+
+    async fn get_mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method(Method::GET))
+            .and(path(format!("/solutions/{POKER_UUID}")))
+            .and(query_param("sideload", "iterations"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "solution": solution_json(),
+                "iterations": [
+                    test_fixtures::iteration_json(2, true),
+                    test_fixtures::iteration_json(1, false),
+                ],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method(Method::GET))
+            .and(path("/solutions"))
+            .and(query_param("track_slug", "rust"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [solution_json()],
+                "meta": { "current_page": 1, "total_count": 1, "total_pages": 1 },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method(Method::GET))
+            .and(path(format!("/solutions/{POKER_UUID}/submissions/submission-2/files")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "files": [
+                    { "filename": "src/lib.rs", "content": "pub fn poker() {}", "digest": "" },
+                    { "filename": "Cargo.toml", "content": "[package]", "digest": "" },
+                ],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    #[test(tokio::test)]
+    async fn test_list() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let solution_path = temp_dir.path().join("rust").join("poker");
+        fs::create_dir_all(solution_path.join(".auxiliaire"))
+            .await
+            .unwrap();
+        fs::write(
+            solution_path.join(".auxiliaire").join("backup_state.json"),
+            json!({ "uuid": POKER_UUID, "last_iteration_marker": "none" }).to_string(),
+        )
+        .await
+        .unwrap();
+        fs::create_dir_all(solution_path.join(get_iterations_dir_name()).join("1"))
+            .await
+            .unwrap();
+
+        let list_args = IterationsListArgs {
+            solution: "rust/poker".parse().unwrap(),
+            path: temp_dir.path().to_path_buf(),
+            token: Some("some_token".into()),
+            token_command: None,
+            format: IterationsFormat::Text,
+        };
+        let mock_server = get_mock_server().await;
+        let command = IterationsCommand::new(
+            IterationsArgs { action: IterationsAction::List(list_args.clone()) },
+            Some(&mock_server.uri()),
+        )
+        .unwrap();
+
+        let iterations = command.get_iterations(&list_args).await.unwrap();
+        assert_eq!(
+            vec![
+                IterationInfo {
+                    index: 1,
+                    created_at: "2023-05-01T05:35:43Z".into(),
+                    tests_status: "passed".into(),
+                    is_published: false,
+                    is_backed_up: true,
+                },
+                IterationInfo {
+                    index: 2,
+                    created_at: "2023-05-02T05:35:43Z".into(),
+                    tests_status: "passed".into(),
+                    is_published: true,
+                    is_backed_up: false,
+                },
+            ],
+            iterations
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_download() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let download_args = IterationsDownloadArgs {
+            solution: "rust/poker".parse().unwrap(),
+            index: 2,
+            path: temp_dir.path().to_path_buf(),
+            token: Some("some_token".into()),
+            token_command: None,
+        };
+        let mock_server = get_mock_server().await;
+        let command = IterationsCommand::new(
+            IterationsArgs { action: IterationsAction::Download(download_args.clone()) },
+            Some(&mock_server.uri()),
+        )
+        .unwrap();
+
+        command.download_iteration(&download_args).await.unwrap();
+        assert_eq!(
+            "pub fn poker() {}",
+            fs::read_to_string(temp_dir.path().join("src").join("lib.rs"))
+                .await
+                .unwrap()
+        );
+        assert!(temp_dir.path().join("Cargo.toml").is_file());
+
+        let mut missing_args = download_args;
+        missing_args.index = 3;
+        assert!(command.download_iteration(&missing_args).await.is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! Arguments that can be passed to the [`Iterations`](crate::command::Command::Iterations) command.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::command::args::SolutionRef;
+
+/// Command-line arguments accepted by the [`Iterations`](crate::command::Command::Iterations) command.
+#[derive(Debug, Clone, Args)]
+pub struct IterationsArgs {
+    /// Operation to perform on the solution's iterations
+    #[command(subcommand)]
+    pub action: IterationsAction,
+}
+
+/// Possible operations on a solution's iterations (see [`IterationsArgs::action`]).
+#[derive(Debug, Clone, Subcommand)]
+pub enum IterationsAction {
+    /// List the iterations of a solution, indicating which ones are backed up locally
+    List(IterationsListArgs),
+
+    /// Download the files of a single iteration of a solution
+    Download(IterationsDownloadArgs),
+}
+
+/// Arguments of the [`List`](IterationsAction::List) operation.
+#[derive(Debug, Clone, Args)]
+pub struct IterationsListArgs {
+    /// Solution whose iterations to list, in the form <track>/<exercise> (e.g. rust/poker)
+    pub solution: SolutionRef,
+
+    /// Path where solutions have been backed up
+    #[arg(short, long, default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = IterationsFormat::Text, env = "AUXILIAIRE_FORMAT")]
+    pub format: IterationsFormat,
+}
+
+/// Arguments of the [`Download`](IterationsAction::Download) operation.
+#[derive(Debug, Clone, Args)]
+pub struct IterationsDownloadArgs {
+    /// Solution whose iteration to download, in the form <track>/<exercise> (e.g. rust/poker)
+    pub solution: SolutionRef,
+
+    /// Index of the iteration to download
+    #[arg(short = 'n', long, env = "AUXILIAIRE_ITERATION_INDEX")]
+    pub index: i32,
+
+    /// Path where to store the iteration's files
+    #[arg(default_value = ".", env = "AUXILIAIRE_ITERATION_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+}
+
+/// Possible output formats for the iterations list (see [`IterationsListArgs::format`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum IterationsFormat {
+    /// Human-readable list, one iteration per line
+    Text,
+
+    /// JSON Lines: one JSON object per iteration
+    Jsonl,
+}
@@ -0,0 +1,308 @@
+//! Definition of the [`List`](crate::command::Command::List) command.
+
+pub mod args;
+
+use std::io::Write;
+
+use anyhow::Context;
+use mini_exercism::api;
+use mini_exercism::api::v2::solution::Solution;
+use mini_exercism::api::v2::solutions;
+use tracing::{info, instrument, trace};
+
+use crate::cache::{cache_scope, solutions_page_key, Expiry, ResponseCache};
+use crate::command::backup::args::SolutionStatus;
+use crate::command::backup::difficulty::ExerciseDifficulties;
+use crate::command::get_credentials;
+use crate::command::list::args::{ListArgs, ListFormat};
+use crate::style::NAME;
+use crate::Result;
+
+/// Command wrapper used for the [`List`](crate::command::Command::List) command.
+#[derive(Debug)]
+pub struct ListCommand {
+    args: ListArgs,
+    v2_client: api::v2::Client,
+    response_cache: ResponseCache,
+    exercise_difficulties: ExerciseDifficulties,
+}
+
+impl ListCommand {
+    /// Creates a new [`ListCommand`] using the provided [`args`](ListArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: ListArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let credentials = get_credentials(args.token.as_deref(), args.token_command.as_deref())?;
+        let response_cache = ResponseCache::new(
+            &args.cache,
+            &cache_scope(
+                credentials.api_token(),
+                &[api_base_url.unwrap_or(api::v2::DEFAULT_V2_API_BASE_URL)],
+            ),
+        );
+
+        let mut builder = api::v2::Client::builder();
+        builder.credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v2_client = builder.build()?;
+
+        Ok(Self {
+            args,
+            v2_client,
+            response_cache,
+            exercise_difficulties: ExerciseDifficulties::default(),
+        })
+    }
+
+    /// List the solutions.
+    ///
+    /// Solutions are written to the standard output as soon as each page is fetched.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        self.list(&mut anstream::stdout()).await
+    }
+
+    async fn list<W: Write>(&self, output: &mut W) -> Result<()> {
+        let mut num_solutions = 0;
+
+        let mut page = 1;
+        loop {
+            let (solutions, meta) = self.get_solutions_for_page(page).await?;
+
+            for solution in &solutions {
+                self.write_solution(output, solution)?;
+            }
+            // Flush after each page so that downstream tools can start processing right away.
+            output.flush()?;
+            num_solutions += solutions.len();
+
+            if meta.current_page >= meta.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        if num_solutions == 0 {
+            info!("No solutions found");
+        }
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn get_solutions_for_page(
+        &self,
+        page: i64,
+    ) -> Result<(Vec<Solution>, solutions::ResponseMeta)> {
+        let track = (self.args.track.len() == 1).then(|| self.args.track[0].as_str());
+        let exercise = (self.args.exercise.len() == 1).then(|| self.args.exercise[0].as_str());
+
+        let mut filters = solutions::Filters::builder();
+        if let Some(track) = track {
+            filters.track(track);
+        }
+        if let Some(exercise) = exercise {
+            filters.criteria(exercise);
+        }
+
+        let key = solutions_page_key(track, exercise, false, page);
+        let response: solutions::Response = self
+            .response_cache
+            .get_or_fetch(&key, Expiry::Ttl, || async {
+                self.v2_client
+                    .get_solutions(
+                        Some(filters.build()),
+                        Some(solutions::Paging::for_page(page)),
+                        Some(solutions::SortOrder::NewestFirst),
+                    )
+                    .await
+                    .with_context(|| format!("failed to fetch solutions for page {page}"))
+            })
+            .await?;
+        trace!(?response.meta);
+
+        let mut solutions = Vec::new();
+        for solution in response
+            .results
+            .into_iter()
+            .filter(|solution| self.args.solution_matches(solution))
+        {
+            let track = &solution.track.name;
+            if self
+                .exercise_difficulties
+                .matches(
+                    &self.response_cache,
+                    &self.args.difficulty,
+                    track,
+                    &solution.exercise.name,
+                    || async {
+                        self.v2_client
+                            .get_exercises(track, None)
+                            .await
+                            .with_context(|| format!("failed to fetch exercises for track {track}"))
+                    },
+                )
+                .await?
+            {
+                solutions.push(solution);
+            }
+        }
+        Ok((solutions, response.meta))
+    }
+
+    fn write_solution<W: Write>(&self, output: &mut W, solution: &Solution) -> Result<()> {
+        match self.args.format {
+            ListFormat::Text => {
+                let status = match SolutionStatus::try_from(solution.status) {
+                    Ok(SolutionStatus::Any) => "started",
+                    Ok(SolutionStatus::Submitted) => "submitted",
+                    Ok(SolutionStatus::Completed) => "completed",
+                    Ok(SolutionStatus::Published) => "published",
+                    Err(_) => "unknown",
+                };
+                writeln!(
+                    output,
+                    "{NAME}{}/{}{NAME:#} ({status}, {} iteration(s))",
+                    solution.track.name, solution.exercise.name, solution.num_iterations
+                )?;
+            },
+            ListFormat::Jsonl => {
+                let mut anonymized;
+                let solution = if self.args.anonymize {
+                    // The public URL of a solution contains its author's handle.
+                    anonymized = solution.clone();
+                    anonymized.public_url.clear();
+                    &anonymized
+                } else {
+                    solution
+                };
+
+                serde_json::to_writer(&mut *output, solution).with_context(|| {
+                    format!(
+                        "failed to serialize solution to {}/{}",
+                        solution.track.name, solution.exercise.name
+                    )
+                })?;
+                writeln!(output)?;
+            },
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::command::args::ResponseCacheArgs;
+    use crate::test_fixtures;
+
+    fn solution_json(track: &str, exercise: &str, status: &str) -> serde_json::Value {
+        test_fixtures::solution_json(
+            track,
+            exercise,
+            json!({
+                "status": status,
+                "updated_at": "2023-11-23T07:13:23Z",
+                "last_iterated_at": null,
+            }),
+        )
+    }
+
+    async fn get_mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        let pages = [
+            vec![
+                solution_json("rust", "poker", "published"),
+                solution_json("rust", "clock", "started"),
+            ],
+            vec![solution_json("clojure", "poker", "completed")],
+        ];
+        for (i, results) in pages.iter().enumerate() {
+            Mock::given(method(Method::GET))
+                .and(path("/solutions"))
+                .and(query_param("page", (i + 1).to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "results": results,
+                    "meta": { "current_page": i + 1, "total_count": 3, "total_pages": pages.len() },
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        mock_server
+    }
+
+    fn get_args(format: ListFormat) -> ListArgs {
+        ListArgs {
+            token: Some("some_token".into()),
+            token_command: None,
+            track: vec![],
+            exercise: vec![],
+            filter_file: None,
+            status: SolutionStatus::Any,
+            difficulty: vec![],
+            format,
+            anonymize: false,
+            cache: ResponseCacheArgs::default(),
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_jsonl() {
+        let mock_server = get_mock_server().await;
+        let command =
+            ListCommand::new(get_args(ListFormat::Jsonl), Some(&mock_server.uri())).unwrap();
+
+        let mut output = Vec::new();
+        command.list(&mut output).await.unwrap();
+
+        let solutions = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Solution>(line).unwrap())
+            .map(|solution| format!("{}/{}", solution.track.name, solution.exercise.name))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["rust/poker", "rust/clock", "clojure/poker"], solutions);
+    }
+
+    #[test(tokio::test)]
+    async fn test_anonymize() {
+        let mock_server = get_mock_server().await;
+        let mut args = get_args(ListFormat::Jsonl);
+        args.anonymize = true;
+        let command = ListCommand::new(args, Some(&mock_server.uri())).unwrap();
+
+        let mut output = Vec::new();
+        command.list(&mut output).await.unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(3, output.lines().count());
+        assert!(!output.contains("clechasseur"));
+    }
+
+    #[test(tokio::test)]
+    async fn test_filters() {
+        let mock_server = get_mock_server().await;
+        let mut args = get_args(ListFormat::Jsonl);
+        args.exercise = vec!["poker".into(), "clock".into()];
+        args.status = SolutionStatus::Completed;
+        let command = ListCommand::new(args, Some(&mock_server.uri())).unwrap();
+
+        let mut output = Vec::new();
+        command.list(&mut output).await.unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(2, output.lines().count());
+        assert!(!output.contains("\"clock\""));
+    }
+}
@@ -0,0 +1,73 @@
+//! Arguments that can be passed to the [`List`](crate::command::Command::List) command.
+
+use clap::{Args, ValueEnum};
+use mini_exercism::api::v2::solution::Solution;
+
+use crate::command::args::{ResponseCacheArgs, SolutionFilter};
+use crate::command::backup::args::{ExerciseDifficulty, SolutionStatus};
+
+/// Command-line arguments accepted by the [`List`](crate::command::Command::List) command.
+#[derive(Debug, Clone, Args)]
+pub struct ListArgs {
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Only list solutions in the given track(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_TRACK", value_delimiter = ',')]
+    pub track: Vec<String>,
+
+    /// Only list solutions for the given exercise(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_EXERCISE", value_delimiter = ',')]
+    pub exercise: Vec<String>,
+
+    /// Only list solutions matching the `track/exercise` patterns listed in the given file (see README)
+    #[arg(long, value_name = "PATH", value_parser = SolutionFilter::load, env = "AUXILIAIRE_FILTER_FILE")]
+    pub filter_file: Option<SolutionFilter>,
+
+    /// Only list solutions with the given status (or greater)
+    #[arg(short, long, value_enum, default_value_t = SolutionStatus::Any, env = "AUXILIAIRE_STATUS")]
+    pub status: SolutionStatus,
+
+    /// Only list solutions to exercises with the given difficulty (can be used multiple times)
+    #[arg(long, value_enum, env = "AUXILIAIRE_DIFFICULTY", value_delimiter = ',')]
+    pub difficulty: Vec<ExerciseDifficulty>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ListFormat::Text, env = "AUXILIAIRE_FORMAT")]
+    pub format: ListFormat,
+
+    /// Strip URLs containing usernames from listed solutions (with --format jsonl), so that the list can be shared publicly
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_ANONYMIZE")]
+    pub anonymize: bool,
+
+    /// Options controlling the cache of Exercism API responses
+    #[command(flatten)]
+    pub cache: ResponseCacheArgs,
+}
+
+impl ListArgs {
+    /// Determines if the given [`Solution`] should be listed.
+    pub fn solution_matches(&self, solution: &Solution) -> bool {
+        (self.track.is_empty() || self.track.contains(&solution.track.name))
+            && (self.exercise.is_empty() || self.exercise.contains(&solution.exercise.name))
+            && self.filter_file.as_ref().map_or(true, |filter| {
+                filter.matches(&solution.track.name, &solution.exercise.name)
+            })
+            && SolutionStatus::try_from(solution.status).is_ok_and(|status| status >= self.status)
+    }
+}
+
+/// Possible output formats for the solutions list (see [`ListArgs::format`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    /// Human-readable list, one solution per line
+    Text,
+
+    /// JSON Lines: one JSON object per solution, emitted as solutions are fetched
+    Jsonl,
+}
@@ -0,0 +1,69 @@
+//! Definition of the [`Man`](crate::command::Command::Man) command.
+
+pub mod args;
+
+use std::io;
+
+use anyhow::Context;
+use clap::CommandFactory;
+use clap_mangen::Man;
+use tracing::{info, instrument};
+
+use crate::command::man::args::ManArgs;
+use crate::{Cli, Result};
+
+/// Command wrapper used for the [`Man`](crate::command::Command::Man) command.
+#[derive(Debug)]
+pub struct ManCommand {
+    args: ManArgs,
+}
+
+impl ManCommand {
+    /// Creates a new [`ManCommand`] using the provided [`args`](ManArgs).
+    pub fn new(args: ManArgs) -> Self {
+        Self { args }
+    }
+
+    /// Generate man pages.
+    ///
+    /// If an [output directory](ManArgs::output_dir) has been specified, one man page is generated
+    /// in that directory for the program and for each of its (non-hidden) commands. Otherwise, the
+    /// main man page is printed to stdout.
+    #[instrument(skip_all)]
+    pub fn execute(&self) -> Result<()> {
+        let command = Cli::command();
+
+        match &self.args.output_dir {
+            Some(output_dir) => {
+                std::fs::create_dir_all(output_dir).with_context(|| {
+                    format!("failed to create man pages output directory {}", output_dir.display())
+                })?;
+                clap_mangen::generate_to(command, output_dir).with_context(|| {
+                    format!("failed to generate man pages in {}", output_dir.display())
+                })?;
+                info!("Man pages generated in {}", output_dir.display());
+
+                Ok(())
+            },
+            None => Man::new(command)
+                .render(&mut io::stdout())
+                .with_context(|| "failed to render man page"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut output = Vec::new();
+        Man::new(Cli::command()).render(&mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with(".ie"));
+        assert!(output.contains("auxiliaire\\-backup(1)"));
+        assert!(!output.contains("auxiliaire\\-man(1)"));
+    }
+}
@@ -0,0 +1,13 @@
+//! Arguments that can be passed to the [`Man`](crate::command::Command::Man) command.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Command-line arguments accepted by the [`Man`](crate::command::Command::Man) command.
+#[derive(Debug, Clone, Args)]
+pub struct ManArgs {
+    /// Directory where to generate man pages for auxiliaire and all its commands; if unspecified,
+    /// the main man page is printed to stdout
+    pub output_dir: Option<PathBuf>,
+}
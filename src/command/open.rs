@@ -0,0 +1,120 @@
+//! Definition of the [`Open`](crate::command::Command::Open) command.
+
+pub mod args;
+
+use std::env;
+use std::ffi::OsString;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use tokio::process::Command;
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::state::BackupState;
+use crate::command::open::args::OpenArgs;
+use crate::Result;
+
+/// Command wrapper used for the [`Open`](crate::command::Command::Open) command.
+#[derive(Debug)]
+pub struct OpenCommand {
+    args: OpenArgs,
+}
+
+impl OpenCommand {
+    /// Creates a new [`OpenCommand`] using the provided [`args`](OpenArgs).
+    pub fn new(args: OpenArgs) -> Self {
+        Self { args }
+    }
+
+    /// Opens the backed up solution.
+    ///
+    /// The solution is located in the backup using its backup state; depending on the
+    /// [arguments](OpenArgs), either its private URL, its public URL or its directory
+    /// (in the user's editor) is then opened.
+    #[instrument(skip_all, fields(solution = %self.args.solution))]
+    pub async fn execute(&self) -> Result<()> {
+        let solution_path = self.args.solution.path_in(&self.args.path);
+        trace!(solution_path = %solution_path.display());
+
+        let state = BackupState::load(&solution_path).await.ok_or_else(|| {
+            anyhow!(
+                "solution to {} not found in backup at {}",
+                self.args.solution,
+                self.args.path.display()
+            )
+        })?;
+        trace!(?state);
+
+        if self.args.editor {
+            self.open_in_editor(&solution_path).await
+        } else {
+            let url = if self.args.public {
+                state.public_url.ok_or_else(|| {
+                    anyhow!(
+                        "public URL of solution to {} is unknown; back it up again to store it",
+                        self.args.solution
+                    )
+                })?
+            } else {
+                state.private_url.unwrap_or_else(|| {
+                    format!(
+                        "https://exercism.org/tracks/{}/exercises/{}",
+                        self.args.solution.track, self.args.solution.exercise
+                    )
+                })
+            };
+
+            self.open_in_browser(&url).await
+        }
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn open_in_browser(&self, url: &str) -> Result<()> {
+        info!("Opening {url}");
+
+        let mut command = if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        } else if cfg!(target_os = "macos") {
+            Command::new("open")
+        } else {
+            Command::new("xdg-open")
+        };
+
+        run(command.arg(url)).await
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn open_in_editor(&self, solution_path: &Path) -> Result<()> {
+        let editor = env::var_os("VISUAL")
+            .or_else(|| env::var_os("EDITOR"))
+            .filter(|editor| !editor.is_empty())
+            .ok_or_else(|| {
+                anyhow!("no editor configured; set the VISUAL or EDITOR environment variable")
+            })?;
+
+        // Editor variables often include arguments (e.g. `code --wait`).
+        let editor = editor.to_string_lossy().into_owned();
+        let mut editor_parts = editor.split_whitespace().map(OsString::from);
+        let mut command = Command::new(editor_parts.next().unwrap_or_default());
+        command.args(editor_parts).arg(solution_path);
+
+        info!("Opening {} in {editor}", solution_path.display());
+        run(&mut command).await
+    }
+}
+
+async fn run(command: &mut Command) -> Result<()> {
+    trace!(?command);
+
+    let status = command
+        .status()
+        .await
+        .with_context(|| format!("failed to run {:?}", command.as_std().get_program()))?;
+
+    status
+        .success()
+        .then_some(())
+        .ok_or_else(|| anyhow!("{:?} exited with {status}", command.as_std().get_program()))
+}
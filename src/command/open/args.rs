@@ -0,0 +1,26 @@
+//! Arguments that can be passed to the [`Open`](crate::command::Command::Open) command.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::command::args::SolutionRef;
+
+/// Command-line arguments accepted by the [`Open`](crate::command::Command::Open) command.
+#[derive(Debug, Clone, Args)]
+pub struct OpenArgs {
+    /// Solution to open, in the form <track>/<exercise> (e.g. rust/poker)
+    pub solution: SolutionRef,
+
+    /// Path where solutions have been backed up
+    #[arg(short, long, default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Open the solution's public URL instead of its private URL
+    #[arg(long, default_value_t = false, conflicts_with = "editor", env = "AUXILIAIRE_PUBLIC")]
+    pub public: bool,
+
+    /// Open the solution's directory in the editor specified by $VISUAL or $EDITOR instead of in a browser
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_EDITOR")]
+    pub editor: bool,
+}
@@ -0,0 +1,207 @@
+//! Definition of the [`Search`](crate::command::Command::Search) command.
+
+pub mod args;
+
+use std::path::Path;
+
+use anstream::println;
+use anyhow::Context;
+use regex::{Regex, RegexBuilder};
+use tokio::fs;
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::iterations::get_iterations_dir_name;
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::command::search::args::SearchArgs;
+use crate::style::{MATCH, NAME};
+use crate::walk::{files, subdirectories};
+use crate::Result;
+
+/// Command wrapper used for the [`Search`](crate::command::Command::Search) command.
+#[derive(Debug)]
+pub struct SearchCommand {
+    args: SearchArgs,
+    matcher: Regex,
+    iterations_dir_name: String,
+}
+
+impl SearchCommand {
+    /// Creates a new [`SearchCommand`] using the provided [`args`](SearchArgs).
+    ///
+    /// Fails if the query is not a valid [regular expression](SearchArgs::regex).
+    pub fn new(args: SearchArgs) -> Result<Self> {
+        let pattern = if args.regex { args.query.clone() } else { regex::escape(&args.query) };
+        let matcher = RegexBuilder::new(&pattern)
+            .case_insensitive(args.ignore_case)
+            .build()
+            .with_context(|| format!("invalid search query: {}", args.query))?;
+
+        Ok(Self { args, matcher, iterations_dir_name: get_iterations_dir_name() })
+    }
+
+    /// Search the backed up solutions.
+    ///
+    /// Matching lines are printed along with the path of their file (relative to the backup
+    /// directory) and their line number.
+    #[instrument(skip_all, fields(query = %self.args.query))]
+    pub async fn execute(&self) -> Result<()> {
+        let mut num_matches = 0;
+
+        for (track, track_path) in subdirectories(&self.args.path).await? {
+            for (exercise, solution_path) in subdirectories(&track_path).await? {
+                if self.args.solution_matches(&track, &exercise) {
+                    num_matches += self.search_solution(&solution_path).await?;
+                }
+            }
+        }
+
+        if num_matches == 0 {
+            info!("No match found for {}", self.args.query);
+        }
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn search_solution(&self, solution_path: &Path) -> Result<usize> {
+        let solution_files = files(solution_path, |dir| self.should_skip_dir(dir)).await?;
+
+        let mut num_matches = 0;
+        for file in solution_files {
+            let extension = file.extension().and_then(|ext| ext.to_str());
+            if self.args.extension_matches(extension) {
+                num_matches += self.search_file(&file).await?;
+            }
+        }
+
+        Ok(num_matches)
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn search_file(&self, file_path: &Path) -> Result<usize> {
+        let content = fs::read(file_path)
+            .await
+            .with_context(|| format!("failed to read file {}", file_path.display()))?;
+        let Ok(content) = String::from_utf8(content) else {
+            trace!("Skipping binary file");
+            return Ok(0);
+        };
+
+        let display_path = file_path
+            .strip_prefix(&self.args.path)
+            .unwrap_or(file_path)
+            .display();
+
+        let mut num_matches = 0;
+        for (line_number, line) in content.lines().enumerate() {
+            if self.matcher.is_match(line) {
+                num_matches += 1;
+
+                if self.args.files_with_matches {
+                    println!("{NAME}{display_path}{NAME:#}");
+                    break;
+                }
+
+                let line = self
+                    .matcher
+                    .replace_all(line, |caps: &regex::Captures<'_>| {
+                        format!("{MATCH}{}{MATCH:#}", &caps[0])
+                    });
+                println!("{NAME}{display_path}{NAME:#}:{}: {line}", line_number + 1);
+            }
+        }
+
+        Ok(num_matches)
+    }
+
+    fn should_skip_dir(&self, dir_path: &Path) -> bool {
+        dir_path.file_name().is_some_and(|name| {
+            name == AUXILIAIRE_STATE_DIR_NAME
+                || (self.args.no_iterations && name == self.iterations_dir_name.as_str())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn get_args(query: &str) -> SearchArgs {
+        SearchArgs {
+            query: query.into(),
+            path: PathBuf::default(),
+            track: vec![],
+            exercise: vec![],
+            extensions: vec![],
+            regex: false,
+            ignore_case: false,
+            no_iterations: false,
+            files_with_matches: false,
+        }
+    }
+
+    mod search_args {
+        use super::*;
+
+        #[test]
+        fn test_solution_matches() {
+            let mut args = get_args("foo");
+            assert!(args.solution_matches("rust", "poker"));
+
+            args.track = vec!["rust".into()];
+            assert!(args.solution_matches("rust", "poker"));
+            assert!(!args.solution_matches("clojure", "poker"));
+
+            args.exercise = vec!["zebra-puzzle".into()];
+            assert!(!args.solution_matches("rust", "poker"));
+        }
+
+        #[test]
+        fn test_extension_matches() {
+            let mut args = get_args("foo");
+            assert!(args.extension_matches(Some("rs")));
+            assert!(args.extension_matches(None));
+
+            args.extensions = vec![".rs".into(), "toml".into()];
+            assert!(args.extension_matches(Some("rs")));
+            assert!(args.extension_matches(Some("TOML")));
+            assert!(!args.extension_matches(Some("md")));
+            assert!(!args.extension_matches(None));
+        }
+    }
+
+    mod search_command {
+        use super::*;
+
+        #[test]
+        fn test_new() {
+            let command = SearchCommand::new(get_args("a.b")).unwrap();
+            assert!(command.matcher.is_match("a.b"));
+            assert!(!command.matcher.is_match("axb"));
+
+            let mut args = get_args("a.b");
+            args.regex = true;
+            args.ignore_case = true;
+            let command = SearchCommand::new(args).unwrap();
+            assert!(command.matcher.is_match("AXB"));
+
+            let mut args = get_args("(");
+            args.regex = true;
+            assert!(SearchCommand::new(args).is_err());
+        }
+
+        #[test]
+        fn test_should_skip_dir() {
+            let command = SearchCommand::new(get_args("foo")).unwrap();
+            assert!(command.should_skip_dir(Path::new("rust/poker/.auxiliaire")));
+            assert!(!command.should_skip_dir(Path::new("rust/poker/_iterations")));
+
+            let mut args = get_args("foo");
+            args.no_iterations = true;
+            let command = SearchCommand::new(args).unwrap();
+            assert!(command.should_skip_dir(Path::new("rust/poker/_iterations")));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Arguments that can be passed to the [`Search`](crate::command::Command::Search) command.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Command-line arguments accepted by the [`Search`](crate::command::Command::Search) command.
+#[derive(Debug, Clone, Args)]
+pub struct SearchArgs {
+    /// Text to search for
+    pub query: String,
+
+    /// Path where solutions have been backed up
+    #[arg(default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Only search solutions in the given track(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_TRACK", value_delimiter = ',')]
+    pub track: Vec<String>,
+
+    /// Only search solutions for the given exercise(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_EXERCISE", value_delimiter = ',')]
+    pub exercise: Vec<String>,
+
+    /// Only search files with the given extension(s) (can be used multiple times)
+    #[arg(short = 'x', long = "extension", env = "AUXILIAIRE_EXTENSION", value_delimiter = ',')]
+    pub extensions: Vec<String>,
+
+    /// Interpret the query as a regular expression
+    #[arg(short, long, default_value_t = false, env = "AUXILIAIRE_REGEX")]
+    pub regex: bool,
+
+    /// Perform a case-insensitive search
+    #[arg(short, long, default_value_t = false, env = "AUXILIAIRE_IGNORE_CASE")]
+    pub ignore_case: bool,
+
+    /// Do not search in backed up iterations
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_NO_ITERATIONS")]
+    pub no_iterations: bool,
+
+    /// Only print the paths of files containing matches
+    #[arg(short = 'l', long, default_value_t = false, env = "AUXILIAIRE_FILES_WITH_MATCHES")]
+    pub files_with_matches: bool,
+}
+
+impl SearchArgs {
+    /// Determines if the solution for the given track and exercise should be searched.
+    pub fn solution_matches(&self, track_name: &str, exercise_name: &str) -> bool {
+        (self.track.is_empty() || self.track.iter().any(|t| t == track_name))
+            && (self.exercise.is_empty() || self.exercise.iter().any(|e| e == exercise_name))
+    }
+
+    /// Determines if a file with the given extension should be searched.
+    pub fn extension_matches(&self, extension: Option<&str>) -> bool {
+        self.extensions.is_empty()
+            || extension.is_some_and(|ext| {
+                self.extensions
+                    .iter()
+                    .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            })
+    }
+}
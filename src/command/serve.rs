@@ -0,0 +1,305 @@
+//! Definition of the [`Serve`](crate::command::Command::Serve) command.
+
+pub mod args;
+mod highlight;
+mod page;
+
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{CONTENT_TYPE, LOCATION};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::fs;
+use tokio::net::TcpListener;
+use tracing::{debug, info, instrument, trace};
+
+use crate::command::serve::args::ServeArgs;
+use crate::command::serve::page::{directory_page, file_page, percent_decode, DirEntry};
+use crate::Result;
+
+/// Command wrapper used for the [`Serve`](crate::command::Command::Serve) command.
+#[derive(Debug)]
+pub struct ServeCommand {
+    args: ServeArgs,
+}
+
+impl ServeCommand {
+    /// Creates a new [`ServeCommand`] using the provided [`args`](ServeArgs).
+    pub fn new(args: ServeArgs) -> Self {
+        Self { args }
+    }
+
+    /// Serve the backup over HTTP until the program is stopped.
+    #[instrument(skip_all, fields(address = %self.args.address))]
+    pub async fn execute(self) -> Result<()> {
+        let listener = TcpListener::bind(self.args.address)
+            .await
+            .with_context(|| format!("failed to listen on {}", self.args.address))?;
+
+        self.serve(listener).await
+    }
+
+    async fn serve(self, listener: TcpListener) -> Result<()> {
+        info!(
+            "Serving backup in {} on http://{}/ (press Ctrl-C to stop)",
+            self.args.path.display(),
+            listener.local_addr()?
+        );
+
+        let this = Arc::new(self);
+        loop {
+            let (stream, remote_addr) = listener
+                .accept()
+                .await
+                .with_context(|| "failed to accept connection")?;
+            trace!(%remote_addr, "Accepted connection");
+
+            let this = Arc::clone(&this);
+            tokio::spawn(async move {
+                let service = service_fn(|request| {
+                    let this = Arc::clone(&this);
+                    async move { Ok::<_, Infallible>(this.respond(request).await) }
+                });
+                if let Err(err) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!("Error serving connection from {remote_addr}: {err}");
+                }
+            });
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(method = %request.method(), uri = %request.uri()))]
+    async fn respond(&self, request: Request<Incoming>) -> Response<Full<Bytes>> {
+        if request.method() != Method::GET && request.method() != Method::HEAD {
+            return text_response(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed");
+        }
+
+        let url_path = request.uri().path();
+        let Some((segments, path)) = self.resolve(url_path) else {
+            return text_response(StatusCode::NOT_FOUND, "Not found");
+        };
+
+        match fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_dir() => {
+                // Relative links in directory listings only work if the URL ends with a slash.
+                if !url_path.ends_with('/') {
+                    return Response::builder()
+                        .status(StatusCode::MOVED_PERMANENTLY)
+                        .header(LOCATION, format!("{url_path}/"))
+                        .body(Full::default())
+                        .unwrap();
+                }
+
+                match list_directory(&path).await {
+                    Ok(entries) => html_response(directory_page(&segments, &entries)),
+                    Err(err) => server_error(err),
+                }
+            },
+            Ok(metadata) if metadata.is_file() => match fs::read(&path).await {
+                Ok(content) if request.uri().query() == Some("raw") => Response::builder()
+                    .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+                    .body(Full::new(Bytes::from(content)))
+                    .unwrap(),
+                Ok(content) => {
+                    html_response(file_page(&segments, &String::from_utf8_lossy(&content)))
+                },
+                Err(err) => server_error(err.into()),
+            },
+            _ => text_response(StatusCode::NOT_FOUND, "Not found"),
+        }
+    }
+
+    /// Returns the path segments of the given URL path, along with the path of the file
+    /// or directory it refers to in the backup.
+    ///
+    /// Hidden files and directories (like `.auxiliaire`) cannot be accessed, which also prevents
+    /// escaping the backup directory via `..`.
+    fn resolve(&self, url_path: &str) -> Option<(Vec<String>, PathBuf)> {
+        let segments = url_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect::<Option<Vec<_>>>()?;
+        if segments
+            .iter()
+            .any(|segment| segment.starts_with('.') || segment.contains(['/', '\\']))
+        {
+            return None;
+        }
+
+        let mut path = self.args.path.clone();
+        path.extend(&segments);
+        Some((segments, path))
+    }
+}
+
+async fn list_directory(path: &Path) -> Result<Vec<DirEntry>> {
+    let mut dir_entries = fs::read_dir(path)
+        .await
+        .with_context(|| format!("failed to list content of directory {}", path.display()))?;
+
+    let mut entries = Vec::new();
+    while let Some(entry) = dir_entries.next_entry().await? {
+        match entry.file_name().into_string() {
+            Ok(name) if !name.starts_with('.') => {
+                entries.push(DirEntry { is_file: !entry.file_type().await?.is_dir(), name });
+            },
+            _ => (),
+        }
+    }
+
+    // Directories are listed first.
+    entries.sort_unstable();
+    Ok(entries)
+}
+
+fn html_response(page: String) -> Response<Full<Bytes>> {
+    Response::builder()
+        .header(CONTENT_TYPE, "text/html; charset=utf-8")
+        .body(Full::new(Bytes::from(page)))
+        .unwrap()
+}
+
+fn text_response(status: StatusCode, text: &'static str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Full::new(Bytes::from_static(text.as_bytes())))
+        .unwrap()
+}
+
+fn server_error(err: crate::Error) -> Response<Full<Bytes>> {
+    debug!("Error serving request: {err:#}");
+    text_response(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+}
+
+#[cfg(test)]
+mod tests {
+    use mini_exercism::http;
+    use test_log::test;
+
+    use super::*;
+
+    fn test_backup_path() -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "resources", "tests", "with_backup_state"]
+            .iter()
+            .collect()
+    }
+
+    fn command() -> ServeCommand {
+        ServeCommand::new(ServeArgs {
+            path: test_backup_path(),
+            address: "127.0.0.1:0".parse().unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_resolve() {
+        let command = command();
+
+        let (segments, path) = command.resolve("/rust/poker/my%20file.rs").unwrap();
+        assert_eq!(vec!["rust", "poker", "my file.rs"], segments);
+        assert_eq!(
+            test_backup_path()
+                .join("rust")
+                .join("poker")
+                .join("my file.rs"),
+            path
+        );
+
+        assert_eq!(Some((vec![], test_backup_path())), command.resolve("/"));
+        assert!(command.resolve("/rust/../../etc/passwd").is_none());
+        assert!(command.resolve("/rust/%2E%2E/secret").is_none());
+        assert!(command.resolve("/rust/poker/.auxiliaire/").is_none());
+        assert!(command.resolve("/rust/a%2Fb").is_none());
+    }
+
+    #[test(tokio::test)]
+    async fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(command().serve(listener));
+
+        let client = http::Client::builder()
+            .redirect(http::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let get = |path: &str| client.get(format!("{base_url}{path}")).send();
+
+        let response = get("/").await.unwrap();
+        assert_eq!(http::StatusCode::OK, response.status());
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("<a href=\"rust/\">rust/</a>"));
+
+        let response = get("/rust").await.unwrap();
+        assert_eq!(http::StatusCode::MOVED_PERMANENTLY, response.status());
+        assert_eq!(Some("/rust/"), response.headers()[LOCATION.as_str()].to_str().ok());
+
+        let response = get("/rust/poker/").await.unwrap();
+        assert_eq!(http::StatusCode::OK, response.status());
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("This directory is empty."));
+
+        let response = get("/rust/poker/.auxiliaire/backup_state.json")
+            .await
+            .unwrap();
+        assert_eq!(http::StatusCode::NOT_FOUND, response.status());
+
+        let response = client.post(format!("{base_url}/")).send().await.unwrap();
+        assert_eq!(http::StatusCode::METHOD_NOT_ALLOWED, response.status());
+
+        server.abort();
+    }
+
+    #[test(tokio::test)]
+    async fn test_serve_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp_dir.path().join("rust/poker/src"))
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("rust/poker/src/lib.rs"), "fn a() -> Vec<u8> {}")
+            .await
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let command = ServeCommand::new(ServeArgs {
+            path: temp_dir.path().to_path_buf(),
+            address: listener.local_addr().unwrap(),
+        });
+        let server = tokio::spawn(command.serve(listener));
+
+        let page = http::get(format!("{base_url}/rust/poker/src/lib.rs"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(page.contains("<span class=\"hl-keyword\">fn</span> a() -&gt; Vec&lt;u8&gt; {}"));
+
+        let raw = http::get(format!("{base_url}/rust/poker/src/lib.rs?raw"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!("fn a() -> Vec<u8> {}", raw);
+
+        server.abort();
+    }
+}
@@ -0,0 +1,18 @@
+//! Arguments that can be passed to the [`Serve`](crate::command::Command::Serve) command.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Command-line arguments accepted by the [`Serve`](crate::command::Command::Serve) command.
+#[derive(Debug, Clone, Args)]
+pub struct ServeArgs {
+    /// Path where solutions have been backed up
+    #[arg(default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Address on which to listen for HTTP requests
+    #[arg(short, long, default_value = "127.0.0.1:8080", env = "AUXILIAIRE_ADDRESS")]
+    pub address: SocketAddr,
+}
@@ -0,0 +1,286 @@
+//! Minimal syntax highlighting for the files displayed by the [`serve`](crate::command::serve)
+//! command.
+//!
+//! This is not meant to be accurate: comments, strings, numbers and keywords are recognized
+//! using simple rules shared by most languages of Exercism tracks, which is enough to make
+//! solutions easier to read without pulling a full-blown highlighter into the binary.
+
+use std::fmt::Write;
+
+use crate::command::serve::page::escape;
+
+/// Lexical rules of a language family.
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    keywords: &[
+        "abstract",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "final",
+        "finally",
+        "for",
+        "func",
+        "function",
+        "go",
+        "if",
+        "implements",
+        "import",
+        "interface",
+        "let",
+        "new",
+        "nil",
+        "null",
+        "override",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "struct",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "typedef",
+        "undefined",
+        "using",
+        "val",
+        "var",
+        "void",
+        "while",
+    ],
+};
+
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    // Single quotes are not strings in Rust, since they are also used for lifetimes.
+    quotes: &['"'],
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+        "type", "unsafe", "use", "where", "while",
+    ],
+};
+
+const PYTHON: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+        "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True",
+        "try", "while", "with", "yield",
+    ],
+};
+
+const RUBY_LIKE: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[
+        "alias",
+        "and",
+        "begin",
+        "case",
+        "class",
+        "cond",
+        "def",
+        "defmodule",
+        "defp",
+        "do",
+        "else",
+        "elsif",
+        "end",
+        "ensure",
+        "false",
+        "fn",
+        "for",
+        "if",
+        "in",
+        "module",
+        "next",
+        "nil",
+        "not",
+        "or",
+        "raise",
+        "rescue",
+        "return",
+        "self",
+        "then",
+        "true",
+        "unless",
+        "until",
+        "when",
+        "while",
+        "with",
+        "yield",
+    ],
+};
+
+const SHELL: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ],
+};
+
+const HASKELL_LIKE: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comment: Some(("{-", "-}")),
+    quotes: &['"'],
+    keywords: &[
+        "case", "class", "data", "deriving", "do", "else", "end", "function", "if", "import", "in",
+        "instance", "let", "local", "module", "newtype", "of", "then", "type", "where",
+    ],
+};
+
+fn syntax_for(extension: &str) -> Option<&'static Syntax> {
+    match extension {
+        "rs" => Some(&RUST),
+        "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "fs" | "go" | "java" | "js" | "mjs" | "ts"
+        | "kt" | "kts" | "scala" | "swift" | "dart" | "php" | "groovy" | "zig" | "v" => {
+            Some(&C_LIKE)
+        },
+        "py" => Some(&PYTHON),
+        "rb" | "ex" | "exs" | "cr" | "jl" | "r" | "R" => Some(&RUBY_LIKE),
+        "sh" | "bash" => Some(&SHELL),
+        "hs" | "elm" | "lua" | "sql" => Some(&HASKELL_LIKE),
+        _ => None,
+    }
+}
+
+/// Returns the content of a file with the given extension as HTML, with its tokens wrapped in
+/// `<span>`s with a `hl-<kind>` class (`hl-comment`, `hl-string`, `hl-number` or `hl-keyword`).
+///
+/// If the extension is not recognized, the content is only [escaped](escape).
+pub fn highlight(extension: &str, content: &str) -> String {
+    let Some(syntax) = syntax_for(extension) else {
+        return escape(content).into_owned();
+    };
+
+    let mut html = String::with_capacity(content.len() * 2);
+    let mut rest = content;
+    while let Some(c) = rest.chars().next() {
+        let (kind, len) = if let Some(len) = comment_len(syntax, rest) {
+            (Some("comment"), len)
+        } else if syntax.quotes.contains(&c) {
+            (Some("string"), string_len(rest, c))
+        } else if c.is_ascii_digit() {
+            (Some("number"), word_len(rest))
+        } else if c.is_alphabetic() || c == '_' {
+            let len = word_len(rest);
+            (syntax.keywords.contains(&&rest[..len]).then_some("keyword"), len)
+        } else {
+            (None, c.len_utf8())
+        };
+
+        let (token, remaining) = rest.split_at(len);
+        match kind {
+            Some(kind) => {
+                let _ = write!(html, "<span class=\"hl-{kind}\">{}</span>", escape(token));
+            },
+            None => html.push_str(&escape(token)),
+        }
+        rest = remaining;
+    }
+
+    html
+}
+
+fn comment_len(syntax: &Syntax, text: &str) -> Option<usize> {
+    if let Some((start, end)) = syntax.block_comment {
+        if let Some(comment) = text.strip_prefix(start) {
+            return Some(
+                comment
+                    .find(end)
+                    .map_or(text.len(), |i| start.len() + i + end.len()),
+            );
+        }
+    }
+
+    syntax
+        .line_comments
+        .iter()
+        .any(|prefix| text.starts_with(prefix))
+        .then(|| text.find('\n').unwrap_or(text.len()))
+}
+
+fn string_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return i + c.len_utf8(),
+            _ => (),
+        }
+    }
+    text.len()
+}
+
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            "<span class=\"hl-keyword\">fn</span> lifetime&lt;&#39;a&gt;() -&gt; u8 { \
+             <span class=\"hl-number\">42</span> } <span class=\"hl-comment\">// answer</span>\n\
+             <span class=\"hl-string\">&quot;a \\&quot; b&quot;</span>",
+            highlight("rs", "fn lifetime<'a>() -> u8 { 42 } // answer\n\"a \\\" b\"")
+        );
+        assert_eq!(
+            "<span class=\"hl-keyword\">def</span> poker(): \
+             <span class=\"hl-comment\"># todo</span>",
+            highlight("py", "def poker(): # todo")
+        );
+        assert_eq!(
+            "<span class=\"hl-comment\">{- multi\nline -}</span> x",
+            highlight("hs", "{- multi\nline -} x")
+        );
+        assert_eq!(
+            "<span class=\"hl-string\">&quot;unterminated</span>",
+            highlight("js", "\"unterminated")
+        );
+        assert_eq!("fn &lt;data&gt;", highlight("txt", "fn <data>"));
+    }
+}
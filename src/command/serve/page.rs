@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::command::serve::highlight::highlight;
+
+const STYLESHEET: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; padding: 0 1em; }
+nav { margin-bottom: 1em; }
+ul { list-style: none; padding: 0; }
+li { padding: 0.2em 0; }
+a { color: #604fcd; text-decoration: none; }
+a:hover { text-decoration: underline; }
+pre { background: #f6f5fa; border-radius: 4px; overflow-x: auto; padding: 1em; }
+.hl-comment { color: #6a737d; font-style: italic; }
+.hl-string { color: #22863a; }
+.hl-number { color: #005cc5; }
+.hl-keyword { color: #d73a49; font-weight: bold; }
+";
+
+/// Entry of a directory listed by [`directory_page`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DirEntry {
+    pub is_file: bool,
+    pub name: String,
+}
+
+/// Renders the listing of a directory of the backup.
+///
+/// `segments` is the path of the directory, relative to the backup directory.
+pub fn directory_page(segments: &[String], entries: &[DirEntry]) -> String {
+    let mut body = String::new();
+    if entries.is_empty() {
+        body.push_str("<p>This directory is empty.</p>\n");
+    } else {
+        body.push_str("<ul>\n");
+        for entry in entries {
+            let suffix = if entry.is_file { "" } else { "/" };
+            let _ = writeln!(
+                body,
+                "<li><a href=\"{}{suffix}\">{}{suffix}</a></li>",
+                percent_encode(&entry.name),
+                escape(&entry.name),
+            );
+        }
+        body.push_str("</ul>\n");
+    }
+
+    layout(segments, &body)
+}
+
+/// Renders the content of a file of the backup.
+///
+/// `segments` is the path of the file, relative to the backup directory.
+pub fn file_page(segments: &[String], content: &str) -> String {
+    let extension = segments
+        .last()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension)
+        .unwrap_or_default();
+    let body = format!(
+        "<p><a href=\"?raw\">Raw file</a></p>\n<pre><code class=\"language-{}\">{}</code></pre>\n",
+        escape(extension),
+        highlight(extension, content),
+    );
+
+    layout(segments, &body)
+}
+
+fn layout(segments: &[String], body: &str) -> String {
+    let title = match segments.last() {
+        Some(name) => format!("{} - auxiliaire", escape(name)),
+        None => "auxiliaire".into(),
+    };
+
+    let mut nav = String::from("<a href=\"/\">backup</a>");
+    let mut href = String::from("/");
+    for segment in segments {
+        href.push_str(&percent_encode(segment));
+        href.push('/');
+        let _ = write!(nav, " / <a href=\"{href}\">{}</a>", escape(segment));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLESHEET}</style>\n</head>\n<body>\n<nav>{nav}</nav>\n{body}</body>\n</html>\n"
+    )
+}
+
+/// Escapes characters that have a special meaning in HTML.
+pub fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Encodes a path segment so that it can be used in a URL.
+pub fn percent_encode(segment: &str) -> Cow<'_, str> {
+    let is_safe = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~');
+    if segment.bytes().all(is_safe) {
+        return Cow::Borrowed(segment);
+    }
+
+    let mut encoded = String::with_capacity(segment.len() * 3);
+    for b in segment.bytes() {
+        if is_safe(b) {
+            encoded.push(b as char);
+        } else {
+            let _ = write!(encoded, "%{b:02X}");
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+/// Decodes a path segment of a URL; returns `None` if the segment is not properly encoded.
+pub fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("fn main() {}", escape("fn main() {}"));
+        assert_eq!(
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; &#39;d&#39;",
+            escape("a < b && \"c\" > 'd'")
+        );
+    }
+
+    #[test]
+    fn test_percent_encoding() {
+        assert_eq!("lib.rs", percent_encode("lib.rs"));
+        assert_eq!("my%20file%23.txt", percent_encode("my file#.txt"));
+        assert_eq!(Some("my file#.txt".to_string()), percent_decode("my%20file%23.txt"));
+        assert_eq!(None, percent_decode("bad%2"));
+        assert_eq!(None, percent_decode("bad%zz"));
+    }
+
+    #[test]
+    fn test_directory_page() {
+        let segments = vec!["rust".to_string(), "poker".to_string()];
+        let entries = vec![
+            DirEntry { is_file: false, name: "src".into() },
+            DirEntry { is_file: true, name: "Cargo.toml".into() },
+        ];
+
+        let page = directory_page(&segments, &entries);
+        assert!(page.contains("<title>poker - auxiliaire</title>"));
+        assert!(page.contains("<a href=\"/rust/poker/\">poker</a>"));
+        assert!(page.contains("<li><a href=\"src/\">src/</a></li>"));
+        assert!(page.contains("<li><a href=\"Cargo.toml\">Cargo.toml</a></li>"));
+
+        assert!(directory_page(&[], &[]).contains("This directory is empty."));
+    }
+
+    #[test]
+    fn test_file_page() {
+        let segments = vec!["rust".to_string(), "poker".to_string(), "lib.rs".to_string()];
+
+        let page = file_page(&segments, "fn a() -> Vec<u8> {}");
+        assert!(page.contains(
+            "<code class=\"language-rs\"><span class=\"hl-keyword\">fn</span> a() -&gt; Vec&lt;u8&gt; {}</code>"
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+//! Definition of the [`Size`](crate::command::Command::Size) command.
+
+pub mod args;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+use std::path::Path;
+
+use anstream::println;
+use anyhow::Context;
+use itertools::Itertools;
+use tokio::fs;
+use tracing::{instrument, trace};
+
+use crate::command::backup::iterations::get_iterations_dir_name;
+use crate::command::backup::state::AUXILIAIRE_STATE_DIR_NAME;
+use crate::command::size::args::SizeArgs;
+use crate::style::{HEADER, NAME, SIZE};
+use crate::walk::subdirectories;
+use crate::Result;
+
+/// Command wrapper used for the [`Size`](crate::command::Command::Size) command.
+#[derive(Debug)]
+pub struct SizeCommand {
+    args: SizeArgs,
+    iterations_dir_name: String,
+}
+
+impl SizeCommand {
+    /// Creates a new [`SizeCommand`] using the provided [`args`](SizeArgs).
+    pub fn new(args: SizeArgs) -> Self {
+        Self { args, iterations_dir_name: get_iterations_dir_name() }
+    }
+
+    /// Execute the size report.
+    ///
+    /// Scans the backup directory and prints a summary of disk usage per track, along with
+    /// the biggest solutions found.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        let solutions = self.get_solution_sizes().await?;
+
+        let total = solutions
+            .iter()
+            .fold(UsedSpace::default(), |acc, s| acc + s.space);
+        println!(
+            "Backup size for {NAME}{}{NAME:#}: {} in {} solution(s)",
+            self.args.path.display(),
+            total,
+            solutions.len()
+        );
+
+        let tracks =
+            solutions
+                .iter()
+                .fold(BTreeMap::<&str, (UsedSpace, usize)>::new(), |mut tracks, s| {
+                    let (space, count) = tracks.entry(s.track.as_str()).or_default();
+                    *space = *space + s.space;
+                    *count += 1;
+                    tracks
+                });
+        if !tracks.is_empty() {
+            println!("\n{HEADER}Per track{HEADER:#}");
+            for (track, (space, count)) in tracks {
+                println!("  {NAME}{track}{NAME:#}: {space} in {count} solution(s)");
+            }
+        }
+
+        if self.args.top > 0 && !solutions.is_empty() {
+            println!("\n{HEADER}Biggest solutions{HEADER:#}");
+            for solution in solutions
+                .iter()
+                .sorted_by(|a, b| b.space.total().cmp(&a.space.total()))
+                .take(self.args.top)
+            {
+                println!(
+                    "  {NAME}{}/{}{NAME:#}: {}",
+                    solution.track, solution.exercise, solution.space
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn get_solution_sizes(&self) -> Result<Vec<SolutionSize>> {
+        let mut solutions = Vec::new();
+
+        for (track, track_path) in subdirectories(&self.args.path).await? {
+            for (exercise, solution_path) in subdirectories(&track_path).await? {
+                let space = self.get_solution_space(&solution_path).await?;
+                trace!(track, exercise, ?space);
+
+                solutions.push(SolutionSize { track: track.clone(), exercise, space });
+            }
+        }
+
+        Ok(solutions)
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn get_solution_space(&self, solution_path: &Path) -> Result<UsedSpace> {
+        let mut space = UsedSpace::default();
+
+        let mut entries = fs::read_dir(solution_path).await.with_context(|| {
+            format!("failed to list content of solution directory {}", solution_path.display())
+        })?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            if name == AUXILIAIRE_STATE_DIR_NAME {
+                continue;
+            }
+
+            let size = disk_usage(&entry.path()).await?;
+            if name == self.iterations_dir_name.as_str() {
+                space.iterations += size;
+            } else {
+                space.files += size;
+            }
+        }
+
+        Ok(space)
+    }
+}
+
+/// Disk space used by a backed up solution.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct UsedSpace {
+    /// Number of bytes used by the solution's current files.
+    pub files: u64,
+
+    /// Number of bytes used by the solution's iterations.
+    pub iterations: u64,
+}
+
+impl UsedSpace {
+    /// Total number of bytes used.
+    pub fn total(&self) -> u64 {
+        self.files + self.iterations
+    }
+}
+
+impl Add for UsedSpace {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self { files: self.files + rhs.files, iterations: self.iterations + rhs.iterations }
+    }
+}
+
+impl Display for UsedSpace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{SIZE}{}{SIZE:#} (files: {}, iterations: {})",
+            human_size(self.total()),
+            human_size(self.files),
+            human_size(self.iterations)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SolutionSize {
+    track: String,
+    exercise: String,
+    space: UsedSpace,
+}
+
+/// Formats a number of bytes in a human-readable way (e.g. `1.5 MiB`).
+pub(crate) fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+async fn disk_usage(path: &Path) -> Result<u64> {
+    let mut total = 0;
+    let mut to_visit = vec![path.to_path_buf()];
+
+    while let Some(path) = to_visit.pop() {
+        let metadata = fs::symlink_metadata(&path)
+            .await
+            .with_context(|| format!("failed to get metadata for {}", path.display()))?;
+
+        if metadata.is_dir() {
+            let mut entries = fs::read_dir(&path).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                to_visit.push(entry.path());
+            }
+        } else {
+            total += metadata.len();
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod human_size {
+        use super::*;
+
+        #[test]
+        fn test_all() {
+            assert_eq!("0 B", human_size(0));
+            assert_eq!("1023 B", human_size(1023));
+            assert_eq!("1.0 KiB", human_size(1024));
+            assert_eq!("1.5 KiB", human_size(1536));
+            assert_eq!("1.0 MiB", human_size(1024 * 1024));
+            assert_eq!("2.0 GiB", human_size(2 * 1024 * 1024 * 1024));
+        }
+    }
+
+    mod size_command {
+        use std::path::PathBuf;
+
+        use super::*;
+        use crate::command::backup::snapshot::solution_snapshots_path;
+
+        fn test_backup_path(part: &str) -> PathBuf {
+            [env!("CARGO_MANIFEST_DIR"), "resources", "tests", part]
+                .iter()
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_get_solution_sizes() {
+            let command = SizeCommand::new(SizeArgs {
+                path: test_backup_path("without_backup_state"),
+                top: 10,
+            });
+
+            let solutions = command.get_solution_sizes().await.unwrap();
+            assert_eq!(1, solutions.len());
+            assert_eq!("rust", solutions[0].track);
+            assert_eq!("poker", solutions[0].exercise);
+            assert_eq!(UsedSpace::default(), solutions[0].space);
+        }
+
+        #[tokio::test]
+        async fn test_state_is_ignored() {
+            let command =
+                SizeCommand::new(SizeArgs { path: test_backup_path("with_backup_state"), top: 10 });
+
+            let solutions = command.get_solution_sizes().await.unwrap();
+            assert_eq!(1, solutions.len());
+            assert_eq!(0, solutions[0].space.total());
+        }
+
+        #[tokio::test]
+        async fn test_solution_snapshots_are_ignored() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let solution_path = temp_dir.path().join("rust").join("poker");
+            fs::create_dir_all(&solution_path).await.unwrap();
+            fs::write(solution_path.join("lib.rs"), "pub fn new() {}")
+                .await
+                .unwrap();
+            let snapshot_path = solution_snapshots_path(temp_dir.path(), "rust", "poker")
+                .join("2023-05-07T053543Z");
+            fs::create_dir_all(&snapshot_path).await.unwrap();
+            fs::write(snapshot_path.join("lib.rs"), "pub fn old_and_longer() {}")
+                .await
+                .unwrap();
+
+            let command =
+                SizeCommand::new(SizeArgs { path: temp_dir.path().to_path_buf(), top: 10 });
+
+            let solutions = command.get_solution_sizes().await.unwrap();
+            assert_eq!(1, solutions.len());
+            assert_eq!("poker", solutions[0].exercise);
+            assert_eq!(15, solutions[0].space.total());
+        }
+    }
+}
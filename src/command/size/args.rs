@@ -0,0 +1,17 @@
+//! Arguments that can be passed to the [`Size`](crate::command::Command::Size) command.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Command-line arguments accepted by the [`Size`](crate::command::Command::Size) command.
+#[derive(Debug, Clone, Args)]
+pub struct SizeArgs {
+    /// Path where solutions have been backed up
+    #[arg(env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Number of biggest solutions to list
+    #[arg(short = 'n', long, default_value_t = 10, env = "AUXILIAIRE_TOP")]
+    pub top: usize,
+}
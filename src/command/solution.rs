@@ -0,0 +1,285 @@
+//! Definition of the [`Solution`](crate::command::Command::Solution) command.
+
+pub mod args;
+
+use std::io::Write;
+
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use mini_exercism::api;
+use mini_exercism::api::v2::iteration::Iteration;
+use mini_exercism::api::v2::solution::Solution;
+use mini_exercism::api::v2::solutions;
+use serde::Serialize;
+use tracing::{instrument, trace};
+
+use crate::command::args::SolutionRef;
+use crate::command::backup::state::BackupState;
+use crate::command::get_credentials;
+use crate::command::solution::args::{
+    SolutionAction, SolutionArgs, SolutionFormat, SolutionShowArgs,
+};
+use crate::style::{HEADER, NAME};
+use crate::Result;
+
+/// Command wrapper used for the [`Solution`](crate::command::Command::Solution) command.
+#[derive(Debug)]
+pub struct SolutionCommand {
+    args: SolutionArgs,
+    v2_client: api::v2::Client,
+}
+
+/// Information about a solution, as shown by the [`Show`](SolutionAction::Show) operation.
+#[derive(Debug, Clone, Serialize)]
+struct SolutionInfo {
+    solution: Solution,
+    iterations: Vec<Iteration>,
+    backup_state: Option<BackupState>,
+}
+
+impl SolutionCommand {
+    /// Creates a new [`SolutionCommand`] using the provided [`args`](SolutionArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: SolutionArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let (token, token_command) = match &args.action {
+            SolutionAction::Show(show_args) => (&show_args.token, &show_args.token_command),
+        };
+        let credentials = get_credentials(token.as_deref(), token_command.as_deref())?;
+
+        let mut builder = api::v2::Client::builder();
+        builder.credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v2_client = builder.build()?;
+
+        Ok(Self { args, v2_client })
+    }
+
+    /// Perform the requested [solution operation](SolutionArgs::action).
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        match &self.args.action {
+            SolutionAction::Show(show_args) => {
+                let info = self.get_solution_info(show_args).await?;
+                write_solution_info(&mut anstream::stdout(), show_args, &info)
+            },
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(solution = %args.solution))]
+    async fn get_solution_info(&self, args: &SolutionShowArgs) -> Result<SolutionInfo> {
+        let backup_state = BackupState::load(&args.solution.path_in(&args.path)).await;
+        trace!(?backup_state);
+
+        let uuid = solution_uuid(&self.v2_client, &args.solution, backup_state.as_ref()).await?;
+
+        let response = self
+            .v2_client
+            .get_solution(&uuid, true)
+            .await
+            .with_context(|| format!("failed to get solution to {}", args.solution))?;
+
+        Ok(SolutionInfo {
+            solution: response.solution,
+            iterations: response.iterations,
+            backup_state,
+        })
+    }
+}
+
+/// Returns the UUID of the given solution.
+///
+/// If the solution has been backed up, its UUID is taken from its backup state; otherwise,
+/// the solution is looked up on Exercism.
+pub(crate) async fn solution_uuid(
+    v2_client: &api::v2::Client,
+    solution_ref: &SolutionRef,
+    backup_state: Option<&BackupState>,
+) -> Result<String> {
+    if let Some(state) = backup_state {
+        return Ok(state.uuid.clone());
+    }
+
+    let filters = solutions::Filters::builder()
+        .track(solution_ref.track.as_str())
+        .criteria(solution_ref.exercise.as_str())
+        .build();
+
+    let mut page = 1;
+    loop {
+        let response = v2_client
+            .get_solutions(Some(filters.clone()), Some(solutions::Paging::for_page(page)), None)
+            .await
+            .with_context(|| format!("failed to fetch solutions for page {page}"))?;
+
+        if let Some(solution) = response.results.into_iter().find(|solution| {
+            solution.track.name == solution_ref.track
+                && solution.exercise.name == solution_ref.exercise
+        }) {
+            return Ok(solution.uuid);
+        }
+
+        if response.meta.current_page >= response.meta.total_pages {
+            return Err(anyhow!("solution to {solution_ref} not found on Exercism"));
+        }
+        page += 1;
+    }
+}
+
+fn write_solution_info<W: Write>(
+    output: &mut W,
+    args: &SolutionShowArgs,
+    info: &SolutionInfo,
+) -> Result<()> {
+    if args.format == SolutionFormat::Json {
+        serde_json::to_writer_pretty(&mut *output, info)
+            .with_context(|| format!("failed to serialize solution to {}", args.solution))?;
+        writeln!(output)?;
+        return Ok(());
+    }
+
+    let solution = &info.solution;
+    writeln!(output, "{NAME}{}{NAME:#}", args.solution)?;
+    writeln!(output, "  UUID: {}", solution.uuid)?;
+    writeln!(output, "  Status: {}", solution.status)?;
+    writeln!(output, "  Mentoring status: {}", solution.mentoring_status)?;
+    writeln!(
+        output,
+        "  Published iteration tests status: {}",
+        solution.published_iteration_head_tests_status
+    )?;
+    writeln!(output, "  Private URL: {}", solution.private_url)?;
+    writeln!(output, "  Public URL: {}", solution.public_url)?;
+    if let Some(last_iterated_at) = &solution.last_iterated_at {
+        writeln!(output, "  Last iterated at: {last_iterated_at}")?;
+    }
+
+    writeln!(output, "\n{HEADER}Iterations{HEADER:#}")?;
+    for iteration in &info.iterations {
+        let published = if iteration.is_published { ", published" } else { "" };
+        writeln!(
+            output,
+            "  {}: {}, tests {}{published}",
+            iteration.index, iteration.created_at, iteration.tests_status
+        )?;
+    }
+
+    writeln!(output, "\n{HEADER}Local backup{HEADER:#}")?;
+    match &info.backup_state {
+        None => writeln!(output, "  Not backed up")?,
+        Some(state) => {
+            let status = match state.needs_update(solution) {
+                Ok(false) => "up-to-date".to_string(),
+                Ok(true) => "outdated".to_string(),
+                Err(err) => format!("inconsistent ({err})"),
+            };
+            writeln!(output, "  Path: {}", args.solution.path_in(&args.path).display())?;
+            writeln!(output, "  Status: {status}")?;
+            if let Some(iterations) = &state.iterations {
+                writeln!(output, "  Backed up iterations: {}", iterations.iter().join(", "))?;
+            }
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::test_fixtures;
+
+    const POKER_UUID: &str = "00c717b68e1b4213b316df82636f5e0f";
+
+    fn solution_json(exercise: &str, uuid: &str) -> serde_json::Value {
+        test_fixtures::solution_json("rust", exercise, json!({ "uuid": uuid }))
+    }
+
+    async fn get_mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method(Method::GET))
+            .and(path("/solutions"))
+            .and(query_param("track_slug", "rust"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [solution_json("poker", POKER_UUID)],
+                "meta": { "current_page": 1, "total_count": 1, "total_pages": 1 },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method(Method::GET))
+            .and(path(format!("/solutions/{POKER_UUID}")))
+            .and(query_param("sideload", "iterations"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "solution": solution_json("poker", POKER_UUID),
+                "iterations": [],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    fn get_args(solution: &str, part: &str) -> SolutionShowArgs {
+        SolutionShowArgs {
+            solution: solution.parse().unwrap(),
+            path: [env!("CARGO_MANIFEST_DIR"), "resources", "tests", part]
+                .iter()
+                .collect::<PathBuf>(),
+            token: Some("some_token".into()),
+            token_command: None,
+            format: SolutionFormat::Text,
+        }
+    }
+
+    async fn get_solution_info(args: SolutionShowArgs) -> Result<SolutionInfo> {
+        let mock_server = get_mock_server().await;
+        let command = SolutionCommand::new(
+            SolutionArgs { action: SolutionAction::Show(args.clone()) },
+            Some(&mock_server.uri()),
+        )
+        .unwrap();
+
+        command.get_solution_info(&args).await
+    }
+
+    #[test(tokio::test)]
+    async fn test_backed_up() {
+        let args = get_args("rust/poker", "with_backup_state");
+        let info = get_solution_info(args.clone()).await.unwrap();
+        assert_eq!(POKER_UUID, info.solution.uuid);
+        assert!(info.backup_state.is_some());
+
+        let mut output = Vec::new();
+        write_solution_info(&mut output, &args, &info).unwrap();
+        let output = anstream::adapter::strip_str(&String::from_utf8(output).unwrap()).to_string();
+        assert!(output.contains("Status: up-to-date"));
+    }
+
+    #[test(tokio::test)]
+    async fn test_not_backed_up() {
+        let info = get_solution_info(get_args("rust/poker", "without_backup_state"))
+            .await
+            .unwrap();
+        assert_eq!(POKER_UUID, info.solution.uuid);
+        assert!(info.backup_state.is_none());
+    }
+
+    #[test(tokio::test)]
+    async fn test_not_found() {
+        assert!(get_solution_info(get_args("rust/clock", "without_backup_state"))
+            .await
+            .is_err());
+    }
+}
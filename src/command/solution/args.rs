@@ -0,0 +1,55 @@
+//! Arguments that can be passed to the [`Solution`](crate::command::Command::Solution) command.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::command::args::SolutionRef;
+
+/// Command-line arguments accepted by the [`Solution`](crate::command::Command::Solution) command.
+#[derive(Debug, Clone, Args)]
+pub struct SolutionArgs {
+    /// Operation to perform on the solution
+    #[command(subcommand)]
+    pub action: SolutionAction,
+}
+
+/// Possible operations on a solution (see [`SolutionArgs::action`]).
+#[derive(Debug, Clone, Subcommand)]
+pub enum SolutionAction {
+    /// Show the metadata of a solution, along with its local backup state
+    Show(SolutionShowArgs),
+}
+
+/// Arguments of the [`Show`](SolutionAction::Show) operation.
+#[derive(Debug, Clone, Args)]
+pub struct SolutionShowArgs {
+    /// Solution to show, in the form <track>/<exercise> (e.g. rust/poker)
+    pub solution: SolutionRef,
+
+    /// Path where solutions have been backed up
+    #[arg(short, long, default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = SolutionFormat::Text, env = "AUXILIAIRE_FORMAT")]
+    pub format: SolutionFormat,
+}
+
+/// Possible output formats for solution metadata (see [`SolutionShowArgs::format`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum SolutionFormat {
+    /// Human-readable output
+    Text,
+
+    /// JSON object including the solution, its iterations and its backup state
+    Json,
+}
@@ -0,0 +1,455 @@
+//! Definition of the [`Status`](crate::command::Command::Status) command.
+
+pub mod args;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anstream::println;
+use anyhow::Context;
+use mini_exercism::api;
+use mini_exercism::api::v2::solution::Solution;
+use mini_exercism::api::v2::solutions;
+use tokio::fs;
+use tracing::{info, instrument, trace};
+
+use crate::command::backup::history::BackupHistory;
+use crate::command::backup::iterations::get_iterations_dir_name;
+use crate::command::backup::paths::ShortenedPaths;
+use crate::command::backup::state::{BackupState, LastIterationMarker, BACKUP_STATE_FILE_NAME};
+use crate::command::get_credentials;
+use crate::command::status::args::StatusArgs;
+use crate::style::{HEADER, NAME, STALE};
+use crate::walk::subdirectories;
+use crate::Result;
+
+/// Command wrapper used for the [`Status`](crate::command::Command::Status) command.
+#[derive(Debug)]
+pub struct StatusCommand {
+    args: StatusArgs,
+    v2_client: Option<api::v2::Client>,
+    iterations_dir_name: String,
+}
+
+impl StatusCommand {
+    /// Creates a new [`StatusCommand`] using the provided [`args`](StatusArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    /// In [offline](StatusArgs::offline) mode, no API client is created.
+    pub fn new(args: StatusArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let v2_client = if args.offline {
+            None
+        } else {
+            let mut builder = api::v2::Client::builder();
+            builder.credentials(get_credentials(
+                args.token.as_deref(),
+                args.token_command.as_deref(),
+            )?);
+            if let Some(api_base_url) = api_base_url {
+                builder.api_base_url(api_base_url);
+            }
+            Some(builder.build()?)
+        };
+
+        Ok(Self { args, v2_client, iterations_dir_name: get_iterations_dir_name() })
+    }
+
+    /// Report the status of backed up solutions.
+    ///
+    /// Each solution found in the backup directory is checked against its backup state. Unless in
+    /// [offline](StatusArgs::offline) mode, solutions are also compared with those on Exercism.org
+    /// to find solutions that are outdated or that have not been backed up yet.
+    ///
+    /// The time of the last backup of each solution and track is also reported; solutions and
+    /// tracks that have not been backed up in [some time](StatusArgs::stale_after) are flagged as stale.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        let statuses = self.get_statuses().await?;
+
+        if statuses.is_empty() {
+            info!("No solutions found");
+            return Ok(());
+        }
+
+        let now = unix_time(SystemTime::now());
+        let last_backups = self.get_last_backups(&statuses).await?;
+
+        let mut counts = BTreeMap::<&str, usize>::new();
+        let mut track_last_backups = BTreeMap::<&str, u64>::new();
+        for (key, status) in &statuses {
+            let (track, exercise) = key;
+            match last_backups.get(key) {
+                Some(&last_backup) => {
+                    let age = backup_age(last_backup, now);
+                    println!(
+                        "{NAME}{track}/{exercise}{NAME:#}: {status}, last backed up {}{}",
+                        format_age(age),
+                        self.stale_flag(age)
+                    );
+
+                    let track_last_backup = track_last_backups.entry(track).or_default();
+                    *track_last_backup = (*track_last_backup).max(last_backup);
+                    if self.args.is_stale(age) {
+                        *counts.entry("stale").or_default() += 1;
+                    }
+                },
+                None => println!("{NAME}{track}/{exercise}{NAME:#}: {status}"),
+            }
+            *counts.entry(status.summary()).or_default() += 1;
+        }
+
+        if !track_last_backups.is_empty() {
+            println!("\n{HEADER}Last backup per track{HEADER:#}");
+            for (track, last_backup) in track_last_backups {
+                let age = backup_age(last_backup, now);
+                println!("  {NAME}{track}{NAME:#}: {}{}", format_age(age), self.stale_flag(age));
+            }
+        }
+
+        println!("\n{HEADER}Summary{HEADER:#}");
+        for (summary, count) in counts {
+            println!("  {summary}: {count}");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the time of the last backup of each solution found locally, in seconds since the Unix epoch.
+    ///
+    /// The time is read from the backup directory's [history](BackupHistory); for solutions backed up
+    /// before it existed, the modification time of the solution's backup state file is used instead.
+    async fn get_last_backups(
+        &self,
+        statuses: &BTreeMap<(String, String), SolutionStatus>,
+    ) -> Result<BTreeMap<(String, String), u64>> {
+        let history = BackupHistory::load(&self.args.path).await?;
+
+        let mut last_backups = BTreeMap::new();
+        for key in statuses.keys() {
+            let (track, exercise) = key;
+            let last_backup = match history.last_backed_up(track, exercise) {
+                Some(last_backup) => Some(last_backup),
+                None => {
+                    let mut state_file_path = self.args.path.join(track).join(exercise);
+                    state_file_path.extend(BACKUP_STATE_FILE_NAME.split('/'));
+                    file_modified(&state_file_path).await
+                },
+            };
+            if let Some(last_backup) = last_backup {
+                last_backups.insert(key.clone(), last_backup);
+            }
+        }
+
+        Ok(last_backups)
+    }
+
+    fn stale_flag(&self, age: Duration) -> String {
+        if self.args.is_stale(age) {
+            format!(" {STALE}(stale){STALE:#}")
+        } else {
+            String::new()
+        }
+    }
+
+    async fn get_statuses(&self) -> Result<BTreeMap<(String, String), SolutionStatus>> {
+        let mut statuses = BTreeMap::new();
+
+        let mut local_states = BTreeMap::new();
+        for (track, track_path) in subdirectories(&self.args.path).await? {
+            for (exercise, solution_path) in subdirectories(&track_path).await? {
+                if self.args.solution_matches(&track, &exercise) {
+                    let state = BackupState::load(&solution_path).await;
+                    trace!(track, exercise, ?state);
+
+                    let status = match &state {
+                        None => SolutionStatus::NoState,
+                        Some(state) => self.check_local_state(state, &solution_path).await?,
+                    };
+                    statuses.insert((track.clone(), exercise.clone()), status);
+                    local_states.insert((track.clone(), exercise), state);
+                }
+            }
+        }
+
+        if let Some(v2_client) = &self.v2_client {
+            let mut remote_keys = Vec::new();
+            for solution in self.get_remote_solutions(v2_client).await? {
+                let key = (solution.track.name.clone(), solution.exercise.name.clone());
+                let status = match local_states.get(&key) {
+                    None => SolutionStatus::NotBackedUp,
+                    Some(None) => SolutionStatus::NoState,
+                    Some(Some(state)) => match state.needs_update(&solution) {
+                        Err(err) => SolutionStatus::Inconsistent(err.to_string()),
+                        Ok(true) => SolutionStatus::Outdated,
+                        Ok(false) => statuses
+                            .remove(&key)
+                            .map(SolutionStatus::into_up_to_date)
+                            .unwrap_or(SolutionStatus::UpToDate),
+                    },
+                };
+                statuses.insert(key.clone(), status);
+                remote_keys.push(key);
+            }
+
+            for (key, status) in statuses.iter_mut() {
+                if !remote_keys.contains(key) && !matches!(status, SolutionStatus::Inconsistent(_))
+                {
+                    *status = SolutionStatus::NotOnExercism;
+                }
+            }
+        }
+
+        Ok(statuses)
+    }
+
+    async fn check_local_state(
+        &self,
+        state: &BackupState,
+        solution_path: &Path,
+    ) -> Result<SolutionStatus> {
+        if let Some(files) = &state.files {
+            let shortened_paths = ShortenedPaths::load(solution_path).await?;
+            for file in files {
+                let mut file_path = solution_path.to_path_buf();
+                file_path.extend(shortened_paths.local_path(file).split('/'));
+                if !path_exists(&file_path).await {
+                    return Ok(SolutionStatus::Inconsistent(format!("file {file} is missing")));
+                }
+            }
+        }
+
+        if let Some(iterations) = &state.iterations {
+            let iterations_path = solution_path.join(&self.iterations_dir_name);
+            for iteration in iterations {
+                if !path_exists(&iterations_path.join(iteration.to_string())).await {
+                    return Ok(SolutionStatus::Inconsistent(format!(
+                        "iteration {iteration} is missing"
+                    )));
+                }
+            }
+        }
+
+        Ok(SolutionStatus::BackedUp(state.last_iteration_marker.clone()))
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn get_remote_solutions(&self, v2_client: &api::v2::Client) -> Result<Vec<Solution>> {
+        let mut solutions = Vec::new();
+
+        let mut page = 1;
+        loop {
+            let response = v2_client
+                .get_solutions(None, Some(solutions::Paging::for_page(page)), None)
+                .await
+                .with_context(|| format!("failed to fetch solutions for page {page}"))?;
+
+            solutions.extend(response.results.into_iter().filter(|solution| {
+                self.args
+                    .solution_matches(&solution.track.name, &solution.exercise.name)
+            }));
+
+            if response.meta.current_page >= response.meta.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(solutions)
+    }
+}
+
+/// Status of a solution, as reported by the [`Status`](crate::command::Command::Status) command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SolutionStatus {
+    /// Solution is backed up and consistent with its backup state (offline mode only).
+    BackedUp(LastIterationMarker),
+
+    /// Solution is backed up and up-to-date with Exercism.org.
+    UpToDate,
+
+    /// Solution is backed up, but has changed on Exercism.org since.
+    Outdated,
+
+    /// Solution exists on Exercism.org, but has not been backed up.
+    NotBackedUp,
+
+    /// Solution exists on Exercism.org, but not anymore (or not with the same name).
+    NotOnExercism,
+
+    /// Solution directory exists, but does not have a backup state.
+    NoState,
+
+    /// Solution's files do not match its backup state.
+    Inconsistent(String),
+}
+
+impl SolutionStatus {
+    fn into_up_to_date(self) -> Self {
+        match self {
+            Self::BackedUp(_) => Self::UpToDate,
+            status => status,
+        }
+    }
+
+    fn summary(&self) -> &'static str {
+        match self {
+            Self::BackedUp(_) => "backed up",
+            Self::UpToDate => "up-to-date",
+            Self::Outdated => "outdated",
+            Self::NotBackedUp => "not backed up",
+            Self::NotOnExercism => "not on Exercism",
+            Self::NoState => "no backup state",
+            Self::Inconsistent(_) => "inconsistent",
+        }
+    }
+}
+
+impl Display for SolutionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BackedUp(LastIterationMarker::LastIteratedAt(last_iterated_at)) => {
+                write!(f, "backed up (last iterated at {last_iterated_at})")
+            },
+            Self::BackedUp(LastIterationMarker::NumIterations(num_iterations)) => {
+                write!(f, "backed up ({num_iterations} iteration(s))")
+            },
+            Self::Inconsistent(reason) => write!(f, "inconsistent ({reason})"),
+            status => write!(f, "{}", status.summary()),
+        }
+    }
+}
+
+async fn path_exists(path: &Path) -> bool {
+    fs::try_exists(path).await.unwrap_or(false)
+}
+
+/// Returns the last modification time of the given file, in seconds since the Unix epoch.
+async fn file_modified(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).await.ok()?.modified().ok()?;
+    Some(unix_time(modified))
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn backup_age(last_backup: u64, now: u64) -> Duration {
+    Duration::from_secs(now.saturating_sub(last_backup))
+}
+
+pub(crate) const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Formats the age of a backup in a human-readable way (e.g. `3 day(s) ago`).
+fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        secs if secs < 3_600 => "less than an hour ago".into(),
+        secs if secs < SECONDS_PER_DAY => format!("{} hour(s) ago", secs / 3_600),
+        secs => format!("{} day(s) ago", secs / SECONDS_PER_DAY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::command::backup::snapshot::solution_snapshots_path;
+
+    fn get_args(part: &str) -> StatusArgs {
+        StatusArgs {
+            path: [env!("CARGO_MANIFEST_DIR"), "resources", "tests", part]
+                .iter()
+                .collect::<PathBuf>(),
+            token: None,
+            token_command: None,
+            track: vec![],
+            exercise: vec![],
+            offline: true,
+            stale_after: None,
+        }
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!("less than an hour ago", format_age(Duration::from_secs(59 * 60)));
+        assert_eq!("5 hour(s) ago", format_age(Duration::from_secs(5 * 3_600 + 42)));
+        assert_eq!("3 day(s) ago", format_age(Duration::from_secs(3 * SECONDS_PER_DAY + 7_200)));
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let mut args = get_args("with_backup_state");
+        assert!(!args.is_stale(Duration::from_secs(365 * SECONDS_PER_DAY)));
+
+        args.stale_after = Some(2);
+        assert!(!args.is_stale(Duration::from_secs(2 * SECONDS_PER_DAY)));
+        assert!(args.is_stale(Duration::from_secs(2 * SECONDS_PER_DAY + 1)));
+    }
+
+    mod offline {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_with_backup_state() {
+            let command = StatusCommand::new(get_args("with_backup_state"), None).unwrap();
+
+            let statuses = command.get_statuses().await.unwrap();
+            assert_eq!(
+                Some(&SolutionStatus::BackedUp(LastIterationMarker::LastIteratedAt(
+                    "2023-05-07T05:35:43Z".into()
+                ))),
+                statuses.get(&("rust".into(), "poker".into()))
+            );
+        }
+
+        #[tokio::test]
+        async fn test_last_backups() {
+            let command = StatusCommand::new(get_args("with_backup_state"), None).unwrap();
+
+            let statuses = command.get_statuses().await.unwrap();
+            let last_backups = command.get_last_backups(&statuses).await.unwrap();
+            assert!(last_backups.contains_key(&("rust".into(), "poker".into())));
+        }
+
+        #[tokio::test]
+        async fn test_without_backup_state() {
+            let command = StatusCommand::new(get_args("without_backup_state"), None).unwrap();
+
+            let statuses = command.get_statuses().await.unwrap();
+            assert_eq!(
+                Some(&SolutionStatus::NoState),
+                statuses.get(&("rust".into(), "poker".into()))
+            );
+        }
+
+        #[tokio::test]
+        async fn test_filtered() {
+            let mut args = get_args("with_backup_state");
+            args.track = vec!["clojure".into()];
+            let command = StatusCommand::new(args, None).unwrap();
+
+            assert!(command.get_statuses().await.unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_solution_snapshots_are_ignored() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let snapshot_path = solution_snapshots_path(temp_dir.path(), "rust", "poker")
+                .join("2023-05-07T053543Z");
+            fs::create_dir_all(&snapshot_path).await.unwrap();
+            fs::write(snapshot_path.join("lib.rs"), "pub fn old() {}")
+                .await
+                .unwrap();
+
+            let mut args = get_args("with_backup_state");
+            args.path = temp_dir.path().to_path_buf();
+            let command = StatusCommand::new(args, None).unwrap();
+
+            assert!(command.get_statuses().await.unwrap().is_empty());
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Arguments that can be passed to the [`Status`](crate::command::Command::Status) command.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+
+use crate::command::status::SECONDS_PER_DAY;
+
+/// Command-line arguments accepted by the [`Status`](crate::command::Command::Status) command.
+#[derive(Debug, Clone, Args)]
+pub struct StatusArgs {
+    /// Path where solutions have been backed up
+    #[arg(default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Only report on solutions in the given track(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_TRACK", value_delimiter = ',')]
+    pub track: Vec<String>,
+
+    /// Only report on solutions for the given exercise(s) (can be used multiple times)
+    #[arg(short, long, env = "AUXILIAIRE_EXERCISE", value_delimiter = ',')]
+    pub exercise: Vec<String>,
+
+    /// Only use local backup state and files, without contacting Exercism.org
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_OFFLINE")]
+    pub offline: bool,
+
+    /// Flag solutions and tracks that have not been backed up in the given number of days as stale
+    #[arg(long, value_name = "DAYS", env = "AUXILIAIRE_STALE_AFTER")]
+    pub stale_after: Option<u64>,
+}
+
+impl StatusArgs {
+    /// Determines if a backup made `age` ago is stale according to [`stale_after`](Self::stale_after).
+    pub fn is_stale(&self, age: Duration) -> bool {
+        self.stale_after
+            .is_some_and(|days| age > Duration::from_secs(days * SECONDS_PER_DAY))
+    }
+
+    /// Determines if the solution for the given track and exercise should be reported on.
+    pub fn solution_matches(&self, track_name: &str, exercise_name: &str) -> bool {
+        (self.track.is_empty() || self.track.iter().any(|t| t == track_name))
+            && (self.exercise.is_empty() || self.exercise.iter().any(|e| e == exercise_name))
+    }
+}
@@ -0,0 +1,305 @@
+//! Definition of the [`Tracks`](crate::command::Command::Tracks) command.
+
+pub mod args;
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use anyhow::Context;
+use mini_exercism::api;
+use mini_exercism::api::v2::solution::Solution;
+use mini_exercism::api::v2::{solution, solutions, tracks};
+use serde::Serialize;
+use tracing::{info, instrument, trace};
+
+use crate::command::get_credentials;
+use crate::command::tracks::args::{TracksArgs, TracksFormat};
+use crate::style::NAME;
+use crate::Result;
+
+/// Command wrapper used for the [`Tracks`](crate::command::Command::Tracks) command.
+#[derive(Debug)]
+pub struct TracksCommand {
+    args: TracksArgs,
+    v2_client: api::v2::Client,
+}
+
+/// Statistics about a track joined by the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TrackStats {
+    track: String,
+    title: String,
+    num_exercises: usize,
+    num_completed_exercises: usize,
+    num_published_exercises: usize,
+    last_activity: Option<String>,
+}
+
+impl TracksCommand {
+    /// Creates a new [`TracksCommand`] using the provided [`args`](TracksArgs).
+    ///
+    /// The `api_base_url` parameter should only be set to test using a different Exercism local endpoint.
+    pub fn new(args: TracksArgs, api_base_url: Option<&str>) -> Result<Self> {
+        let credentials = get_credentials(args.token.as_deref(), args.token_command.as_deref())?;
+
+        let mut builder = api::v2::Client::builder();
+        builder.credentials(credentials);
+        if let Some(api_base_url) = api_base_url {
+            builder.api_base_url(api_base_url);
+        }
+        let v2_client = builder.build()?;
+
+        Ok(Self { args, v2_client })
+    }
+
+    /// List the joined tracks.
+    ///
+    /// For each track, the number of completed and published exercises is printed, along with
+    /// the date of the last activity (e.g. the last iteration submitted) in the track.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        self.list(&mut anstream::stdout()).await
+    }
+
+    async fn list<W: Write>(&self, output: &mut W) -> Result<()> {
+        let tracks = self.get_track_stats().await?;
+
+        if tracks.is_empty() {
+            info!("No joined tracks found");
+        }
+        for track in &tracks {
+            self.write_track(output, track)?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_track_stats(&self) -> Result<Vec<TrackStats>> {
+        let filters = tracks::Filters::builder()
+            .status(tracks::StatusFilter::Joined)
+            .build();
+        let tracks = self
+            .v2_client
+            .get_tracks(Some(filters))
+            .await
+            .with_context(|| "failed to fetch joined tracks")?
+            .tracks;
+
+        let mut solutions_per_track = HashMap::<String, Vec<Solution>>::new();
+        for solution in self.get_solutions().await? {
+            solutions_per_track
+                .entry(solution.track.name.clone())
+                .or_default()
+                .push(solution);
+        }
+
+        Ok(tracks
+            .into_iter()
+            .filter(|track| track.is_joined)
+            .map(|track| {
+                let solutions = solutions_per_track.remove(&track.name).unwrap_or_default();
+                TrackStats {
+                    num_published_exercises: solutions
+                        .iter()
+                        .filter(|solution| solution.status == solution::Status::Published)
+                        .count(),
+                    last_activity: solutions.iter().map(last_activity).max().map(Into::into),
+                    track: track.name,
+                    title: track.title,
+                    num_exercises: track.num_exercises,
+                    num_completed_exercises: track.num_completed_exercises,
+                }
+            })
+            .collect())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn get_solutions(&self) -> Result<Vec<Solution>> {
+        let mut solutions = Vec::new();
+
+        let mut page = 1;
+        loop {
+            let response = self
+                .v2_client
+                .get_solutions(None, Some(solutions::Paging::for_page(page)), None)
+                .await
+                .with_context(|| format!("failed to fetch solutions for page {page}"))?;
+            trace!(?response.meta);
+
+            solutions.extend(response.results);
+            if response.meta.current_page >= response.meta.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(solutions)
+    }
+
+    fn write_track<W: Write>(&self, output: &mut W, track: &TrackStats) -> Result<()> {
+        match self.args.format {
+            TracksFormat::Text => {
+                let last_activity = track
+                    .last_activity
+                    .as_deref()
+                    .map(|date| format!(", last activity on {}", date_part(date)))
+                    .unwrap_or_default();
+                writeln!(
+                    output,
+                    "{NAME}{}{NAME:#} ({}): {}/{} exercise(s) completed, {} published{last_activity}",
+                    track.track,
+                    track.title,
+                    track.num_completed_exercises,
+                    track.num_exercises,
+                    track.num_published_exercises,
+                )?;
+            },
+            TracksFormat::Jsonl => {
+                serde_json::to_writer(&mut *output, track)
+                    .with_context(|| format!("failed to serialize track {}", track.track))?;
+                writeln!(output)?;
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the date of the last activity on a solution (as an RFC 3339 timestamp).
+fn last_activity(solution: &Solution) -> &str {
+    // Timestamps returned by the Exercism API are all in UTC, so they can be compared as strings.
+    solution
+        .last_iterated_at
+        .as_deref()
+        .map_or(solution.updated_at.as_str(), |last_iterated_at| {
+            last_iterated_at.max(solution.updated_at.as_str())
+        })
+}
+
+fn date_part(timestamp: &str) -> &str {
+    timestamp.split('T').next().unwrap_or(timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::test_fixtures;
+
+    fn track_json(track: &str, num_completed_exercises: usize) -> serde_json::Value {
+        json!({
+            "slug": track,
+            "title": track.to_uppercase(),
+            "num_concepts": 10,
+            "num_exercises": 50,
+            "web_url": format!("https://exercism.org/tracks/{track}"),
+            "icon_url": "",
+            "tags": [],
+            "links": { "self": "", "exercises": "", "concepts": "" },
+            "is_joined": true,
+            "num_learnt_concepts": 0,
+            "num_completed_exercises": num_completed_exercises,
+        })
+    }
+
+    fn solution_json(
+        track: &str,
+        exercise: &str,
+        status: &str,
+        updated_at: &str,
+    ) -> serde_json::Value {
+        test_fixtures::solution_json(
+            track,
+            exercise,
+            json!({ "status": status, "updated_at": updated_at, "last_iterated_at": null }),
+        )
+    }
+
+    async fn get_mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method(Method::GET))
+            .and(path("/tracks"))
+            .and(query_param("status", "joined"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tracks": [track_json("clojure", 0), track_json("rust", 2)],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let pages = [
+            vec![
+                solution_json("rust", "poker", "published", "2023-11-23T07:13:23Z"),
+                solution_json("rust", "clock", "completed", "2024-01-02T10:00:00Z"),
+            ],
+            vec![solution_json("rust", "luhn", "published", "2023-05-01T08:00:00Z")],
+        ];
+        for (i, results) in pages.iter().enumerate() {
+            Mock::given(method(Method::GET))
+                .and(path("/solutions"))
+                .and(query_param("page", (i + 1).to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "results": results,
+                    "meta": { "current_page": i + 1, "total_count": 3, "total_pages": pages.len() },
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        mock_server
+    }
+
+    fn get_args(format: TracksFormat) -> TracksArgs {
+        TracksArgs { token: Some("some_token".into()), token_command: None, format }
+    }
+
+    #[test(tokio::test)]
+    async fn test_text() {
+        let mock_server = get_mock_server().await;
+        let command =
+            TracksCommand::new(get_args(TracksFormat::Text), Some(&mock_server.uri())).unwrap();
+
+        let mut output = Vec::new();
+        command.list(&mut output).await.unwrap();
+
+        let output = anstream::adapter::strip_str(&String::from_utf8(output).unwrap()).to_string();
+        assert_eq!(
+            "clojure (CLOJURE): 0/50 exercise(s) completed, 0 published\n\
+             rust (RUST): 2/50 exercise(s) completed, 2 published, last activity on 2024-01-02\n",
+            output
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_jsonl() {
+        let mock_server = get_mock_server().await;
+        let command =
+            TracksCommand::new(get_args(TracksFormat::Jsonl), Some(&mock_server.uri())).unwrap();
+
+        let mut output = Vec::new();
+        command.list(&mut output).await.unwrap();
+
+        let tracks = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(2, tracks.len());
+        assert_eq!(
+            json!({
+                "track": "rust",
+                "title": "RUST",
+                "num_exercises": 50,
+                "num_completed_exercises": 2,
+                "num_published_exercises": 2,
+                "last_activity": "2024-01-02T10:00:00Z",
+            }),
+            tracks[1]
+        );
+    }
+}
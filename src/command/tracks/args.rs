@@ -0,0 +1,29 @@
+//! Arguments that can be passed to the [`Tracks`](crate::command::Command::Tracks) command.
+
+use clap::{Args, ValueEnum};
+
+/// Command-line arguments accepted by the [`Tracks`](crate::command::Command::Tracks) command.
+#[derive(Debug, Clone, Args)]
+pub struct TracksArgs {
+    /// Exercism.org API token; if unspecified, CLI token will be used instead
+    #[arg(long, env = "AUXILIAIRE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Command to run to obtain the Exercism.org API token (e.g. "pass show exercism/token")
+    #[arg(long, conflicts_with = "token", env = "AUXILIAIRE_TOKEN_COMMAND")]
+    pub token_command: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = TracksFormat::Text, env = "AUXILIAIRE_FORMAT")]
+    pub format: TracksFormat,
+}
+
+/// Possible output formats for the tracks list (see [`TracksArgs::format`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TracksFormat {
+    /// Human-readable list, one track per line
+    Text,
+
+    /// JSON Lines: one JSON object per track
+    Jsonl,
+}
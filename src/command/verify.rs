@@ -0,0 +1,381 @@
+//! Definition of the [`Verify`](crate::command::Command::Verify) command.
+
+pub mod args;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use anstream::println;
+use anyhow::{anyhow, bail, Context};
+use tokio::fs;
+use tracing::{info, instrument, trace, warn};
+
+use crate::command::backup::manifest::{
+    backup_files, hash_file, modified_nanos, signature_path, verify_manifest_signature, Manifest,
+};
+use crate::command::verify::args::VerifyArgs;
+use crate::style::{HEADER, NAME};
+use crate::Result;
+
+/// Command wrapper used for the [`Verify`](crate::command::Command::Verify) command.
+#[derive(Debug)]
+pub struct VerifyCommand {
+    args: VerifyArgs,
+}
+
+impl VerifyCommand {
+    /// Creates a new [`VerifyCommand`] using the provided [`args`](VerifyArgs).
+    pub fn new(args: VerifyArgs) -> Self {
+        Self { args }
+    }
+
+    /// Verify the integrity of backed up files.
+    ///
+    /// Every file in the backup directory is hashed again and compared with its entry in the
+    /// [manifest](VerifyArgs::manifest), without contacting Exercism.org. Fails if any file
+    /// does not match.
+    ///
+    /// If the manifest has been signed, its signature is checked first; if a [signer](VerifyArgs::signer)
+    /// is specified, the signature must have been made by that key.
+    #[instrument(skip_all)]
+    pub async fn execute(&self) -> Result<()> {
+        self.check_signature().await?;
+        let problems = self.get_problems().await?;
+
+        if problems.is_empty() {
+            info!("All files match the manifest");
+            return Ok(());
+        }
+
+        let mut counts = BTreeMap::<&str, usize>::new();
+        for (file, problem) in &problems {
+            println!("{NAME}{file}{NAME:#}: {problem}");
+            *counts.entry(problem.summary()).or_default() += 1;
+        }
+
+        println!("\n{HEADER}Summary{HEADER:#}");
+        for (summary, count) in counts {
+            println!("  {summary}: {count}");
+        }
+
+        bail!("{} file(s) do not match the manifest", problems.len())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn check_signature(&self) -> Result<()> {
+        let manifest_path = self.args.manifest_path();
+        let signature_path = signature_path(&manifest_path);
+
+        if fs::try_exists(&signature_path).await.unwrap_or(false) {
+            let signer = verify_manifest_signature(&manifest_path, &signature_path).await?;
+            match &self.args.signer {
+                Some(expected) if signer.is(expected) => {
+                    info!("Manifest signature is valid (signed by {expected})");
+                    Ok(())
+                },
+                Some(expected) => Err(anyhow!(
+                    "manifest {} was signed by key {}, not by the expected key {expected}",
+                    manifest_path.display(),
+                    signer.primary_fingerprint,
+                )),
+                None => {
+                    warn!(
+                        "Manifest signature is valid, but any key in the GPG keyring is accepted; use --signer {} to make sure it was signed by the expected key",
+                        signer.primary_fingerprint,
+                    );
+                    Ok(())
+                },
+            }
+        } else if self.args.require_signature || self.args.signer.is_some() {
+            Err(anyhow!("signature of manifest {} not found", manifest_path.display()))
+        } else {
+            trace!("Manifest is not signed");
+            Ok(())
+        }
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn get_problems(&self) -> Result<BTreeMap<String, FileProblem>> {
+        let manifest_path = self.args.manifest_path();
+        let mut manifest = Manifest::load_from(&manifest_path)
+            .await?
+            .ok_or_else(|| anyhow!("manifest {} not found", manifest_path.display()))?;
+
+        let mut problems = BTreeMap::new();
+        for (key, file_path) in backup_files(&self.args.path).await? {
+            let problem = match manifest.files.remove(&key) {
+                None => Some(FileProblem::Unexpected),
+                Some(entry) => {
+                    trace!(file = key, "Hashing file");
+                    if hash_file(&file_path, manifest.checksum).await? == entry.hash {
+                        None
+                    } else {
+                        let metadata = fs::metadata(&file_path).await.with_context(|| {
+                            format!("failed to read metadata of {}", file_path.display())
+                        })?;
+                        if metadata.len() == entry.size
+                            && modified_nanos(&metadata) == entry.modified
+                        {
+                            Some(FileProblem::Corrupted)
+                        } else {
+                            Some(FileProblem::Modified)
+                        }
+                    }
+                },
+            };
+
+            if let Some(problem) = problem {
+                problems.insert(key, problem);
+            }
+        }
+        problems.extend(
+            manifest
+                .files
+                .into_keys()
+                .map(|key| (key, FileProblem::Missing)),
+        );
+
+        Ok(problems)
+    }
+}
+
+/// Problem found with a backed up file by the [`Verify`](crate::command::Command::Verify) command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FileProblem {
+    /// File's content has changed since it was hashed, but its size and modification time have not,
+    /// which usually indicates disk corruption.
+    Corrupted,
+
+    /// File has been modified since it was hashed.
+    Modified,
+
+    /// File is listed in the manifest, but does not exist anymore.
+    Missing,
+
+    /// File exists, but is not listed in the manifest.
+    Unexpected,
+}
+
+impl FileProblem {
+    fn summary(&self) -> &'static str {
+        match self {
+            Self::Corrupted => "corrupted",
+            Self::Modified => "modified",
+            Self::Missing => "missing",
+            Self::Unexpected => "not in manifest",
+        }
+    }
+}
+
+impl Display for FileProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::command::backup::args::ChecksumAlgorithm;
+    use crate::command::backup::manifest::MANIFEST_FILE_NAME;
+
+    async fn create_backup(backup_path: &Path) -> Manifest {
+        fs::create_dir_all(backup_path.join("rust/poker"))
+            .await
+            .unwrap();
+        for file in ["corrupted.rs", "modified.rs", "missing.rs", "unchanged.rs"] {
+            fs::write(backup_path.join("rust/poker").join(file), "foo")
+                .await
+                .unwrap();
+        }
+
+        Manifest::update(backup_path, ChecksumAlgorithm::Blake3)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_problems() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let backup_path = temp_dir.path();
+        let mut manifest = create_backup(backup_path).await;
+
+        // Simulate bit-rot by altering the hash without touching size or modification time.
+        manifest
+            .files
+            .get_mut("rust/poker/corrupted.rs")
+            .unwrap()
+            .hash = "bad".into();
+        manifest.save(backup_path).await.unwrap();
+        fs::write(backup_path.join("rust/poker/modified.rs"), "foobar")
+            .await
+            .unwrap();
+        fs::remove_file(backup_path.join("rust/poker/missing.rs"))
+            .await
+            .unwrap();
+        fs::write(backup_path.join("rust/poker/unexpected.rs"), "foo")
+            .await
+            .unwrap();
+
+        let command = VerifyCommand::new(VerifyArgs {
+            path: backup_path.into(),
+            manifest: None,
+            require_signature: false,
+            signer: None,
+        });
+        let problems = command.get_problems().await.unwrap();
+        assert_eq!(
+            vec![
+                ("rust/poker/corrupted.rs", FileProblem::Corrupted),
+                ("rust/poker/missing.rs", FileProblem::Missing),
+                ("rust/poker/modified.rs", FileProblem::Modified),
+                ("rust/poker/unexpected.rs", FileProblem::Unexpected),
+            ],
+            problems
+                .iter()
+                .map(|(file, problem)| (file.as_str(), *problem))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_external_manifest() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let backup_path = temp_dir.path().join("backup");
+        let manifest = create_backup(&backup_path).await;
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::rename(backup_path.join(MANIFEST_FILE_NAME), &manifest_path)
+            .await
+            .unwrap();
+        assert_eq!(4, manifest.files.len());
+
+        let command = VerifyCommand::new(VerifyArgs {
+            path: backup_path.clone(),
+            manifest: Some(manifest_path),
+            require_signature: false,
+            signer: None,
+        });
+        assert!(command.get_problems().await.unwrap().is_empty());
+
+        let command = VerifyCommand::new(VerifyArgs {
+            path: backup_path,
+            manifest: None,
+            require_signature: false,
+            signer: None,
+        });
+        assert!(command.get_problems().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_signature() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let backup_path = temp_dir.path();
+        create_backup(backup_path).await;
+
+        let args = VerifyArgs {
+            path: backup_path.into(),
+            manifest: None,
+            require_signature: false,
+            signer: None,
+        };
+        assert!(VerifyCommand::new(args.clone())
+            .check_signature()
+            .await
+            .is_ok());
+
+        let command = VerifyCommand::new(VerifyArgs { require_signature: true, ..args.clone() });
+        let err = command.check_signature().await.unwrap_err();
+        assert!(err.to_string().contains("signature"), "{err}");
+
+        let command =
+            VerifyCommand::new(VerifyArgs { signer: Some("0123456789ABCDEF".into()), ..args });
+        let err = command.check_signature().await.unwrap_err();
+        assert!(err.to_string().contains("signature"), "{err}");
+    }
+
+    mod gpg {
+        use std::path::PathBuf;
+        use std::process::Command;
+
+        use super::*;
+        use crate::command::backup::manifest::sign_manifest;
+
+        /// Runs `gpg` with the given arguments in the given home directory, returning its output.
+        fn gpg(gnupg_home: &Path, args: &[&str]) -> String {
+            let output = Command::new("gpg")
+                .env("GNUPGHOME", gnupg_home)
+                .args(["--batch", "--passphrase", ""])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            String::from_utf8(output.stdout).unwrap()
+        }
+
+        /// Generates a throwaway signing key, returning its fingerprint.
+        fn generate_key(gnupg_home: &Path, user_id: &str) -> String {
+            gpg(gnupg_home, &["--quick-gen-key", user_id, "ed25519", "sign", "never"]);
+
+            gpg(gnupg_home, &["--with-colons", "--fingerprint", user_id])
+                .lines()
+                .find_map(|line| line.strip_prefix("fpr:"))
+                .and_then(|fields| fields.split(':').find(|field| !field.is_empty()))
+                .unwrap()
+                .to_owned()
+        }
+
+        #[tokio::test]
+        async fn test_check_signature() {
+            if Command::new("gpg").arg("--version").output().is_err() {
+                eprintln!("gpg is not installed; skipping test");
+                return;
+            }
+
+            // gpg-agent's socket is created in GNUPGHOME, whose path length is limited,
+            // so we cannot use a deeply-nested temporary directory.
+            let gnupg_home = tempfile::tempdir().unwrap();
+            let owner = generate_key(gnupg_home.path(), "Owner <owner@example.com>");
+            let attacker = generate_key(gnupg_home.path(), "Attacker <attacker@example.com>");
+            // `sign_manifest` and `verify_manifest_signature` run gpg with our environment.
+            std::env::set_var("GNUPGHOME", gnupg_home.path());
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            let backup_path = temp_dir.path();
+            create_backup(backup_path).await;
+            let check = |signer: Option<&str>| {
+                VerifyCommand::new(VerifyArgs {
+                    path: PathBuf::from(backup_path),
+                    manifest: None,
+                    require_signature: false,
+                    signer: signer.map(Into::into),
+                })
+            };
+
+            sign_manifest(backup_path, &owner).await.unwrap();
+            assert!(check(Some(&owner)).check_signature().await.is_ok());
+            assert!(check(None).check_signature().await.is_ok());
+
+            // Re-signing the manifest with another key in the keyring must be detected.
+            sign_manifest(backup_path, &attacker).await.unwrap();
+            let err = check(Some(&owner)).check_signature().await.unwrap_err();
+            assert!(err.to_string().contains(&attacker), "{err}");
+
+            // So must tampering with the manifest itself.
+            sign_manifest(backup_path, &owner).await.unwrap();
+            let manifest_path = backup_path.join(MANIFEST_FILE_NAME);
+            let manifest = fs::read_to_string(&manifest_path).await.unwrap();
+            fs::write(&manifest_path, manifest.replace("unchanged.rs", "renamed.rs"))
+                .await
+                .unwrap();
+            let err = check(Some(&owner)).check_signature().await.unwrap_err();
+            assert!(err.to_string().contains("invalid signature"), "{err}");
+
+            let _ = Command::new("gpgconf")
+                .env("GNUPGHOME", gnupg_home.path())
+                .args(["--kill", "gpg-agent"])
+                .output();
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Arguments that can be passed to the [`Verify`](crate::command::Command::Verify) command.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::command::backup::manifest::MANIFEST_FILE_NAME;
+
+/// Command-line arguments accepted by the [`Verify`](crate::command::Command::Verify) command.
+#[derive(Debug, Clone, Args)]
+pub struct VerifyArgs {
+    /// Path where solutions have been backed up
+    #[arg(default_value = ".", env = "AUXILIAIRE_PATH")]
+    pub path: PathBuf,
+
+    /// Manifest to verify files against; if unspecified, the manifest stored in the backup directory is used
+    #[arg(long, value_name = "FILE", env = "AUXILIAIRE_MANIFEST")]
+    pub manifest: Option<PathBuf>,
+
+    /// Fail if the manifest has not been signed (see the backup command's --sign-key); signatures are always checked when present
+    #[arg(long, default_value_t = false, env = "AUXILIAIRE_REQUIRE_SIGNATURE")]
+    pub require_signature: bool,
+
+    /// Fingerprint of the GPG key that must have signed the manifest; signatures made by any other key are rejected (implies --require-signature)
+    #[arg(long, value_name = "FINGERPRINT", env = "AUXILIAIRE_SIGNER")]
+    pub signer: Option<String>,
+}
+
+impl VerifyArgs {
+    /// Returns the path of the manifest to verify files against.
+    pub fn manifest_path(&self) -> PathBuf {
+        self.manifest
+            .clone()
+            .unwrap_or_else(|| self.path.join(MANIFEST_FILE_NAME))
+    }
+}
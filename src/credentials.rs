@@ -0,0 +1,212 @@
+//! Resolution of the [`Credentials`] used to access the Exercism API.
+//!
+//! Commands obtain their credentials through a [`CredentialsProvider`]. Library users can
+//! implement this trait (or pass a closure) to supply an API token from their own secret store
+//! instead of relying on the Exercism CLI configuration.
+//!
+//! auxiliaire does not read system keyrings itself, since doing so portably would require
+//! platform-specific dependencies. Programs that want to store the API token in a keyring can
+//! use a closure calling the keyring library of their choice:
+//!
+//! ```no_run
+//! use auxiliaire::command::backup::args::BackupArgs;
+//! use auxiliaire::command::backup::BackupCommand;
+//! use mini_exercism::core::Credentials;
+//!
+//! # fn read_keyring_secret(service: &str, user: &str) -> auxiliaire::Result<String> { unimplemented!() }
+//! # async fn perform_backup(args: BackupArgs) -> auxiliaire::Result<()> {
+//! let backup_command = BackupCommand::builder(args)
+//!     .credentials_provider(|| {
+//!         let api_token = read_keyring_secret("exercism", "api-token")?;
+//!         Ok(Credentials::from_api_token(api_token))
+//!     })
+//!     .build()?;
+//! BackupCommand::execute(backup_command).await
+//! # }
+//! ```
+//!
+//! Users of the `auxiliaire` program can achieve the same with `--token-command` and their
+//! platform's keyring tool (see [`TokenCommandCredentialsProvider`]).
+
+use std::fmt::{Debug, Formatter};
+use std::process::Stdio;
+
+use anyhow::{bail, Context};
+use mini_exercism::cli::get_cli_credentials;
+use mini_exercism::core::Credentials;
+
+use crate::Result;
+
+/// Trait implemented by types that can provide [`Credentials`] to access the Exercism API.
+///
+/// This trait is implemented for closures returning [`Result<Credentials>`], so custom providers
+/// can be created without declaring a new type.
+pub trait CredentialsProvider {
+    /// Returns the [`Credentials`] to use.
+    fn credentials(&self) -> Result<Credentials>;
+}
+
+impl<F> CredentialsProvider for F
+where
+    F: Fn() -> Result<Credentials>,
+{
+    fn credentials(&self) -> Result<Credentials> {
+        self()
+    }
+}
+
+/// [`CredentialsProvider`] using the API token configured for the Exercism CLI.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CliCredentialsProvider;
+
+impl CredentialsProvider for CliCredentialsProvider {
+    fn credentials(&self) -> Result<Credentials> {
+        get_cli_credentials().with_context(|| "failed to get Exercism CLI credentials")
+    }
+}
+
+/// [`CredentialsProvider`] using a known API token.
+#[derive(Clone)]
+pub struct TokenCredentialsProvider(String);
+
+impl TokenCredentialsProvider {
+    /// Creates a new provider for the given API token.
+    pub fn new<T: Into<String>>(token: T) -> Self {
+        Self(token.into())
+    }
+}
+
+impl Debug for TokenCredentialsProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Make sure the token does not end up in logs.
+        f.debug_struct("TokenCredentialsProvider")
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialsProvider for TokenCredentialsProvider {
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::from_api_token(self.0.as_str()))
+    }
+}
+
+/// [`CredentialsProvider`] running a command to obtain the API token.
+///
+/// The command is run through the system shell; its standard output (trimmed) is used as
+/// the API token.
+#[derive(Debug, Clone)]
+pub struct TokenCommandCredentialsProvider(String);
+
+impl TokenCommandCredentialsProvider {
+    /// Creates a new provider that will run the given command.
+    pub fn new<C: Into<String>>(token_command: C) -> Self {
+        Self(token_command.into())
+    }
+}
+
+impl CredentialsProvider for TokenCommandCredentialsProvider {
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::from_api_token(run_token_command(&self.0)?))
+    }
+}
+
+/// Returns the [`CredentialsProvider`] to use given the `--token` and `--token-command`
+/// command-line arguments.
+///
+/// If an API token is provided, it is used directly. Otherwise, if a token command is provided,
+/// it is run and its output is used as the API token. If neither is provided, the token
+/// configured for the Exercism CLI is used instead.
+pub fn credentials_provider(
+    token: Option<&str>,
+    token_command: Option<&str>,
+) -> Box<dyn CredentialsProvider> {
+    match (token, token_command) {
+        (Some(token), _) => Box::new(TokenCredentialsProvider::new(token)),
+        (None, Some(token_command)) => {
+            Box::new(TokenCommandCredentialsProvider::new(token_command))
+        },
+        (None, None) => Box::new(CliCredentialsProvider),
+    }
+}
+
+/// Runs the given command through the system shell and returns its trimmed standard output.
+fn run_token_command(token_command: &str) -> Result<String> {
+    let mut command = if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c");
+        command
+    };
+
+    let output = command
+        .arg(token_command)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("failed to run token command `{token_command}`"))?;
+    if !output.status.success() {
+        bail!("token command `{token_command}` failed ({})", output.status);
+    }
+
+    let token = String::from_utf8(output.stdout)
+        .with_context(|| format!("token command `{token_command}` did not output valid UTF-8"))?;
+    let token = token.trim();
+    if token.is_empty() {
+        bail!("token command `{token_command}` did not output a token");
+    }
+
+    Ok(token.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    mod token_credentials_provider {
+        use super::*;
+
+        #[test]
+        fn test_debug() {
+            let provider = TokenCredentialsProvider::new("some-secret-token");
+            assert!(!format!("{provider:?}").contains("some-secret-token"));
+        }
+    }
+
+    mod closure {
+        use super::*;
+
+        #[test]
+        fn test_credentials() {
+            let provider = || Ok(Credentials::from_api_token("some-token"));
+            assert_eq!(Credentials::from_api_token("some-token"), provider.credentials().unwrap());
+        }
+    }
+
+    mod run_token_command {
+        use super::*;
+
+        #[test]
+        fn test_success() {
+            assert_matches!(run_token_command("echo some-token"), Ok(token) if token == "some-token");
+        }
+
+        #[test]
+        fn test_failure() {
+            assert_matches!(run_token_command("exit 1"), Err(err) => {
+                assert!(err.to_string().contains("failed"));
+            });
+        }
+
+        #[test]
+        fn test_empty_output() {
+            assert_matches!(run_token_command("echo"), Err(err) => {
+                assert!(err.to_string().contains("did not output a token"));
+            });
+        }
+    }
+}
@@ -17,17 +17,51 @@ pub type Error = AnyhowError;
 /// Currently mapped to [`anyhow::Result`] in order to use our [`Error`] type.
 pub type Result<T> = AnyhowResult<T>;
 
+/// Exit code used when the program is [interrupted](Interrupted).
+///
+/// Follows the shell convention of `128 + SIGINT`.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Error returned when a command is interrupted by the user (e.g. via Ctrl-C).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Interrupted;
+
+impl Display for Interrupted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "interrupted")
+    }
+}
+
+impl StdError for Interrupted {}
+
 #[derive(Debug)]
-pub(crate) struct MultiError(Vec<Error>);
+pub(crate) struct MultiError {
+    errors: Vec<Error>,
+    labels: Vec<Option<String>>,
+}
 
 impl MultiError {
     #[must_use]
-    #[allow(dead_code)] // Only used in tests currently
     pub fn errors(&self) -> &[Error] {
-        &self.0
+        &self.errors
+    }
+
+    /// Returns the label of the task that produced each [error](Self::errors), if any.
+    #[must_use]
+    pub fn labels(&self) -> &[Option<String>] {
+        &self.labels
     }
 
+    #[allow(dead_code)] // Only used in tests currently
     pub fn check<C, F>(errors: Vec<Error>, context: F) -> Result<()>
+    where
+        F: FnOnce() -> C,
+        C: Display + Send + Sync + 'static,
+    {
+        Self::check_labeled(errors.into_iter().map(|error| (None, error)).collect(), context)
+    }
+
+    pub fn check_labeled<C, F>(errors: Vec<(Option<String>, Error)>, context: F) -> Result<()>
     where
         F: FnOnce() -> C,
         C: Display + Send + Sync + 'static,
@@ -35,7 +69,10 @@ impl MultiError {
         errors
             .is_empty()
             .then_some(())
-            .ok_or_else(|| MultiError(errors))
+            .ok_or_else(|| {
+                let (labels, errors) = errors.into_iter().unzip();
+                MultiError { errors, labels }
+            })
             .with_context(context)
     }
 }
@@ -43,10 +80,14 @@ impl MultiError {
 impl Display for MultiError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "multiple errors encountered:\n")?;
-        self.0
+        self.errors
             .iter()
+            .zip(&self.labels)
             .enumerate()
-            .try_fold((), |_, (i, error)| writeln!(f, "{i}: {error:?}\n"))
+            .try_fold((), |_, (i, (error, label))| match label {
+                Some(label) => writeln!(f, "{i} ({label}): {error:?}\n"),
+                None => writeln!(f, "{i}: {error:?}\n"),
+            })
     }
 }
 
@@ -76,6 +117,28 @@ mod tests {
             }
         }
 
+        mod check_labeled {
+            use anyhow::anyhow;
+            use assert_matches::assert_matches;
+
+            use crate::error::MultiError;
+
+            #[test]
+            fn test_labels() {
+                let errors =
+                    vec![(Some("rust/poker".to_string()), anyhow!("foo")), (None, anyhow!("bar"))];
+                let error = MultiError::check_labeled(errors, || "baz").unwrap_err();
+
+                assert_matches!(error.source(), Some(err) => {
+                    assert_matches!(err.downcast_ref::<MultiError>(), Some(multi_err) => {
+                        assert_eq!(&[Some("rust/poker".to_string()), None], multi_err.labels());
+                        assert!(multi_err.to_string().contains("0 (rust/poker): foo"));
+                        assert!(multi_err.to_string().contains("1: bar"));
+                    });
+                });
+            }
+        }
+
         mod check {
             use anyhow::anyhow;
             use assert_matches::assert_matches;
@@ -8,21 +8,51 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
 
+pub(crate) mod cache;
 pub mod command;
+pub mod credentials;
 pub mod error;
+#[cfg(not(feature = "bench"))]
 pub(crate) mod limiter;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod limiter;
+#[cfg(feature = "otel")]
+pub(crate) mod otel;
+pub(crate) mod style;
+#[cfg(not(feature = "bench"))]
 pub(crate) mod task_pool;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod task_pool;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+pub(crate) mod update_check;
+pub(crate) mod walk;
 
+use std::ffi::OsString;
+use std::io;
+use std::num::NonZeroUsize;
 use std::str::FromStr;
 
-use clap::Parser;
+use anyhow::Context;
+use clap::{ColorChoice, Parser};
 use clap_verbosity_flag::{InfoLevel, Verbosity};
 pub use error::Error;
 pub use error::Result;
+use tokio::runtime::{self, Runtime};
 use tracing_subscriber::filter::Directive;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
 
 use crate::command::Command;
+#[cfg(feature = "otel")]
+use crate::otel::TracesExporter;
+use crate::update_check::UpdateCheck;
+
+/// User agent sent with HTTP requests, in the form `auxiliaire/<version>`.
+pub(crate) const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 /// Main CLI application.
 ///
@@ -36,6 +66,40 @@ pub struct Cli {
     #[command(flatten)]
     pub verbose: Verbosity<InfoLevel>,
 
+    /// When to use colors in console output
+    ///
+    /// When set to `auto`, colors are used if the output is a terminal, unless
+    /// the `NO_COLOR` environment variable is set.
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto, env = "AUXILIAIRE_COLOR")]
+    pub color: ColorChoice,
+
+    /// Do not check whether a newer version of auxiliaire is available
+    #[arg(long, global = true, env = "AUXILIAIRE_NO_UPDATE_CHECK")]
+    pub no_update_check: bool,
+
+    /// Number of threads used to run auxiliaire's tasks; defaults to the number of CPU cores
+    ///
+    /// Lowering this can be useful on machines with limited resources, like small VPSes or NAS boxes.
+    #[arg(long, global = true, value_name = "N", env = "AUXILIAIRE_WORKER_THREADS")]
+    pub worker_threads: Option<NonZeroUsize>,
+
+    /// Run all of auxiliaire's tasks on the main thread
+    #[arg(
+        long,
+        global = true,
+        default_value_t = false,
+        conflicts_with = "worker_threads",
+        env = "AUXILIAIRE_SINGLE_THREADED"
+    )]
+    pub single_threaded: bool,
+
+    /// Export traces to the given OpenTelemetry (OTLP over HTTP) endpoint
+    ///
+    /// Only spans enabled by the current verbosity level are exported.
+    #[cfg(feature = "otel")]
+    #[arg(long, global = true, env = "AUXILIAIRE_OTEL_ENDPOINT")]
+    pub otel_endpoint: Option<String>,
+
     /// Command to be executed.
     #[command(subcommand)]
     pub command: Command,
@@ -44,8 +108,64 @@ pub struct Cli {
 impl Cli {
     /// Execute our CLI program.
     ///
-    /// This method parses the arguments, sets up [`tracing`] support and executes the appropriate
-    /// command. If parsing results in an error, [`clap`] reports it automatically to the user.
+    /// This method parses the program's arguments and [runs](Cli::run_with_update_check) the
+    /// appropriate command.
+    /// If parsing results in an error, [`clap`] reports it automatically to the user.
+    pub async fn execute() -> Result<()> {
+        Self::parse().run_with_update_check().await
+    }
+
+    /// Builds the [`tokio`] [`Runtime`] that should be used to [run](Self::run) this [`Cli`],
+    /// according to the [`worker_threads`](Self::worker_threads) and
+    /// [`single_threaded`](Self::single_threaded) arguments.
+    ///
+    /// This is used by the `auxiliaire` program; programs embedding auxiliaire can
+    /// use their own runtime instead.
+    pub fn runtime(&self) -> Result<Runtime> {
+        let mut builder = if self.single_threaded {
+            runtime::Builder::new_current_thread()
+        } else {
+            let mut builder = runtime::Builder::new_multi_thread();
+            if let Some(worker_threads) = self.worker_threads {
+                builder.worker_threads(worker_threads.get());
+            }
+            builder
+        };
+
+        builder
+            .enable_all()
+            .build()
+            .with_context(|| "failed to create tokio runtime")
+    }
+
+    /// Parses the given arguments to create a [`Cli`].
+    ///
+    /// This can be used by other programs to execute auxiliaire commands without spawning
+    /// a subprocess. Like for command-line arguments, the first argument is the program name.
+    /// If parsing fails, the returned [`clap::Error`] can be used to report the problem
+    /// (or the help/version information, if requested).
+    ///
+    /// ```no_run
+    /// use auxiliaire::Cli;
+    ///
+    /// # async fn backup() -> auxiliaire::Result<()> {
+    /// let cli = Cli::try_from_args(["auxiliaire", "backup", "--track", "rust", "solutions"])?;
+    /// cli.run().await
+    /// # }
+    /// ```
+    pub fn try_from_args<I, T>(args: I) -> std::result::Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        Self::try_parse_from(args)
+    }
+
+    /// Runs the command specified by this [`Cli`].
+    ///
+    /// This method sets up [`tracing`] support and executes the appropriate command. If a global
+    /// [`tracing`] subscriber has already been installed (for example by a program embedding
+    /// auxiliaire), it is used as-is.
     ///
     /// # Tracing support
     ///
@@ -62,19 +182,88 @@ impl Cli {
     ///   This can also be used to enable tracing for other modules (e.g. setting the environment
     ///   variable to `trace` will print everything, from all crates involved).
     ///
+    /// Colors used in console output (including by [`tracing`]) can be controlled via the
+    /// [`color`](Cli::color) argument.
+    ///
+    /// This method does not check for a newer version of auxiliaire, since this is only relevant
+    /// to users of the `auxiliaire` program; see [`run_with_update_check`](Self::run_with_update_check).
+    ///
+    /// # OpenTelemetry support
+    ///
+    /// When the `otel` feature is enabled, traces can also be exported to an OpenTelemetry collector
+    /// via the `--otel-endpoint` argument.
+    ///
     /// [`env_logger`]: https://docs.rs/env_logger/latest/env_logger/
-    pub async fn execute() -> Result<()> {
-        let cli = Self::parse();
+    pub async fn run(self) -> Result<()> {
+        self.run_impl(false).await
+    }
 
+    /// [Runs](Self::run) the command specified by this [`Cli`], checking for updates as well.
+    ///
+    /// This is used by the `auxiliaire` program. While the command is executed, we check whether
+    /// a newer version of auxiliaire has been published on [crates.io](https://crates.io/crates/auxiliaire)
+    /// and print a hint if so. The result of this check (even if it fails) is cached for a day.
+    /// The check can be disabled via the [`no_update_check`](Cli::no_update_check) flag.
+    pub async fn run_with_update_check(self) -> Result<()> {
+        let check_for_update = self.should_check_for_update();
+        self.run_impl(check_for_update).await
+    }
+
+    async fn run_impl(self, check_for_update: bool) -> Result<()> {
         let default_directive =
-            Directive::from_str(&format!("{}={}", module_path!(), cli.verbose.log_level_filter()))
+            Directive::from_str(&format!("{}={}", module_path!(), self.verbose.log_level_filter()))
                 .expect("default directive should be valid");
         let env_filter = EnvFilter::builder()
             .with_default_directive(default_directive)
             .from_env_lossy();
-        tracing_subscriber::fmt().with_env_filter(env_filter).init();
+        match self.color {
+            ColorChoice::Auto => anstream::ColorChoice::Auto,
+            ColorChoice::Always => anstream::ColorChoice::Always,
+            ColorChoice::Never => anstream::ColorChoice::Never,
+        }
+        .write_global();
+        let ansi = anstream::AutoStream::choice(&io::stdout()) != anstream::ColorChoice::Never;
+
+        let registry = tracing_subscriber::registry()
+            .with(env_filter)
+            .with(fmt::layer().with_ansi(ansi));
+
+        // Ignore failures to install our subscriber: it means that another one is already set.
+        #[cfg(not(feature = "otel"))]
+        let _ = registry.try_init();
+
+        #[cfg(feature = "otel")]
+        let traces_exporter = self
+            .otel_endpoint
+            .as_deref()
+            .map(TracesExporter::new)
+            .transpose()?;
+        #[cfg(feature = "otel")]
+        let _ = registry
+            .with(traces_exporter.as_ref().map(TracesExporter::layer))
+            .try_init();
+
+        let update_check = check_for_update.then(UpdateCheck::spawn);
+
+        let result = self.command.execute().await;
+
+        if let Some(update_check) = update_check {
+            update_check.finish().await;
+        }
 
-        cli.command.execute().await
+        #[cfg(feature = "otel")]
+        if let Some(traces_exporter) = traces_exporter {
+            // Failing to export traces should not affect the outcome of the command itself.
+            if let Err(err) = traces_exporter.shutdown().await {
+                tracing::warn!("{err:#}");
+            }
+        }
+
+        result
+    }
+
+    fn should_check_for_update(&self) -> bool {
+        !self.no_update_check && !matches!(self.command, Command::Man(_))
     }
 }
 
@@ -94,5 +283,37 @@ mod tests {
             // Other tests will take the form of integration tests.
             Cli::command().debug_assert();
         }
+
+        #[test]
+        fn test_try_from_args() {
+            let cli = Cli::try_from_args(["auxiliaire", "size", "--no-update-check", "solutions"])
+                .unwrap();
+            assert!(cli.no_update_check);
+            assert!(matches!(cli.command, Command::Size(_)));
+
+            let err = Cli::try_from_args(["auxiliaire", "unknown"]).unwrap_err();
+            assert_eq!(clap::error::ErrorKind::InvalidSubcommand, err.kind());
+        }
+
+        #[test]
+        fn test_runtime() {
+            let cli =
+                Cli::try_from_args(["auxiliaire", "size", "--worker-threads", "2", "solutions"])
+                    .unwrap();
+            assert_eq!(2, cli.runtime().unwrap().metrics().num_workers());
+
+            let cli = Cli::try_from_args(["auxiliaire", "size", "--single-threaded", "solutions"])
+                .unwrap();
+            assert_eq!(1, cli.runtime().unwrap().metrics().num_workers());
+
+            assert!(Cli::try_from_args([
+                "auxiliaire",
+                "size",
+                "--worker-threads",
+                "0",
+                "solutions"
+            ])
+            .is_err());
+        }
     }
 }
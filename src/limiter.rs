@@ -1,20 +1,182 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::sync::{Mutex as AsyncMutex, OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+use tokio::time::{sleep, Instant};
 
+/// Limits the number of concurrent operations.
+///
+/// A limiter can have a parent (see [`child`](Self::child)); acquiring a permit then also
+/// requires a permit from the parent, so that children cannot exceed their parent's limit.
 #[derive(Debug, Clone)]
-pub struct Limiter(Arc<Semaphore>);
+pub struct Limiter {
+    semaphore: Arc<Semaphore>,
+    parent: Option<Arc<Limiter>>,
+}
 
 #[derive(Debug)]
-pub struct Permit<'a>(#[allow(unused)] SemaphorePermit<'a>);
+pub struct Permit<'a>(
+    // Permits are stored from root to leaf, so that parent permits are released first.
+    #[allow(unused)] Vec<SemaphorePermit<'a>>,
+);
 
 impl Limiter {
     pub fn new(limit: usize) -> Self {
-        Self(Arc::new(Semaphore::new(limit)))
+        Self { semaphore: Arc::new(Semaphore::new(limit)), parent: None }
+    }
+
+    /// Returns a limiter with its own limit that also respects this limiter's limit.
+    ///
+    /// If `limit` is `None`, the returned limiter shares this limiter's permits.
+    pub fn child(&self, limit: Option<usize>) -> Self {
+        match limit {
+            Some(limit) => Self {
+                semaphore: Arc::new(Semaphore::new(limit)),
+                parent: Some(Arc::new(self.clone())),
+            },
+            None => self.clone(),
+        }
     }
 
     pub async fn get_permit(&self) -> Permit<'_> {
-        Permit(self.0.acquire().await.unwrap())
+        // Like for keyed permits, we acquire the child permit first so that tasks waiting
+        // on a child's limit do not hold permits that other children could be using.
+        let mut permits = Vec::new();
+        let mut limiter = Some(self);
+        while let Some(current) = limiter {
+            permits.push(current.semaphore.acquire().await.unwrap());
+            limiter = current.parent.as_deref();
+        }
+        permits.reverse();
+
+        Permit(permits)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyedLimiter {
+    global: Limiter,
+    key_limit: Option<usize>,
+    key_semaphores: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+#[derive(Debug)]
+pub struct KeyedPermit<'a> {
+    // Field order matters: the global permit must be released before the key permit.
+    #[allow(unused)]
+    global_permit: Permit<'a>,
+    #[allow(unused)]
+    key_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl KeyedLimiter {
+    pub fn new(global_limit: usize, key_limit: Option<usize>) -> Self {
+        Self { global: Limiter::new(global_limit), key_limit, key_semaphores: Arc::default() }
+    }
+
+    /// Returns a limiter whose global limit is a [child](Limiter::child) of this limiter's.
+    ///
+    /// Key limits are shared with this limiter.
+    pub fn child(&self, limit: Option<usize>) -> Self {
+        Self {
+            global: self.global.child(limit),
+            key_limit: self.key_limit,
+            key_semaphores: Arc::clone(&self.key_semaphores),
+        }
+    }
+
+    pub async fn get_permit(&self) -> Permit<'_> {
+        self.global.get_permit().await
+    }
+
+    pub async fn get_permit_for(&self, key: &str) -> KeyedPermit<'_> {
+        // We acquire the key permit first so that tasks waiting on their key's
+        // limit do not hold global permits that other keys could be using.
+        let key_permit = match self.key_semaphore(key) {
+            Some(semaphore) => Some(semaphore.acquire_owned().await.unwrap()),
+            None => None,
+        };
+        let global_permit = self.global.get_permit().await;
+
+        KeyedPermit { global_permit, key_permit }
+    }
+
+    /// Returns a [`Limiter`] that respects both the limit of the given key and the global limit.
+    pub fn for_key(&self, key: &str) -> Limiter {
+        match self.key_semaphore(key) {
+            Some(semaphore) => Limiter { semaphore, parent: Some(Arc::new(self.global.clone())) },
+            None => self.global.clone(),
+        }
+    }
+
+    fn key_semaphore(&self, key: &str) -> Option<Arc<Semaphore>> {
+        self.key_limit.map(|key_limit| {
+            let mut key_semaphores = self.key_semaphores.lock().unwrap();
+            Arc::clone(
+                key_semaphores
+                    .entry(key.into())
+                    .or_insert_with(|| Arc::new(Semaphore::new(key_limit))),
+            )
+        })
+    }
+}
+
+/// Token bucket limiting the rate at which requests can be performed.
+///
+/// The bucket holds at most one second's worth of tokens, so that bursts do not
+/// significantly exceed the requested number of requests per minute.
+#[derive(Debug, Clone)]
+pub struct RateLimiter(Option<Arc<AsyncMutex<TokenBucket>>>);
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: Option<u32>) -> Self {
+        Self(
+            requests_per_minute
+                .filter(|&rpm| rpm > 0)
+                .map(|rpm| Arc::new(AsyncMutex::new(TokenBucket::new(rpm)))),
+        )
+    }
+
+    pub async fn acquire(&self) {
+        if let Some(bucket) = &self.0 {
+            // The bucket stays locked while we wait so that requests are served in order.
+            let mut bucket = bucket.lock().await;
+            bucket.refill();
+            if bucket.tokens < 1.0 {
+                sleep(bucket.time_until_next_token()).await;
+                bucket.refill();
+            }
+            bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests_per_minute: u32) -> Self {
+        let tokens_per_second = f64::from(requests_per_minute) / 60.0;
+        let capacity = tokens_per_second.max(1.0);
+
+        Self { capacity, tokens_per_second, tokens: capacity, last_refill: Instant::now() }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.tokens_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    fn time_until_next_token(&self) -> Duration {
+        Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / self.tokens_per_second)
     }
 }
 
@@ -22,9 +184,10 @@ impl Limiter {
 mod tests {
     mod limiter {
         use std::sync::Arc;
+        use std::time::Duration;
 
         use test_log::test;
-        use tokio::task;
+        use tokio::{task, time};
 
         use crate::limiter::Limiter;
 
@@ -41,5 +204,129 @@ mod tests {
             drop(permit);
             assert!(join_handle.await.is_ok());
         }
+
+        #[test(tokio::test)]
+        async fn test_child_permit() {
+            let limiter = Limiter::new(1);
+            let child = limiter.child(Some(2));
+            let other_child = limiter.child(Some(2));
+            let permit = child.get_permit().await;
+
+            // Children cannot exceed their parent's limit.
+            let join_handle = task::spawn(async move {
+                let _permit = other_child.get_permit().await;
+            });
+            time::sleep(Duration::from_millis(10)).await;
+            assert!(!join_handle.is_finished());
+
+            drop(permit);
+            assert!(join_handle.await.is_ok());
+        }
+
+        #[test(tokio::test)]
+        async fn test_child_without_limit() {
+            let limiter = Limiter::new(1);
+            let child = limiter.child(None);
+            let permit = limiter.get_permit().await;
+
+            let join_handle = task::spawn(async move {
+                let _permit = child.get_permit().await;
+            });
+            time::sleep(Duration::from_millis(10)).await;
+            assert!(!join_handle.is_finished());
+
+            drop(permit);
+            assert!(join_handle.await.is_ok());
+        }
+    }
+
+    mod keyed_limiter {
+        use std::time::Duration;
+
+        use test_log::test;
+        use tokio::{task, time};
+
+        use crate::limiter::KeyedLimiter;
+
+        #[test(tokio::test)]
+        async fn test_permit_for_key() {
+            let limiter = KeyedLimiter::new(2, Some(1));
+            let task_limiter = limiter.clone();
+            let permit = limiter.get_permit_for("rust").await;
+
+            // Another key should not be blocked by the first key's limit...
+            let _other_permit = limiter.get_permit_for("clojure").await;
+
+            // ...but the same key should.
+            let join_handle = task::spawn(async move {
+                let _permit = task_limiter.get_permit_for("rust").await;
+            });
+            time::sleep(Duration::from_millis(10)).await;
+            assert!(!join_handle.is_finished());
+
+            drop(permit);
+            assert!(join_handle.await.is_ok());
+        }
+
+        #[test(tokio::test)]
+        async fn test_for_key() {
+            let limiter = KeyedLimiter::new(2, Some(1));
+            let permit = limiter.get_permit_for("rust").await;
+
+            let other_key_limiter = limiter.for_key("clojure");
+            let _other_permit = other_key_limiter.get_permit().await;
+
+            let key_limiter = limiter.for_key("rust");
+            let join_handle = task::spawn(async move {
+                let _permit = key_limiter.get_permit().await;
+            });
+            time::sleep(Duration::from_millis(10)).await;
+            assert!(!join_handle.is_finished());
+
+            drop(permit);
+            assert!(join_handle.await.is_ok());
+        }
+
+        #[test(tokio::test)]
+        async fn test_permit_without_key_limit() {
+            let limiter = KeyedLimiter::new(2, None);
+
+            let _permit = limiter.get_permit_for("rust").await;
+            let _other_permit = limiter.get_permit_for("rust").await;
+        }
+    }
+
+    mod rate_limiter {
+        use std::time::Duration;
+
+        use test_log::test;
+        use tokio::time::Instant;
+
+        use crate::limiter::RateLimiter;
+
+        #[test(tokio::test)]
+        async fn test_acquire() {
+            // 600 requests per minute: bursts of 10 requests, then one request every 100ms.
+            let limiter = RateLimiter::new(Some(600));
+
+            let start = Instant::now();
+            for _ in 0..10 {
+                limiter.acquire().await;
+            }
+            assert!(start.elapsed() < Duration::from_millis(100));
+
+            limiter.acquire().await;
+            limiter.acquire().await;
+            assert!(start.elapsed() >= Duration::from_millis(190));
+        }
+
+        #[test(tokio::test)]
+        async fn test_acquire_without_limit() {
+            let limiter = RateLimiter::new(None);
+
+            for _ in 0..1000 {
+                limiter.acquire().await;
+            }
+        }
     }
 }
@@ -1,11 +1,23 @@
 //! Main [`auxiliaire`] program entry point.
 //!
-//! Simply delegates to the auxiliaire [`Cli`] wrapper.
+//! Simply delegates to the auxiliaire [`Cli`] wrapper, running it in a [`tokio`] runtime
+//! configured via its arguments (see [`Cli::runtime`]) and checking for updates
+//! (see [`Cli::run_with_update_check`]).
 
+use std::process::exit;
+
+use auxiliaire::error::{Interrupted, INTERRUPTED_EXIT_CODE};
 use auxiliaire::Cli;
+use clap::Parser;
 
 /// Main program entry point.
-#[tokio::main]
-async fn main() -> auxiliaire::Result<()> {
-    Cli::execute().await
+fn main() -> auxiliaire::Result<()> {
+    let cli = Cli::parse();
+    match cli.runtime()?.block_on(cli.run_with_update_check()) {
+        Err(err) if err.is::<Interrupted>() => {
+            eprintln!("Error: {err:?}");
+            exit(INTERRUPTED_EXIT_CODE);
+        },
+        result => result,
+    }
 }
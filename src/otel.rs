@@ -0,0 +1,50 @@
+use anyhow::Context;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::{runtime, Resource};
+use tokio::task::spawn_blocking;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::Result;
+
+#[derive(Debug)]
+pub struct TracesExporter {
+    provider: TracerProvider,
+}
+
+impl TracesExporter {
+    pub fn new(endpoint: &str) -> Result<Self> {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()
+            .with_context(|| format!("failed to create OTLP exporter for endpoint {endpoint}"))?;
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new([
+                KeyValue::new("service.name", env!("CARGO_PKG_NAME")),
+                KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            ]))
+            .build();
+
+        Ok(Self { provider })
+    }
+
+    pub fn layer<S>(&self) -> OpenTelemetryLayer<S, Tracer>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(self.provider.tracer(env!("CARGO_PKG_NAME")))
+    }
+
+    pub async fn shutdown(self) -> Result<()> {
+        // Shutting down flushes pending spans, which blocks the current thread.
+        spawn_blocking(move || self.provider.shutdown())
+            .await?
+            .with_context(|| "failed to flush traces to OTLP endpoint")
+    }
+}
@@ -0,0 +1,11 @@
+use anstyle::{AnsiColor, Color, Style};
+
+pub const HEADER: Style = Style::new().bold().underline();
+pub const NAME: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Cyan)));
+pub const STALE: Style = Style::new()
+    .bold()
+    .fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+pub const SIZE: Style = Style::new().bold();
+pub const MATCH: Style = Style::new()
+    .bold()
+    .fg_color(Some(Color::Ansi(AnsiColor::Red)));
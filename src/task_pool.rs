@@ -1,16 +1,32 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::future::Future;
 use std::panic::resume_unwind;
 
 use anyhow::Context;
-use tokio::task::{AbortHandle, JoinSet};
+use tokio::task::{AbortHandle, Id, JoinError, JoinSet};
 
 use crate::error::MultiError;
-use crate::Result;
+use crate::limiter::Limiter;
+use crate::{Error, Result};
 
 #[derive(Debug, Default)]
 pub struct TaskPool {
     join_set: JoinSet<Result<()>>,
+    limiter: Option<Limiter>,
+    max_tasks: Option<usize>,
+    labels: HashMap<Id, String>,
+
+    /// Errors of tasks that completed before the pool was joined (see [`spawn_bounded`](Self::spawn_bounded)).
+    errors: Vec<(Option<String>, Error)>,
+    completed: usize,
+}
+
+/// Progress of a [`TaskPool`] being [joined](TaskPool::join_with_progress).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub completed: usize,
+    pub remaining: usize,
 }
 
 impl TaskPool {
@@ -18,11 +34,76 @@ impl TaskPool {
         Self::default()
     }
 
-    pub fn spawn<F>(&mut self, task: F) -> AbortHandle
+    /// Creates a pool whose tasks spawned via [`spawn_limited`](Self::spawn_limited)
+    /// will need to acquire a permit from the given [`Limiter`] before running.
+    pub fn with_limiter(limiter: Limiter) -> Self {
+        Self { limiter: Some(limiter), ..Self::default() }
+    }
+
+    /// Creates a pool that holds at most `max_tasks` tasks at once when spawning them
+    /// via [`spawn_bounded`](Self::spawn_bounded).
+    pub fn with_max_tasks(max_tasks: usize) -> Self {
+        Self { max_tasks: Some(max_tasks.max(1)), ..Self::default() }
+    }
+
+    /// Spawns a task in the pool.
+    ///
+    /// The task's `label` (e.g. the name of the solution or file it processes) is used
+    /// to identify the task if it fails.
+    pub fn spawn<L, F>(&mut self, label: L, task: F) -> AbortHandle
     where
+        L: Into<String>,
         F: Future<Output = Result<()>> + Send + 'static,
     {
-        self.join_set.spawn(task)
+        let abort_handle = self.join_set.spawn(task);
+        self.labels.insert(abort_handle.id(), label.into());
+        abort_handle
+    }
+
+    /// Spawns a task that will only start running once it acquires a permit from the pool's
+    /// [`Limiter`]. The permit is held until the task completes.
+    ///
+    /// This is meant for leaf tasks that need a permit for their whole duration. Tasks that
+    /// spawn other limited tasks, or that share a pool with tasks needing permits from other
+    /// limiters, should instead acquire permits around the individual operations that need
+    /// them; holding a permit for their whole duration could starve (or deadlock) their subtasks.
+    ///
+    /// If the pool has no limiter, this is equivalent to [`spawn`](Self::spawn).
+    pub fn spawn_limited<L, F>(&mut self, label: L, task: F) -> AbortHandle
+    where
+        L: Into<String>,
+        F: Future<Output = Result<()>> + Send + 'static,
+    {
+        match self.limiter.clone() {
+            Some(limiter) => self.spawn(label, async move {
+                let _permit = limiter.get_permit().await;
+                task.await
+            }),
+            None => self.spawn(label, task),
+        }
+    }
+
+    /// Spawns a task in the pool, first waiting for running tasks to complete if the pool
+    /// already holds its [maximum number of tasks](Self::with_max_tasks).
+    ///
+    /// This makes it possible to avoid creating a large number of tasks (and the data they own)
+    /// up front when they cannot all run at once anyway. Errors of tasks that complete
+    /// while waiting are reported when the pool is [joined](Self::join).
+    pub async fn spawn_bounded<L, F>(&mut self, label: L, task: F) -> AbortHandle
+    where
+        L: Into<String>,
+        F: Future<Output = Result<()>> + Send + 'static,
+    {
+        if let Some(max_tasks) = self.max_tasks {
+            while self.join_set.len() >= max_tasks {
+                match self.join_set.join_next_with_id().await {
+                    Some(join_result) => self.task_completed(join_result),
+                    None => break,
+                }
+            }
+        }
+
+        self.spawn(label, task)
     }
 
     pub async fn join<C, F>(&mut self, context: F) -> Result<()>
@@ -30,30 +111,62 @@ impl TaskPool {
         F: FnOnce() -> C,
         C: Display + Send + Sync + 'static,
     {
-        let mut errors = Vec::new();
+        self.join_with_progress(context, |_| ()).await
+    }
+
+    /// Waits for all tasks in the pool to complete, calling `on_progress` each time a task completes.
+    pub async fn join_with_progress<C, F, P>(
+        &mut self,
+        context: F,
+        mut on_progress: P,
+    ) -> Result<()>
+    where
+        F: FnOnce() -> C,
+        C: Display + Send + Sync + 'static,
+        P: FnMut(Progress),
+    {
+        while let Some(join_result) = self.join_set.join_next_with_id().await {
+            self.task_completed(join_result);
+            on_progress(Progress { completed: self.completed, remaining: self.join_set.len() });
+        }
+
+        self.completed = 0;
+        MultiError::check_labeled(std::mem::take(&mut self.errors), context)
+    }
 
-        while let Some(join_result) = self.join_set.join_next().await {
-            match join_result {
-                Ok(Ok(_)) => (),
-                Ok(Err(task_error)) => errors.push(task_error),
-                Err(join_error) => match join_error.try_into_panic() {
+    fn task_completed(&mut self, join_result: std::result::Result<(Id, Result<()>), JoinError>) {
+        let (id, error) = match join_result {
+            Ok((id, Ok(_))) => (id, None),
+            Ok((id, Err(task_error))) => (id, Some(task_error)),
+            Err(join_error) => {
+                let id = join_error.id();
+                match join_error.try_into_panic() {
                     Ok(panic_err) => resume_unwind(panic_err),
-                    Err(join_error) => errors.push(
-                        Err::<(), _>(join_error)
-                            .with_context(|| "join error")
-                            .unwrap_err(),
+                    Err(join_error) => (
+                        id,
+                        Some(
+                            Err::<(), _>(join_error)
+                                .with_context(|| "join error")
+                                .unwrap_err(),
+                        ),
                     ),
-                },
-            }
+                }
+            },
+        };
+
+        let label = self.labels.remove(&id);
+        if let Some(error) = error {
+            self.errors.push((label, error));
         }
 
-        MultiError::check(errors, context)
+        self.completed += 1;
     }
 }
 
 //noinspection DuplicatedCode
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -69,7 +182,6 @@ mod tests {
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     use super::*;
-    use crate::limiter::Limiter;
 
     async fn get_mock_server() -> MockServer {
         let mock_server = MockServer::start().await;
@@ -86,11 +198,9 @@ mod tests {
     #[test(tokio::test)]
     async fn test_one_download() {
         let mock_server = get_mock_server().await;
-        let mut task_pool = TaskPool::new();
-        let limiter = Limiter::new(1);
+        let mut task_pool = TaskPool::with_limiter(Limiter::new(1));
 
-        task_pool.spawn(async move {
-            let _permit = limiter.get_permit();
+        task_pool.spawn_limited("download", async move {
             let result = get(format!("{}/", mock_server.uri())).await;
             assert_matches!(result, Ok(response) if response.status().is_success());
             Ok(())
@@ -102,14 +212,11 @@ mod tests {
     #[test(tokio::test)]
     async fn test_multiple_downloads_no_limit() {
         let mock_server = get_mock_server().await;
-        let mut task_pool = TaskPool::new();
-        let limiter = Limiter::new(100);
+        let mut task_pool = TaskPool::with_limiter(Limiter::new(100));
 
         for _ in 0..10 {
             let uri = mock_server.uri();
-            let limiter = limiter.clone();
-            task_pool.spawn(async move {
-                let _permit = limiter.get_permit();
+            task_pool.spawn_limited("download", async move {
                 let result = get(format!("{}/", uri)).await;
                 assert_matches!(result, Ok(response) if response.status().is_success());
                 Ok(())
@@ -122,14 +229,11 @@ mod tests {
     #[test(tokio::test)]
     async fn test_multiple_downloads_with_limit() {
         let mock_server = get_mock_server().await;
-        let mut task_pool = TaskPool::new();
-        let limiter = Limiter::new(2);
+        let mut task_pool = TaskPool::with_limiter(Limiter::new(2));
 
         for _ in 0..10 {
             let uri = mock_server.uri();
-            let limiter = limiter.clone();
-            task_pool.spawn(async move {
-                let _permit = limiter.get_permit();
+            task_pool.spawn_limited("download", async move {
                 let result = get(format!("{}/", uri)).await;
                 assert_matches!(result, Ok(response) if response.status().is_success());
                 Ok(())
@@ -142,14 +246,11 @@ mod tests {
     #[test(tokio::test)]
     async fn test_errors() {
         let mock_server = get_mock_server().await;
-        let mut task_pool = TaskPool::new();
-        let limiter = Limiter::new(100);
+        let mut task_pool = TaskPool::with_limiter(Limiter::new(100));
 
         for i in 0..10 {
             let uri = mock_server.uri();
-            let limiter = limiter.clone();
-            task_pool.spawn(async move {
-                let _permit = limiter.get_permit();
+            task_pool.spawn_limited(format!("download {i}"), async move {
                 get(if i % 2 == 0 { format!("{}/", uri) } else { format!("{}/doesnotexist", uri) })
                     .await
                     .with_context(|| "download error")?
@@ -159,7 +260,79 @@ mod tests {
             });
         }
 
-        assert!(task_pool.join(|| "error occurred").await.is_err());
+        let mut progress = Vec::new();
+        let result = task_pool
+            .join_with_progress(|| "error occurred", |p| progress.push(p))
+            .await;
+        assert_eq!(10, progress.len());
+        assert_eq!(Some(&Progress { completed: 10, remaining: 0 }), progress.last());
+
+        assert_matches!(result, Err(err) => {
+            assert_matches!(err.source(), Some(err) => {
+                assert_matches!(err.downcast_ref::<MultiError>(), Some(multi_err) => {
+                    let labels = multi_err.labels().iter().flatten().sorted().collect_vec();
+                    assert_eq!(vec!["download 1", "download 3", "download 5", "download 7", "download 9"], labels);
+                });
+            });
+        });
+    }
+
+    #[test(tokio::test)]
+    async fn test_spawn_limited() {
+        let mutex = Arc::new(Mutex::new(()));
+        let task_mutex = Arc::clone(&mutex);
+        let limiter = Limiter::new(1);
+        let mut task_pool = TaskPool::with_limiter(limiter.clone());
+
+        // Hold the only permit; the task should not start until it is released.
+        let permit = limiter.get_permit().await;
+        let lock = mutex.lock().await;
+        task_pool.spawn_limited("task", async move {
+            let _lock = task_mutex
+                .try_lock()
+                .map_err(|_| anyhow::anyhow!("task started too early"))?;
+            Ok(())
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(lock);
+        drop(permit);
+
+        assert!(task_pool.join(|| "should not happen").await.is_ok());
+    }
+
+    #[test(tokio::test)]
+    async fn test_spawn_bounded() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let mut task_pool = TaskPool::with_max_tasks(2);
+
+        for i in 0..10 {
+            let running = Arc::clone(&running);
+            let max_running = Arc::clone(&max_running);
+            task_pool
+                .spawn_bounded(format!("task {i}"), async move {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+
+                    match i {
+                        0 => Err(anyhow::anyhow!("first task failed")),
+                        _ => Ok(()),
+                    }
+                })
+                .await;
+        }
+
+        let result = task_pool.join(|| "error occurred").await;
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+        assert_matches!(result, Err(err) => {
+            assert_matches!(err.source(), Some(err) => {
+                assert_matches!(err.downcast_ref::<MultiError>(), Some(multi_err) => {
+                    assert_eq!(&[Some("task 0".to_string())], multi_err.labels());
+                });
+            });
+        });
     }
 
     #[test(tokio::test)]
@@ -167,7 +340,7 @@ mod tests {
     async fn test_panic() {
         let mut task_pool = TaskPool::new();
 
-        task_pool.spawn(async {
+        task_pool.spawn("panic", async {
             panic!("foo");
         });
         let _ = task_pool.join(|| "baz").await;
@@ -181,7 +354,7 @@ mod tests {
         let _lock = mutex.lock().await;
 
         let mut task_pool = TaskPool::new();
-        let abort_handle = task_pool.spawn(async move {
+        let abort_handle = task_pool.spawn("abort", async move {
             let _lock = task_mutex.lock().await;
             unreachable!("should be cancelled before we reach this point");
         });
@@ -193,6 +366,7 @@ mod tests {
                 assert_matches!(err.downcast_ref::<MultiError>(), Some(multi_err) => {
                     assert!(!multi_err.to_string().is_empty());
 
+                    assert_eq!(&[Some("abort".to_string())], multi_err.labels());
                     assert_matches!(multi_err.errors().iter().exactly_one(), Ok(err) => {
                         assert_eq!("join error", err.to_string());
 
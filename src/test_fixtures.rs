@@ -0,0 +1,68 @@
+//! Fixtures shared by the tests of multiple commands.
+
+use mini_exercism::api::v2::solution::Solution;
+use serde_json::{json, Value};
+
+/// Returns the JSON representation of a solution to the given exercise, as returned by
+/// the Exercism API v2.
+///
+/// The solution is published and has a single iteration. Fields specified in `overrides`
+/// (which must be a JSON object, e.g. `json!({ "status": "iterated" })`) replace the default ones.
+pub fn solution_json(track: &str, exercise: &str, overrides: Value) -> Value {
+    let mut solution = json!({
+        "uuid": format!("{track}-{exercise}"),
+        "private_url": format!("https://exercism.org/tracks/{track}/exercises/{exercise}"),
+        "public_url": format!("https://exercism.org/tracks/{track}/exercises/{exercise}/solutions/clechasseur"),
+        "status": "published",
+        "mentoring_status": "none",
+        "published_iteration_head_tests_status": "passed",
+        "has_notifications": false,
+        "num_views": 0,
+        "num_stars": 0,
+        "num_comments": 0,
+        "num_iterations": 1,
+        "num_loc": 26,
+        "is_out_of_date": false,
+        "published_at": null,
+        "completed_at": null,
+        "updated_at": "2023-05-07T05:35:43Z",
+        "last_iterated_at": "2023-05-07T05:35:43Z",
+        "exercise": { "slug": exercise, "title": exercise, "icon_url": "" },
+        "track": { "slug": track, "title": track, "icon_url": "" },
+    });
+
+    match (&mut solution, overrides) {
+        (Value::Object(solution), Value::Object(overrides)) => solution.extend(overrides),
+        (_, overrides) => panic!("solution overrides should be a JSON object, got {overrides}"),
+    }
+    solution
+}
+
+/// Returns a solution to the given exercise; see [`solution_json`] for details.
+pub fn solution(track: &str, exercise: &str, overrides: Value) -> Solution {
+    serde_json::from_value(solution_json(track, exercise, overrides))
+        .expect("solution fixture should be a valid solution")
+}
+
+/// Returns the JSON representation of the iteration with the given index, as returned by
+/// the Exercism API v2.
+///
+/// The iteration's submission UUID is `submission-<index>`.
+pub fn iteration_json(index: i32, is_published: bool) -> Value {
+    json!({
+        "uuid": format!("iteration-{index}"),
+        "submission_uuid": format!("submission-{index}"),
+        "idx": index,
+        "status": "no_automated_feedback",
+        "num_essential_automated_comments": 0,
+        "num_actionable_automated_comments": 0,
+        "num_non_actionable_automated_comments": 0,
+        "num_celebratory_automated_comments": 0,
+        "submission_method": "cli",
+        "created_at": format!("2023-05-0{index}T05:35:43Z"),
+        "tests_status": "passed",
+        "is_published": is_published,
+        "is_latest": is_published,
+        "links": { "self": "", "solution": "" },
+    })
+}
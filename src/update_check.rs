@@ -0,0 +1,265 @@
+use std::env;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use mini_exercism::http;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tokio::{fs, time};
+use tracing::{debug, info, instrument, trace};
+
+use crate::{Result, USER_AGENT};
+
+pub const NO_UPDATE_CHECK_ENV_VAR_NAME: &str = "AUXILIAIRE_NO_UPDATE_CHECK";
+
+const CRATES_IO_URL: &str = "https://crates.io/api/v1/crates/auxiliaire";
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+const JOIN_TIMEOUT: Duration = Duration::from_millis(500);
+const CACHE_FILE_NAME: &str = "update_check.json";
+
+/// Update check running in the background while a command is executed.
+pub struct UpdateCheck(JoinHandle<()>);
+
+impl UpdateCheck {
+    /// Starts checking whether a newer version of auxiliaire has been released
+    /// (see [`notify_if_outdated`]), without waiting for the check to complete.
+    pub fn spawn() -> Self {
+        Self(tokio::spawn(notify_if_outdated()))
+    }
+
+    /// Waits for the check to complete.
+    ///
+    /// Since the check runs concurrently with the command, it is usually complete by then; if not,
+    /// we do not make the user wait more than [`JOIN_TIMEOUT`] and the check is abandoned.
+    pub async fn finish(self) {
+        let mut handle = self.0;
+        if time::timeout(JOIN_TIMEOUT, &mut handle).await.is_err() {
+            debug!("Update check still running after command, abandoning it");
+            handle.abort();
+        }
+    }
+}
+
+/// Checks if a newer version of auxiliaire has been released and prints a hint if so.
+///
+/// The result of the check is cached so that crates.io is queried at most once a day; this
+/// includes failed checks, so that an unreachable crates.io does not slow down every command.
+/// Errors are never reported to the user, since they should not affect the command being run.
+#[instrument(level = "debug")]
+async fn notify_if_outdated() {
+    let cache_file_path = cache_dir().map(|dir| dir.join(CACHE_FILE_NAME));
+
+    match get_latest_version(cache_file_path.as_deref(), fetch_latest_version()).await {
+        Ok(Some(latest)) => {
+            let current = current_version();
+            trace!(%current, %latest);
+
+            if latest > current {
+                info!(
+                    "A new version of auxiliaire is available: {latest} (current version: {current}); set {NO_UPDATE_CHECK_ENV_VAR_NAME}=true or use --no-update-check to disable this check"
+                );
+            }
+        },
+        Ok(None) => trace!("Latest version is unknown"),
+        Err(err) => debug!("Failed to check for new version: {err:#}"),
+    }
+}
+
+fn current_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("package version should be valid semver")
+}
+
+/// Returns the latest version of auxiliaire, using the given cache file if possible, otherwise
+/// using `fetch` (which is only awaited if needed).
+///
+/// Returns `None` if the version could not be fetched, either now or during the last check.
+async fn get_latest_version<F>(cache_file_path: Option<&Path>, fetch: F) -> Result<Option<Version>>
+where
+    F: Future<Output = Result<Version>>,
+{
+    if let Some(cache_file_path) = cache_file_path {
+        let cached = fs::read_to_string(cache_file_path)
+            .await
+            .ok()
+            .and_then(|cached| serde_json::from_str::<CachedCheck>(&cached).ok());
+        if let Some(cached) = cached.filter(|cached| !cached.is_stale(SystemTime::now())) {
+            trace!("Using cached version check result");
+            return Ok(cached.latest_version);
+        }
+    }
+
+    let latest_version = match time::timeout(CHECK_TIMEOUT, fetch).await {
+        Ok(Ok(latest_version)) => Some(latest_version),
+        Ok(Err(err)) => {
+            debug!("Failed to check for new version: {err:#}");
+            None
+        },
+        Err(_) => {
+            debug!("Timed out while checking for new version");
+            None
+        },
+    };
+
+    if let Some(cache_file_path) = cache_file_path {
+        let cached = CachedCheck { checked_at: unix_time(SystemTime::now()), latest_version };
+        if let Some(parent) = cache_file_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(cache_file_path, serde_json::to_string(&cached)?).await?;
+        return Ok(cached.latest_version);
+    }
+
+    Ok(latest_version)
+}
+
+async fn fetch_latest_version() -> Result<Version> {
+    let response = http::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()?
+        .get(CRATES_IO_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<CrateResponse>()
+        .await
+        .with_context(|| "failed to parse crates.io response")?;
+
+    Ok(response.krate.max_stable_version)
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Caches"))
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+
+    base.map(|base| base.join(env!("CARGO_PKG_NAME")))
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedCheck {
+    checked_at: u64,
+    /// Latest version found, or `None` if the check failed.
+    latest_version: Option<Version>,
+}
+
+impl CachedCheck {
+    fn is_stale(&self, now: SystemTime) -> bool {
+        unix_time(now).saturating_sub(self.checked_at) >= CHECK_INTERVAL.as_secs()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: Crate,
+}
+
+#[derive(Debug, Deserialize)]
+struct Crate {
+    max_stable_version: Version,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod cached_check {
+        use super::*;
+
+        #[test]
+        fn test_is_stale() {
+            let now = SystemTime::now();
+            let check = |age: Duration| CachedCheck {
+                checked_at: unix_time(now - age),
+                latest_version: Some(current_version()),
+            };
+
+            assert!(!check(Duration::ZERO).is_stale(now));
+            assert!(!check(Duration::from_secs(60 * 60)).is_stale(now));
+            assert!(check(CHECK_INTERVAL).is_stale(now));
+            assert!(check(CHECK_INTERVAL * 2).is_stale(now));
+        }
+    }
+
+    mod get_latest_version {
+        use anyhow::anyhow;
+        use test_log::test;
+
+        use super::*;
+
+        async fn unexpected_fetch() -> Result<Version> {
+            panic!("cached check result should have been used");
+        }
+
+        #[test(tokio::test)]
+        async fn test_caches_version() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let cache_file_path = temp_dir.path().join(CACHE_FILE_NAME);
+            let latest = Version::new(99, 0, 0);
+
+            let version = get_latest_version(Some(&cache_file_path), async { Ok(latest.clone()) })
+                .await
+                .unwrap();
+            assert_eq!(Some(latest.clone()), version);
+
+            let version = get_latest_version(Some(&cache_file_path), unexpected_fetch())
+                .await
+                .unwrap();
+            assert_eq!(Some(latest), version);
+        }
+
+        #[test(tokio::test)]
+        async fn test_caches_failure() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let cache_file_path = temp_dir.path().join(CACHE_FILE_NAME);
+
+            let version = get_latest_version(Some(&cache_file_path), async {
+                Err(anyhow!("crates.io is unreachable"))
+            })
+            .await
+            .unwrap();
+            assert_eq!(None, version);
+
+            let version = get_latest_version(Some(&cache_file_path), unexpected_fetch())
+                .await
+                .unwrap();
+            assert_eq!(None, version);
+        }
+    }
+
+    mod crate_response {
+        use super::*;
+
+        #[test]
+        fn test_deserialize() {
+            let json = r#"{
+                "crate": {
+                    "id": "auxiliaire",
+                    "name": "auxiliaire",
+                    "max_version": "0.3.0-beta.1",
+                    "max_stable_version": "0.2.2"
+                }
+            }"#;
+
+            let response: CrateResponse = serde_json::from_str(json).unwrap();
+            assert_eq!(Version::new(0, 2, 2), response.krate.max_stable_version);
+        }
+    }
+}
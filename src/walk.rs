@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use tokio::fs;
+
+use crate::command::backup::ARCHIVE_DIR_NAME;
+use crate::command::community::COMMUNITY_DIR_NAME;
+use crate::Result;
+
+/// Returns the non-hidden subdirectories of the given directory, along with their names, sorted by name.
+///
+/// In a backup directory, this can be used to list tracks, or exercises in a track. The directories
+/// where community solutions (see [`COMMUNITY_DIR_NAME`]) and archived solutions
+/// (see [`ARCHIVE_DIR_NAME`]) are stored are not returned.
+pub async fn subdirectories(path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut entries = fs::read_dir(path)
+        .await
+        .with_context(|| format!("failed to list content of directory {}", path.display()))?;
+
+    let mut subdirectories = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().into_string().ok();
+        match name {
+            Some(name)
+                if !name.starts_with('.')
+                    && name != COMMUNITY_DIR_NAME
+                    && name != ARCHIVE_DIR_NAME
+                    && entry.file_type().await?.is_dir() =>
+            {
+                subdirectories.push((name, entry.path()));
+            },
+            _ => (),
+        }
+    }
+
+    subdirectories.sort_unstable();
+    Ok(subdirectories)
+}
+
+/// Returns all files found in the given directory and its subdirectories, sorted by path.
+///
+/// Directories for which `skip_dir` returns `true` are not visited.
+pub async fn files<F>(path: &Path, skip_dir: F) -> Result<Vec<PathBuf>>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut files = Vec::new();
+    let mut to_visit = vec![path.to_path_buf()];
+
+    while let Some(dir_path) = to_visit.pop() {
+        let mut entries = fs::read_dir(&dir_path).await.with_context(|| {
+            format!("failed to list content of directory {}", dir_path.display())
+        })?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_path = entry.path();
+            let file_type = entry.file_type().await?;
+
+            if file_type.is_dir() {
+                if !skip_dir(&entry_path) {
+                    to_visit.push(entry_path);
+                }
+            } else if file_type.is_file() {
+                files.push(entry_path);
+            }
+        }
+    }
+
+    files.sort_unstable();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_backup_path(part: &str) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "resources", "tests", part]
+            .iter()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_subdirectories() {
+        let path = test_backup_path("with_backup_state");
+
+        let tracks = subdirectories(&path).await.unwrap();
+        assert_eq!(vec!["rust"], tracks.iter().map(|(name, _)| name).collect::<Vec<_>>());
+
+        let exercises = subdirectories(&tracks[0].1).await.unwrap();
+        assert_eq!(vec!["poker"], exercises.iter().map(|(name, _)| name).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_files() {
+        let path = test_backup_path("with_backup_state");
+
+        let all_files = files(&path, |_| false).await.unwrap();
+        assert_eq!(1, all_files.len());
+        assert!(all_files[0].ends_with("backup_state.json"));
+
+        let no_hidden_files = files(&path, |dir| {
+            dir.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .await
+        .unwrap();
+        assert!(no_hidden_files.is_empty());
+    }
+}